@@ -225,6 +225,11 @@ fn jobs() -> Yaml {
         LINUX,
         Features::Specific(&["with-bytes"]),
     ));
+    r.push(job(
+        RustToolchain::Stable,
+        LINUX,
+        Features::Specific(&["with-smallvec"]),
+    ));
     r.push(job(RustToolchain::Nightly, LINUX, Features::All));
 
     r.push(job(RustToolchain::Stable, WINDOWS, Features::Default));
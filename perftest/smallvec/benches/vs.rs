@@ -0,0 +1,69 @@
+// `cargo test --benches` and `#[feature(test)]` work only in nightly
+#![cfg(rustc_nightly)]
+#![cfg(feature = "smallvec")]
+#![feature(test)]
+
+extern crate test;
+
+extern crate protobuf;
+
+extern crate perftest_smallvec;
+
+use perftest_smallvec::messages;
+use protobuf::Message;
+
+/// Message with 1000 items, each item has `len` values and tags.
+fn make_items(len: usize) -> Vec<u8> {
+    let mut m = messages::TestMessage::new();
+
+    for i in 0..1000 {
+        let mut item = messages::Item::new();
+        for j in 0..len {
+            item.values.push((i * j) as i32);
+            item.tags.push(format!("t{}", j));
+        }
+        m.items.push(item);
+    }
+
+    m.write_to_bytes().expect("write")
+}
+
+// With up to 4 elements `SmallVec` does not allocate for `values` and `tags`.
+
+#[bench]
+fn parse_items_1_vec(b: &mut test::Bencher) {
+    let bs = make_items(1);
+    b.iter(|| messages::TestMessage::parse_from_bytes(&bs).expect("parse"))
+}
+
+#[bench]
+fn parse_items_1_smallvec(b: &mut test::Bencher) {
+    let bs = make_items(1);
+    b.iter(|| messages::TestMessageWithSmallVec::parse_from_bytes(&bs).expect("parse"))
+}
+
+#[bench]
+fn parse_items_4_vec(b: &mut test::Bencher) {
+    let bs = make_items(4);
+    b.iter(|| messages::TestMessage::parse_from_bytes(&bs).expect("parse"))
+}
+
+#[bench]
+fn parse_items_4_smallvec(b: &mut test::Bencher) {
+    let bs = make_items(4);
+    b.iter(|| messages::TestMessageWithSmallVec::parse_from_bytes(&bs).expect("parse"))
+}
+
+// With more elements `SmallVec` spills to heap like `Vec`.
+
+#[bench]
+fn parse_items_20_vec(b: &mut test::Bencher) {
+    let bs = make_items(20);
+    b.iter(|| messages::TestMessage::parse_from_bytes(&bs).expect("parse"))
+}
+
+#[bench]
+fn parse_items_20_smallvec(b: &mut test::Bencher) {
+    let bs = make_items(20);
+    b.iter(|| messages::TestMessageWithSmallVec::parse_from_bytes(&bs).expect("parse"))
+}
@@ -0,0 +1,7 @@
+#![cfg(feature = "smallvec")]
+
+extern crate protobuf;
+
+extern crate smallvec;
+
+pub mod messages;
@@ -6,6 +6,8 @@ extern crate protobuf_test_common;
 
 #[cfg(feature = "with-bytes")]
 extern crate bytes;
+#[cfg(feature = "with-smallvec")]
+extern crate smallvec;
 
 #[cfg(feature = "with-serde")]
 extern crate serde;
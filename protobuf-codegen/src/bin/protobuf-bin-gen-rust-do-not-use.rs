@@ -25,6 +25,7 @@ fn write_file(bin: &str) -> anyhow::Result<()> {
         &file_names,
         Path::new("."),
         &Default::default(),
+        None,
     )?;
     Ok(())
 }
@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::io;
+
+/// Underlying error of [`CodegenError`] variant.
+type Cause = Box<dyn Error + Send + Sync>;
+
+/// Error returned by [`Codegen::run`](crate::Codegen::run)
+/// and [`generate_from_str`](crate::generate_from_str).
+///
+/// Error implements `std::error::Error + Send + Sync`,
+/// so it can be converted to `anyhow::Error` with `?`.
+///
+/// Underlying error is available as [`source`](Error::source),
+/// so the complete message is printed by error reporters
+/// like `anyhow` with `{:#}` format.
+#[derive(Debug, thiserror::Error)]
+pub enum CodegenError {
+    /// `protoc` command is not found or cannot be executed.
+    ///
+    /// When this error is returned, pure Rust parser can be used instead.
+    #[error("protoc command is not found or not working")]
+    ProtocNotFound(#[source] Cause),
+    /// `protoc` command failed to parse `.proto` files or produced invalid output.
+    #[error("protoc failed")]
+    ProtocFailed(#[source] Cause),
+    /// Pure Rust parser failed to parse `.proto` files.
+    #[error("failed to parse .proto files")]
+    ParseFailed(#[source] Cause),
+    /// Input file is not located in any of include directories.
+    #[error("input file `{0}` is not found in includes {1}")]
+    InputNotInIncludes(String, String),
+    /// [`include_archive`](crate::Codegen::include_archive) is used with `protoc` parser.
+    #[error("include archives are supported only by pure parser")]
+    IncludeArchiveRequiresPure,
+    /// [`cargo_out_dir`](crate::Codegen::cargo_out_dir) is used,
+    /// but `OUT_DIR` environment variable is not set.
+    #[error("OUT_DIR environment variable is not set, cargo_out_dir can be used only from build scripts")]
+    OutDirNotSet,
+    /// Code generation or writing generated files failed.
+    #[error("failed to generate code")]
+    GenFailed(#[source] Cause),
+    /// I/O error, for example, when creating temporary files.
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+}
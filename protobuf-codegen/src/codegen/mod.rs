@@ -1,15 +1,24 @@
+use std::env;
 use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process;
 
 use ::protoc::Protoc;
 
+use crate::gen::mod_rs::gen_include_rs;
+use crate::gen::paths::proto_path_to_rust_mod;
 use crate::gen_and_write::gen_and_write;
 use crate::Customize;
+mod error;
 mod protoc;
 mod pure;
 
+pub use error::CodegenError;
+
 #[derive(Debug)]
 enum WhichParser {
     Pure,
@@ -29,16 +38,40 @@ pub struct Codegen {
     which_parser: WhichParser,
     /// --lang_out= param
     out_dir: PathBuf,
+    /// Module name of the file including generated code from `$OUT_DIR`
+    cargo_out_dir: Option<String>,
     /// -I args
     includes: Vec<PathBuf>,
+    /// Tar archives used as include directories
+    include_archives: Vec<PathBuf>,
     /// List of .proto files to compile
     inputs: Vec<PathBuf>,
     /// Customize code generation
     customize: Customize,
     /// Protoc command path
     protoc: Option<Protoc>,
+    /// Callback to find protoc command path
+    protoc_resolver: Option<ProtocResolver>,
     /// Extra `protoc` args
     extra_args: Vec<OsString>,
+    /// Callback to rewrite generated files before writing
+    post_process: Option<PostProcess>,
+}
+
+struct PostProcess(Box<dyn Fn(&Path, String) -> String>);
+
+impl fmt::Debug for PostProcess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostProcess").finish_non_exhaustive()
+    }
+}
+
+struct ProtocResolver(Box<dyn Fn() -> io::Result<PathBuf>>);
+
+impl fmt::Debug for ProtocResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProtocResolver").finish_non_exhaustive()
+    }
 }
 
 impl Codegen {
@@ -69,6 +102,39 @@ impl Codegen {
         self
     }
 
+    /// Generate code into cargo `$OUT_DIR` and write a single file to include.
+    ///
+    /// Generated files are written to `$OUT_DIR/<module_name>` directory
+    /// (created if needed), and `$OUT_DIR/<module_name>.rs` file is written
+    /// which declares `pub mod <module_name>` containing all generated modules.
+    ///
+    /// This overrides [`out_dir`](Self::out_dir). `OUT_DIR` environment variable
+    /// is read when code is generated, so this is meant to be used from build scripts.
+    ///
+    /// # Examples
+    ///
+    /// In `build.rs`:
+    ///
+    /// ```no_run
+    /// protobuf_codegen::Codegen::new()
+    ///     .cargo_out_dir("protos")
+    ///     .include("src/protos")
+    ///     .input("src/protos/foo.proto")
+    ///     .run_from_script();
+    /// ```
+    ///
+    /// And then in the crate:
+    ///
+    /// ```ignore
+    /// include!(concat!(env!("OUT_DIR"), "/protos.rs"));
+    ///
+    /// use protos::foo::Foo;
+    /// ```
+    pub fn cargo_out_dir(&mut self, module_name: &str) -> &mut Self {
+        self.cargo_out_dir = Some(module_name.to_owned());
+        self
+    }
+
     /// Add an include directory.
     pub fn include(&mut self, include: impl AsRef<Path>) -> &mut Self {
         self.includes.push(include.as_ref().to_owned());
@@ -83,6 +149,16 @@ impl Codegen {
         self
     }
 
+    /// Add an uncompressed tar archive which is searched for imported files
+    /// after include directories.
+    ///
+    /// Paths of files in the archive are relative to the archive root.
+    /// Archives are supported only by [pure](Self::pure) parser.
+    pub fn include_archive(&mut self, archive: impl AsRef<Path>) -> &mut Self {
+        self.include_archives.push(archive.as_ref().to_owned());
+        self
+    }
+
     /// Append a `.proto` file path to compile
     pub fn input(&mut self, input: impl AsRef<Path>) -> &mut Self {
         self.inputs.push(input.as_ref().to_owned());
@@ -119,11 +195,50 @@ impl Codegen {
     /// ```
     ///
     /// This option is ignored when pure Rust parser is used.
+    ///
+    /// This option takes precedence over [`protoc_resolver`](Self::protoc_resolver).
     pub fn protoc_path(&mut self, protoc: impl Into<PathBuf>) -> &mut Self {
         self.protoc = Some(Protoc::from_path(&protoc.into()));
         self
     }
 
+    /// Specify a callback which returns `protoc` command path.
+    ///
+    /// The callback is invoked by [`run`](Self::run) only when `protoc` parser is used,
+    /// so `protoc` can be located or downloaded lazily. Error returned by the callback
+    /// is reported as [`CodegenError::ProtocNotFound`].
+    ///
+    /// Path specified with [`protoc_path`](Self::protoc_path) takes precedence
+    /// over this callback, and when neither is specified, `protoc` is searched in `$PATH`.
+    ///
+    /// This option is ignored when pure Rust parser is used.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # mod protoc_bin_vendored {
+    /// #   pub fn protoc_bin_path() -> Result<std::path::PathBuf, std::io::Error> {
+    /// #       unimplemented!()
+    /// #   }
+    /// # }
+    ///
+    /// use protobuf_codegen::Codegen;
+    ///
+    /// Codegen::new()
+    ///     .protoc()
+    ///     .protoc_resolver(protoc_bin_vendored::protoc_bin_path)
+    ///     // ...
+    ///     .run()
+    ///     .unwrap();
+    /// ```
+    pub fn protoc_resolver(
+        &mut self,
+        resolver: impl Fn() -> io::Result<PathBuf> + 'static,
+    ) -> &mut Self {
+        self.protoc_resolver = Some(ProtocResolver(Box::new(resolver)));
+        self
+    }
+
     /// Set options to customize code generation
     pub fn customize(&mut self, customize: Customize) -> &mut Self {
         self.customize = customize;
@@ -140,6 +255,33 @@ impl Codegen {
         self
     }
 
+    /// Rewrite generated files before they are written to disk.
+    ///
+    /// The callback is invoked with the output file path and generated content,
+    /// and returns the content to be written instead. It can be used to add
+    /// license headers, inject extra code or rename things.
+    ///
+    /// The returned content must still be valid Rust code.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use protobuf_codegen::Codegen;
+    ///
+    /// Codegen::new()
+    ///     .post_process(|_path, content| format!("// Copyright ...\n\n{}", content))
+    ///     // ...
+    ///     .run()
+    ///     .unwrap();
+    /// ```
+    pub fn post_process(
+        &mut self,
+        post_process: impl Fn(&Path, String) -> String + 'static,
+    ) -> &mut Self {
+        self.post_process = Some(PostProcess(Box::new(post_process)));
+        self
+    }
+
     /// Invoke the code generation.
     ///
     /// This is roughly equivalent to `protoc --rust_out=...` but
@@ -147,18 +289,65 @@ impl Codegen {
     ///
     /// This function uses pure Rust parser or `protoc` parser depending on
     /// how this object was configured.
-    pub fn run(&self) -> anyhow::Result<()> {
+    ///
+    /// # Examples
+    ///
+    /// Fall back to pure Rust parser when `protoc` is not available:
+    ///
+    /// ```no_run
+    /// use protobuf_codegen::Codegen;
+    /// use protobuf_codegen::CodegenError;
+    ///
+    /// let mut codegen = Codegen::new();
+    /// // ...
+    /// match codegen.run() {
+    ///     Err(CodegenError::ProtocNotFound(_)) => codegen.pure().run().unwrap(),
+    ///     r => r.unwrap(),
+    /// }
+    /// ```
+    pub fn run(&self) -> Result<(), CodegenError> {
         let (parsed_and_typechecked, parser) = match self.which_parser {
             WhichParser::Protoc => protoc::parse_and_typecheck(self)?,
             WhichParser::Pure => pure::parse_and_typecheck(self)?,
         };
+
+        let cargo_out_dir = match &self.cargo_out_dir {
+            Some(module_name) => {
+                let dir = env::var_os("OUT_DIR").ok_or(CodegenError::OutDirNotSet)?;
+                Some((PathBuf::from(dir), module_name))
+            }
+            None => None,
+        };
+        let out_dir = match &cargo_out_dir {
+            Some((dir, module_name)) => {
+                let out_dir = dir.join(module_name);
+                fs::create_dir_all(&out_dir)?;
+                out_dir
+            }
+            None => self.out_dir.clone(),
+        };
+
         gen_and_write(
             &parsed_and_typechecked.file_descriptors,
             &parser,
             &parsed_and_typechecked.relative_paths,
-            &self.out_dir,
+            &out_dir,
             &self.customize,
+            self.post_process.as_ref().map(|p| &*p.0),
         )
+        .map_err(|e| CodegenError::GenFailed(e.into()))?;
+
+        if let Some((dir, module_name)) = cargo_out_dir {
+            let mods: Vec<String> = parsed_and_typechecked
+                .relative_paths
+                .iter()
+                .map(|p| proto_path_to_rust_mod(p.to_str()).into_string())
+                .collect();
+            let include_rs = gen_include_rs(module_name, &mods, &self.customize);
+            fs::write(dir.join(include_rs.name), include_rs.content)?;
+        }
+
+        Ok(())
     }
 
     /// Similar to `run`, but prints the message to stderr and exits the process on error.
@@ -176,6 +365,122 @@ fn remove_path_prefix<'a>(mut path: &'a Path, mut prefix: &Path) -> Option<&'a P
     path.strip_prefix(prefix).ok()
 }
 
+#[test]
+fn test_post_process() {
+    use std::fs;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let proto_dir = tmp.path().join("proto");
+    let out_dir = tmp.path().join("out");
+    fs::create_dir(&proto_dir).unwrap();
+    fs::create_dir(&out_dir).unwrap();
+    fs::write(
+        proto_dir.join("post_process.proto"),
+        "syntax = \"proto3\"; message Foo {}",
+    )
+    .unwrap();
+
+    let expected_path = out_dir.join("post_process.rs");
+    Codegen::new()
+        .pure()
+        .out_dir(&out_dir)
+        .include(&proto_dir)
+        .input(proto_dir.join("post_process.proto"))
+        .post_process(move |path, content| {
+            assert_eq!(expected_path, path);
+            assert!(content.contains("pub struct Foo"));
+            format!("// post-processed\n{}", content)
+        })
+        .run()
+        .unwrap();
+
+    let content = fs::read_to_string(out_dir.join("post_process.rs")).unwrap();
+    assert!(content.starts_with("// post-processed\n"));
+    assert!(content.contains("pub struct Foo"));
+}
+
+#[test]
+fn test_protoc_not_found() {
+    let tmp = tempfile::tempdir().unwrap();
+    let r = Codegen::new()
+        .protoc()
+        .protoc_path(tmp.path().join("no-such-protoc"))
+        .out_dir(tmp.path())
+        .input(tmp.path().join("foo.proto"))
+        .run();
+    match r {
+        Err(CodegenError::ProtocNotFound(_)) => {}
+        r => panic!("expecting protoc not found: {:?}", r),
+    }
+}
+
+#[test]
+fn test_protoc_resolver() {
+    let tmp = tempfile::tempdir().unwrap();
+    let r = Codegen::new()
+        .protoc()
+        .protoc_resolver(|| Err(io::Error::new(io::ErrorKind::NotFound, "no protoc")))
+        .out_dir(tmp.path())
+        .input(tmp.path().join("foo.proto"))
+        .run();
+    match r {
+        Err(e @ CodegenError::ProtocNotFound(_)) => assert_eq!(
+            "protoc command is not found or not working: no protoc",
+            format!("{:#}", anyhow::Error::from(e))
+        ),
+        r => panic!("expecting protoc not found: {:?}", r),
+    }
+}
+
+#[test]
+fn test_protoc_resolver_is_lazy() {
+    use std::cell::Cell;
+    use std::fs;
+    use std::rc::Rc;
+
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("foo.proto"), "syntax = \"proto3\";").unwrap();
+    let calls = Rc::new(Cell::new(0));
+
+    let mut codegen = Codegen::new();
+    let resolver_calls = calls.clone();
+    codegen
+        .protoc_resolver(move || {
+            resolver_calls.set(resolver_calls.get() + 1);
+            Ok(PathBuf::from("no-such-protoc"))
+        })
+        .out_dir(tmp.path())
+        .include(tmp.path())
+        .input(tmp.path().join("foo.proto"));
+
+    // not called by pure parser
+    codegen.pure().run().unwrap();
+    assert_eq!(0, calls.get());
+
+    // `protoc_path` takes precedence
+    let r = codegen
+        .protoc()
+        .protoc_path(tmp.path().join("other-protoc"))
+        .run();
+    assert!(matches!(r, Err(CodegenError::ProtocNotFound(_))));
+    assert_eq!(0, calls.get());
+}
+
+#[test]
+fn test_include_archive_requires_pure() {
+    let tmp = tempfile::tempdir().unwrap();
+    let r = Codegen::new()
+        .protoc()
+        .out_dir(tmp.path())
+        .include_archive(tmp.path().join("deps.tar"))
+        .input(tmp.path().join("foo.proto"))
+        .run();
+    match r {
+        Err(CodegenError::IncludeArchiveRequiresPure) => {}
+        r => panic!("expecting include archive error: {:?}", r),
+    }
+}
+
 #[test]
 fn test_remove_path_prefix() {
     assert_eq!(
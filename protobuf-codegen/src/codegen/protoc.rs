@@ -1,5 +1,4 @@
 use std::fs;
-use std::io;
 use std::path::PathBuf;
 
 use protobuf::descriptor::FileDescriptorSet;
@@ -10,21 +9,26 @@ use protoc::Protoc;
 
 use crate::codegen::remove_path_prefix;
 use crate::codegen::Codegen;
-
-#[derive(Debug, thiserror::Error)]
-enum Error {
-    #[error("file `{0}` is not found in includes {}")]
-    NotFound(String, String),
-}
+use crate::codegen::CodegenError;
 
 pub(crate) fn parse_and_typecheck(
     codegen: &Codegen,
-) -> anyhow::Result<(ParsedAndTypechecked, String)> {
-    let protoc = match codegen.protoc.clone() {
-        Some(protoc) => protoc,
-        None => Protoc::from_env_path(),
+) -> Result<(ParsedAndTypechecked, String), CodegenError> {
+    if !codegen.include_archives.is_empty() {
+        return Err(CodegenError::IncludeArchiveRequiresPure);
+    }
+
+    let protoc = match (codegen.protoc.clone(), &codegen.protoc_resolver) {
+        (Some(protoc), _) => protoc,
+        (None, Some(resolver)) => {
+            let path = (resolver.0)().map_err(|e| CodegenError::ProtocNotFound(e.into()))?;
+            Protoc::from_path(&path)
+        }
+        (None, None) => Protoc::from_env_path(),
     };
-    protoc.check()?;
+    let version = protoc
+        .version()
+        .map_err(|e| CodegenError::ProtocNotFound(e.into()))?;
 
     let temp_dir = tempfile::Builder::new().prefix("protoc-rust").tempdir()?;
     let temp_file = temp_dir.path().join("descriptor.pbbin");
@@ -36,13 +40,14 @@ pub(crate) fn parse_and_typecheck(
         .inputs(&codegen.inputs)
         .include_imports(true)
         .extra_args(codegen.extra_args.iter())
-        .write_descriptor_set()?;
+        .write_descriptor_set()
+        .map_err(|e| CodegenError::ProtocFailed(e.into()))?;
 
     let fds = fs::read(temp_file)?;
     drop(temp_dir);
 
     let fds: protobuf::descriptor::FileDescriptorSet = FileDescriptorSet::parse_from_bytes(&fds)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        .map_err(|e| CodegenError::ProtocFailed(e.into()))?;
 
     let default_includes = vec![PathBuf::from(".")];
     let includes = if codegen.includes.is_empty() {
@@ -55,12 +60,18 @@ pub(crate) fn parse_and_typecheck(
     'outer: for file in &codegen.inputs {
         for include in includes {
             if let Some(truncated) = remove_path_prefix(file, include) {
-                files_to_generate.push(ProtoPathBuf::from_path(&truncated)?);
+                files_to_generate.push(
+                    ProtoPathBuf::from_path(&truncated)
+                        .map_err(|e| CodegenError::ParseFailed(e.into()))?,
+                );
                 continue 'outer;
             }
         }
 
-        return Err(Error::NotFound(file.display().to_string(), format!("{:?}", includes)).into());
+        return Err(CodegenError::InputNotInIncludes(
+            file.display().to_string(),
+            format!("{:?}", includes),
+        ));
     }
 
     Ok((
@@ -68,6 +79,6 @@ pub(crate) fn parse_and_typecheck(
             relative_paths: files_to_generate,
             file_descriptors: fds.file,
         },
-        format!("protoc {}", protoc.version()?),
+        format!("protoc {}", version),
     ))
 }
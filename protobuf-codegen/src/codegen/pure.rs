@@ -2,10 +2,16 @@ use protobuf_parse::pure;
 use protobuf_parse::ParsedAndTypechecked;
 
 use crate::codegen::Codegen;
+use crate::codegen::CodegenError;
 
 pub(crate) fn parse_and_typecheck(
     codegen: &Codegen,
-) -> anyhow::Result<(ParsedAndTypechecked, String)> {
-    let p = pure::parse_and_typecheck(&codegen.includes, &codegen.inputs)?;
+) -> Result<(ParsedAndTypechecked, String), CodegenError> {
+    let p = pure::parse_and_typecheck_with_archives(
+        &codegen.includes,
+        &codegen.include_archives,
+        &codegen.inputs,
+    )
+    .map_err(|e| CodegenError::ParseFailed(e.into()))?;
     Ok((p, format!("protobuf-codegen={}", env!("CARGO_PKG_VERSION"))))
 }
@@ -3,6 +3,99 @@ use protobuf::descriptor::FileOptions;
 use protobuf::descriptor::MessageOptions;
 use protobuf::rustproto;
 
+/// Return type of generated `get_` functions for `Copy` scalar fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarGetterStyle {
+    /// Numbers and `bool` are returned by value, e. g. `fn get_foo(&self) -> i32`.
+    ///
+    /// This is the default. Enums are always returned by value,
+    /// and strings, bytes, messages, repeated and map fields are always returned by reference,
+    /// regardless of this option.
+    ByValue,
+    /// Numbers and `bool` are returned by reference, e. g. `fn get_foo(&self) -> &i32`.
+    ByRef,
+}
+
+impl ScalarGetterStyle {
+    fn parse(s: &str) -> Option<ScalarGetterStyle> {
+        match s {
+            "by_value" => Some(ScalarGetterStyle::ByValue),
+            "by_ref" => Some(ScalarGetterStyle::ByRef),
+            _ => None,
+        }
+    }
+
+    fn from_rustproto(s: Option<String>) -> anyhow::Result<Option<ScalarGetterStyle>> {
+        s.map(|s| {
+            ScalarGetterStyle::parse(&s).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "unknown scalar_getter_style: `{}`, expecting `by_value` or `by_ref`",
+                    s
+                )
+            })
+        })
+        .transpose()
+    }
+}
+
+/// How nested messages, enums and oneofs of a message are named in generated code.
+///
+/// For message `Outer` containing message `Inner` containing message `Leaf`.
+///
+/// Code generation fails if a flattened name clashes with another type name,
+/// e. g. nested `Outer.Inner` and top-level `OuterInner` with `FlattenCamel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NestedTypeStrategy {
+    /// Nested types are generated in nested modules, e. g. `outer::inner::Leaf`.
+    ///
+    /// This is the default.
+    Modules,
+    /// Nested types are generated at file level with names joined by underscore,
+    /// e. g. `Outer_Inner_Leaf`.
+    FlattenUnderscore,
+    /// Nested types are generated at file level with names concatenated,
+    /// e. g. `OuterInnerLeaf`.
+    FlattenCamel,
+}
+
+impl NestedTypeStrategy {
+    fn parse(s: &str) -> Option<NestedTypeStrategy> {
+        match s {
+            "modules" => Some(NestedTypeStrategy::Modules),
+            "flatten_underscore" => Some(NestedTypeStrategy::FlattenUnderscore),
+            "flatten_camel" => Some(NestedTypeStrategy::FlattenCamel),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_rustproto(s: Option<String>) -> anyhow::Result<Option<NestedTypeStrategy>> {
+        s.map(|s| {
+            NestedTypeStrategy::parse(&s).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "unknown nested_type_strategy: `{}`, \
+                    expecting `modules`, `flatten_underscore` or `flatten_camel`",
+                    s
+                )
+            })
+        })
+        .transpose()
+    }
+}
+
+/// Trait implemented for all generated messages,
+/// added with [`Customize::implement_trait`].
+#[derive(Debug, Clone)]
+pub struct ImplementTrait {
+    /// Path to the trait, e. g. `my_crate::Routable`.
+    pub trait_path: String,
+    /// Body of `impl` block, with template variables:
+    /// * `$name`: message name in `.proto` file, e. g. `Bar`
+    /// * `$full_name`: fully qualified message name, e. g. `foo.Bar` or `foo.Outer.Bar`
+    /// * `$rust_name`: Rust type name, e. g. `Bar`
+    /// * `$descriptor`: expression of type `MessageDescriptor` for the message
+    pub body_template: String,
+}
+
 /// Specifies style of generated code.
 /// Generated files can be customized using this proto
 /// or using `rustproto.proto` options.
@@ -10,20 +103,154 @@ use protobuf::rustproto;
 pub struct Customize {
     /// Make oneof enum public.
     pub expose_oneof: Option<bool>,
+    /// Generate for each oneof an enum of its cases without values
+    /// and `which_xxx` function returning the case which is set.
+    pub generate_oneof_case_enum: Option<bool>,
     /// When true all fields are public, and accessors are not generated
     pub expose_fields: Option<bool>,
     /// When false, `get_`, `set_`, `mut_` etc. accessors are not generated
     pub generate_accessors: Option<bool>,
     /// When false, `get_` is not generated even if `syntax = "proto2"`
     pub generate_getter: Option<bool>,
+    /// Return type of `get_` functions for numeric and `bool` fields,
+    /// [`ScalarGetterStyle::ByValue`] when unspecified.
+    ///
+    /// Reflection, JSON and text format work with either style.
+    pub scalar_getter_style: Option<ScalarGetterStyle>,
+    /// Generate `get_xxx_opt` functions returning `Option` of `get_xxx` value,
+    /// `None` when the field is not set.
+    ///
+    /// Functions are generated only for fields with explicit presence:
+    /// proto2 singular fields, proto3 `optional` fields, message fields and oneof fields.
+    pub generate_option_getters: Option<bool>,
+    /// Add `#[track_caller]` to generated accessors which may panic,
+    /// so panic location points to the caller instead of generated code.
+    ///
+    /// These are `mut_xxx` functions of singular fields with presence and oneof fields,
+    /// and `take_xxx` functions of non-`Copy` oneof fields.
+    pub track_caller_accessors: Option<bool>,
     /// Use `bytes::Bytes` for `bytes` fields
     pub carllerche_bytes_for_bytes: Option<bool>,
     /// Use `bytes::Bytes` for `string` fields
     pub carllerche_bytes_for_string: Option<bool>,
+    /// Type of repeated fields, `Vec` by default.
+    ///
+    /// Can be `smallvec::SmallVec<[_; N]>` to store up to `N` elements inline
+    /// without allocation, generated code then requires `smallvec` crate
+    /// and `with-smallvec` feature of `protobuf` crate.
+    pub repeated_type: Option<String>,
     /// Implement serde_derive for messages
     pub serde_derive: Option<bool>,
     /// When `serde_derive` is set, serde annotations will be guarded with `#[cfg(cfg, ...)]`.
     pub serde_derive_cfg: Option<String>,
+    /// When `serde_derive` is set, fill fields missing in serialized data with default values
+    /// (`#[serde(default)]`), and do not serialize fields with default values:
+    /// unset optional and message fields, empty repeated and map fields,
+    /// and proto3 fields equal to zero or empty.
+    pub serde_default: Option<bool>,
+    /// Generate `fields_present` function which returns
+    /// a [`FieldPresenceSet`](protobuf::FieldPresenceSet) of fields set in the message
+    pub generate_presence_set: Option<bool>,
+    /// Generate `retain_fields` function which clears all fields
+    /// except fields with given numbers, and unknown fields
+    pub generate_retain_fields: Option<bool>,
+    /// Generate `message_descriptor_proto` function which returns
+    /// `DescriptorProto` of the message, part of the file descriptor.
+    pub per_message_descriptor: Option<bool>,
+    /// Generate repeated fields as `Box<[T]>` instead of `Vec<T>`.
+    ///
+    /// This saves memory of unused capacity in messages which are decoded once
+    /// and not modified. Elements can still be modified in place,
+    /// but adding or removing elements requires replacing the whole slice.
+    /// Fields with `repeated_type` option are not affected.
+    pub repeated_boxed_slice: Option<bool>,
+    /// Implement `AsRef<[u8]>` and `Deref<Target = [u8]>` for a message
+    /// which has exactly one singular `bytes` field.
+    ///
+    /// This option is meant to be set for a message with `rustproto.bytes_wrapper`,
+    /// it is not inherited by nested messages.
+    pub bytes_wrapper: Option<bool>,
+    /// Implement `Index<usize>` for message with exactly one repeated field,
+    /// which indexes that field.
+    ///
+    /// This option is meant to be set for a message with `rustproto.repeated_index_impl`,
+    /// it is not inherited by nested messages.
+    pub repeated_index_impl: Option<bool>,
+    /// Name of a field used as primary key: when specified,
+    /// [`Entity`](protobuf::Entity) trait is implemented for the message.
+    /// Field must be singular string, integer or bool field.
+    ///
+    /// This option is meant to be set for a message with `rustproto.primary_key_field`,
+    /// it is not inherited by nested messages.
+    pub primary_key_field: Option<String>,
+    /// Template of `Display` implementation for a message, like `"User {id} ({name})"`.
+    ///
+    /// `{field}` placeholders are replaced with field values formatted with their `Display`,
+    /// format spec can be specified after colon like `{id:>5}`, and `{{` and `}}` are
+    /// literal braces. Placeholder must reference a singular or oneof field of the message.
+    ///
+    /// This option is meant to be set for a message with `rustproto.display_format`,
+    /// it is not inherited by nested messages.
+    pub display_format: Option<String>,
+    /// Generate `xxx_to_vec`, `xxx_to_sorted_vec` and `set_xxx_from_vec`
+    /// functions for map fields.
+    pub generate_map_conversions: Option<bool>,
+    /// Name of a field of repeated message field element.
+    /// When specified, `xxx_to_map` function is generated,
+    /// which collects elements into a map keyed by that field.
+    ///
+    /// This option is meant to be set for a field with `rustproto.map_key_field`.
+    pub map_key_field: Option<String>,
+    /// Implement `PartialEq` which compares `float` and `double` fields
+    /// by bit pattern instead of deriving it.
+    ///
+    /// With derived `PartialEq` a message with `NaN` field is not equal to itself,
+    /// with this option it is. Comparison is by bits rather than by value,
+    /// so `0.0` is not equal to `-0.0`, and `NaN` values with different payloads
+    /// are not equal. This is what tests comparing messages with expected values need,
+    /// but it is not IEEE 754 semantics, so the option is off by default.
+    pub nan_eq: Option<bool>,
+    /// Implement `Eq` for messages.
+    ///
+    /// Messages with `float` or `double` fields need `nan_eq` option,
+    /// and message-typed fields need `impl_eq` for the field type,
+    /// otherwise code generation fails with an error naming the field.
+    pub impl_eq: Option<bool>,
+    /// Implement `Hash` for messages.
+    ///
+    /// Messages with `float`, `double` or map fields cannot implement `Hash`,
+    /// and message-typed fields need `impl_hash` for the field type,
+    /// otherwise code generation fails with an error naming the field.
+    /// Well-known types like `google.protobuf.Timestamp` do not implement `Hash`.
+    pub impl_hash: Option<bool>,
+    /// Generate `builder()` function returning typestate builder for messages
+    /// with proto2 `required` fields.
+    ///
+    /// Builder has a type parameter per required field which tracks whether the field is set,
+    /// and `build()` function compiles only when all required fields are set.
+    /// Builder types get long for messages with many required fields,
+    /// so they are best left to type inference.
+    /// Accessors are generated for required fields of such messages.
+    pub typestate_builder: Option<bool>,
+    /// Enums with more values than this threshold get `from_i32` implemented
+    /// as a binary search in a sorted array instead of a `match` expression.
+    ///
+    /// Huge `match` expressions are slow to compile.
+    pub enum_lookup_threshold: Option<u32>,
+    /// Generate `into_parts` function which decomposes a message into
+    /// `{Message}Parts` struct with public owned fields, and `into_message`
+    /// function of that struct to assemble the message back, without serialization.
+    ///
+    /// Fields are moved as they are stored in the message: oneofs are moved
+    /// as `Option` of oneof enum, and map fields as `HashMap`.
+    pub generate_parts: Option<bool>,
+    /// How nested types are named, [`NestedTypeStrategy::Modules`] when unspecified.
+    ///
+    /// This option can be specified for a file, but not for a message,
+    /// because all references to a type must agree on its name.
+    pub nested_type_strategy: Option<NestedTypeStrategy>,
+    /// Traits implemented for every generated message.
+    pub implement_traits: Option<Vec<ImplementTrait>>,
     /// Enable lite runtime
     pub lite_runtime: Option<bool>,
     /// Generate `mod.rs` in the output directory.
@@ -32,6 +259,13 @@ pub struct Customize {
     ///
     /// This option will likely be on by default in rust-protobuf version 3.
     pub gen_mod_rs: Option<bool>,
+    /// Write trait implementations and inherent `impl` blocks of generated types
+    /// into a separate `<file>_impls.rs` file, which must be declared as module
+    /// `<file>_impls` next to module `<file>` (generated `mod.rs` does that).
+    ///
+    /// Private fields of generated messages are visible to the parent module
+    /// of `<file>` with this option.
+    pub split_impls: Option<bool>,
     /// Used internally to generate protos bundled in protobuf crate
     /// like `descriptor.proto`
     pub inside_protobuf: Option<bool>,
@@ -46,6 +280,9 @@ pub struct Customize {
 pub enum CustomizeParseParameterError {
     EqNotFound,
     CannotParseBool,
+    CannotParseU32,
+    CannotParseScalarGetterStyle(String),
+    CannotParseNestedTypeStrategy(String),
     UnknownOptionName(String),
 }
 
@@ -57,6 +294,9 @@ impl Customize {
         if let Some(v) = that.expose_oneof {
             self.expose_oneof = Some(v);
         }
+        if let Some(v) = that.generate_oneof_case_enum {
+            self.generate_oneof_case_enum = Some(v);
+        }
         if let Some(v) = that.expose_fields {
             self.expose_fields = Some(v);
         }
@@ -66,29 +306,151 @@ impl Customize {
         if let Some(v) = that.generate_getter {
             self.generate_getter = Some(v);
         }
+        if let Some(v) = that.scalar_getter_style {
+            self.scalar_getter_style = Some(v);
+        }
+        if let Some(v) = that.generate_option_getters {
+            self.generate_option_getters = Some(v);
+        }
+        if let Some(v) = that.track_caller_accessors {
+            self.track_caller_accessors = Some(v);
+        }
         if let Some(v) = that.carllerche_bytes_for_bytes {
             self.carllerche_bytes_for_bytes = Some(v);
         }
         if let Some(v) = that.carllerche_bytes_for_string {
             self.carllerche_bytes_for_string = Some(v);
         }
+        if let Some(ref v) = that.repeated_type {
+            self.repeated_type = Some(v.clone());
+        }
         if let Some(v) = that.serde_derive {
             self.serde_derive = Some(v);
         }
         if let Some(ref v) = that.serde_derive_cfg {
             self.serde_derive_cfg = Some(v.clone());
         }
+        if let Some(v) = that.serde_default {
+            self.serde_default = Some(v);
+        }
+        if let Some(v) = that.generate_presence_set {
+            self.generate_presence_set = Some(v);
+        }
+        if let Some(v) = that.generate_retain_fields {
+            self.generate_retain_fields = Some(v);
+        }
+        if let Some(v) = that.per_message_descriptor {
+            self.per_message_descriptor = Some(v);
+        }
+        if let Some(v) = that.repeated_boxed_slice {
+            self.repeated_boxed_slice = Some(v);
+        }
+        if let Some(v) = that.bytes_wrapper {
+            self.bytes_wrapper = Some(v);
+        }
+        if let Some(v) = that.repeated_index_impl {
+            self.repeated_index_impl = Some(v);
+        }
+        if let Some(ref v) = that.primary_key_field {
+            self.primary_key_field = Some(v.clone());
+        }
+        if let Some(ref v) = that.display_format {
+            self.display_format = Some(v.clone());
+        }
+        if let Some(v) = that.generate_map_conversions {
+            self.generate_map_conversions = Some(v);
+        }
+        if let Some(ref v) = that.map_key_field {
+            self.map_key_field = Some(v.clone());
+        }
+        if let Some(v) = that.nan_eq {
+            self.nan_eq = Some(v);
+        }
+        if let Some(v) = that.impl_eq {
+            self.impl_eq = Some(v);
+        }
+        if let Some(v) = that.impl_hash {
+            self.impl_hash = Some(v);
+        }
+        if let Some(v) = that.typestate_builder {
+            self.typestate_builder = Some(v);
+        }
+        if let Some(v) = that.enum_lookup_threshold {
+            self.enum_lookup_threshold = Some(v);
+        }
+        if let Some(v) = that.generate_parts {
+            self.generate_parts = Some(v);
+        }
+        if let Some(v) = that.nested_type_strategy {
+            self.nested_type_strategy = Some(v);
+        }
+        if let Some(ref v) = that.implement_traits {
+            self.implement_traits = Some(v.clone());
+        }
         if let Some(v) = that.lite_runtime {
             self.lite_runtime = Some(v);
         }
         if let Some(v) = that.gen_mod_rs {
             self.gen_mod_rs = Some(v);
         }
+        if let Some(v) = that.split_impls {
+            self.split_impls = Some(v);
+        }
         if let Some(v) = that.inside_protobuf {
             self.inside_protobuf = Some(v);
         }
     }
 
+    /// Implement a trait for every generated message.
+    ///
+    /// `body_template` is the body of `impl` block,
+    /// see [`ImplementTrait::body_template`] for available template variables.
+    ///
+    /// ```
+    /// # use protobuf_codegen::Customize;
+    /// let customize = Customize::default().implement_trait(
+    ///     "my_crate::Routable",
+    ///     "fn route(&self) -> &'static str { \"$full_name\" }",
+    /// );
+    /// ```
+    pub fn implement_trait(
+        mut self,
+        trait_path: impl Into<String>,
+        body_template: impl Into<String>,
+    ) -> Customize {
+        self.implement_traits
+            .get_or_insert_with(Vec::new)
+            .push(ImplementTrait {
+                trait_path: trait_path.into(),
+                body_template: body_template.into(),
+            });
+        self
+    }
+
+    /// Generate `from_i32` of enums with more than `n` values
+    /// as a lookup in a sorted array.
+    ///
+    /// ```
+    /// # use protobuf_codegen::Customize;
+    /// let customize = Customize::default().enum_lookup_threshold(1000);
+    /// ```
+    pub fn enum_lookup_threshold(mut self, n: u32) -> Customize {
+        self.enum_lookup_threshold = Some(n);
+        self
+    }
+
+    /// Set how nested types are named.
+    ///
+    /// ```
+    /// # use protobuf_codegen::Customize;
+    /// # use protobuf_codegen::NestedTypeStrategy;
+    /// let customize = Customize::default().nested_type_strategy(NestedTypeStrategy::FlattenCamel);
+    /// ```
+    pub fn nested_type_strategy(mut self, strategy: NestedTypeStrategy) -> Customize {
+        self.nested_type_strategy = Some(strategy);
+        self
+    }
+
     /// Update unset fields of self with fields from other customize
     pub fn set_defaults_from(&mut self, other: &Customize) {
         let mut tmp = other.clone();
@@ -103,6 +465,11 @@ impl Customize {
                 .map_err(|_| CustomizeParseParameterError::CannotParseBool)
         }
 
+        fn parse_u32(v: &str) -> CustomizeParseParameterResult<u32> {
+            v.parse()
+                .map_err(|_| CustomizeParseParameterError::CannotParseU32)
+        }
+
         let mut r = Customize::default();
         for nv in parameter.split_whitespace() {
             let eq = match nv.find('=') {
@@ -115,24 +482,76 @@ impl Customize {
 
             if n == "expose_oneof" {
                 r.expose_oneof = Some(parse_bool(v)?);
+            } else if n == "generate_oneof_case_enum" {
+                r.generate_oneof_case_enum = Some(parse_bool(v)?);
             } else if n == "expose_fields" {
                 r.expose_fields = Some(parse_bool(v)?);
             } else if n == "generate_accessors" {
                 r.generate_accessors = Some(parse_bool(v)?);
             } else if n == "generate_getter" {
                 r.generate_getter = Some(parse_bool(v)?);
+            } else if n == "scalar_getter_style" {
+                r.scalar_getter_style = Some(ScalarGetterStyle::parse(v).ok_or_else(|| {
+                    CustomizeParseParameterError::CannotParseScalarGetterStyle(v.to_owned())
+                })?);
+            } else if n == "generate_option_getters" {
+                r.generate_option_getters = Some(parse_bool(v)?);
+            } else if n == "track_caller_accessors" {
+                r.track_caller_accessors = Some(parse_bool(v)?);
             } else if n == "carllerche_bytes_for_bytes" {
                 r.carllerche_bytes_for_bytes = Some(parse_bool(v)?);
             } else if n == "carllerche_bytes_for_string" {
                 r.carllerche_bytes_for_string = Some(parse_bool(v)?);
+            } else if n == "repeated_type" {
+                r.repeated_type = Some(v.to_owned());
             } else if n == "serde_derive" {
                 r.serde_derive = Some(parse_bool(v)?);
             } else if n == "serde_derive_cfg" {
                 r.serde_derive_cfg = Some(v.to_owned());
+            } else if n == "serde_default" {
+                r.serde_default = Some(parse_bool(v)?);
+            } else if n == "generate_presence_set" {
+                r.generate_presence_set = Some(parse_bool(v)?);
+            } else if n == "generate_retain_fields" {
+                r.generate_retain_fields = Some(parse_bool(v)?);
+            } else if n == "per_message_descriptor" {
+                r.per_message_descriptor = Some(parse_bool(v)?);
+            } else if n == "repeated_boxed_slice" {
+                r.repeated_boxed_slice = Some(parse_bool(v)?);
+            } else if n == "bytes_wrapper" {
+                r.bytes_wrapper = Some(parse_bool(v)?);
+            } else if n == "repeated_index_impl" {
+                r.repeated_index_impl = Some(parse_bool(v)?);
+            } else if n == "primary_key_field" {
+                r.primary_key_field = Some(v.to_owned());
+            } else if n == "display_format" {
+                r.display_format = Some(v.to_owned());
+            } else if n == "generate_map_conversions" {
+                r.generate_map_conversions = Some(parse_bool(v)?);
+            } else if n == "map_key_field" {
+                r.map_key_field = Some(v.to_owned());
+            } else if n == "nan_eq" {
+                r.nan_eq = Some(parse_bool(v)?);
+            } else if n == "impl_eq" {
+                r.impl_eq = Some(parse_bool(v)?);
+            } else if n == "impl_hash" {
+                r.impl_hash = Some(parse_bool(v)?);
+            } else if n == "typestate_builder" {
+                r.typestate_builder = Some(parse_bool(v)?);
+            } else if n == "enum_lookup_threshold" {
+                r.enum_lookup_threshold = Some(parse_u32(v)?);
+            } else if n == "generate_parts" {
+                r.generate_parts = Some(parse_bool(v)?);
+            } else if n == "nested_type_strategy" {
+                r.nested_type_strategy = Some(NestedTypeStrategy::parse(v).ok_or_else(|| {
+                    CustomizeParseParameterError::CannotParseNestedTypeStrategy(v.to_owned())
+                })?);
             } else if n == "lite_runtime" {
                 r.lite_runtime = Some(parse_bool(v)?);
             } else if n == "gen_mod_rs" {
                 r.gen_mod_rs = Some(parse_bool(v)?);
+            } else if n == "split_impls" {
+                r.split_impls = Some(parse_bool(v)?);
             } else if n == "inside_protobuf" {
                 r.inside_protobuf = Some(parse_bool(v)?);
             } else {
@@ -145,87 +564,241 @@ impl Customize {
     }
 }
 
-pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> Customize {
+pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> anyhow::Result<Customize> {
     let expose_oneof = rustproto::exts::expose_oneof.get(source);
+    let generate_oneof_case_enum = rustproto::exts::generate_oneof_case_enum.get(source);
     let expose_fields = rustproto::exts::expose_fields.get(source);
     let generate_accessors = rustproto::exts::generate_accessors.get(source);
     let generate_getter = rustproto::exts::generate_getter.get(source);
+    let scalar_getter_style =
+        ScalarGetterStyle::from_rustproto(rustproto::exts::scalar_getter_style.get(source))?;
+    let generate_option_getters = rustproto::exts::generate_option_getters.get(source);
+    let track_caller_accessors = rustproto::exts::track_caller_accessors.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes.get(source);
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string.get(source);
+    let repeated_type = rustproto::exts::repeated_type.get(source);
     let serde_derive = rustproto::exts::serde_derive.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg.get(source);
+    let serde_default = rustproto::exts::serde_default.get(source);
+    let generate_presence_set = rustproto::exts::generate_presence_set.get(source);
+    let generate_retain_fields = rustproto::exts::generate_retain_fields.get(source);
+    let per_message_descriptor = rustproto::exts::per_message_descriptor.get(source);
+    let repeated_boxed_slice = rustproto::exts::repeated_boxed_slice.get(source);
+    let bytes_wrapper = rustproto::exts::bytes_wrapper.get(source);
+    let repeated_index_impl = rustproto::exts::repeated_index_impl.get(source);
+    let primary_key_field = rustproto::exts::primary_key_field.get(source);
+    let display_format = rustproto::exts::display_format.get(source);
+    let generate_map_conversions = rustproto::exts::generate_map_conversions.get(source);
+    let map_key_field = None;
+    let nan_eq = rustproto::exts::nan_eq.get(source);
+    let impl_eq = rustproto::exts::impl_eq.get(source);
+    let impl_hash = rustproto::exts::impl_hash.get(source);
+    let typestate_builder = rustproto::exts::typestate_builder.get(source);
+    let enum_lookup_threshold = rustproto::exts::enum_lookup_threshold.get(source);
+    let generate_parts = rustproto::exts::generate_parts.get(source);
+    let nested_type_strategy = None;
+    let implement_traits = None;
     let lite_runtime = None;
     let gen_mod_rs = None;
+    let split_impls = None;
     let inside_protobuf = None;
-    Customize {
+    Ok(Customize {
         expose_oneof,
+        generate_oneof_case_enum,
         expose_fields,
         generate_accessors,
         generate_getter,
+        scalar_getter_style,
+        generate_option_getters,
+        track_caller_accessors,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
+        repeated_type,
         serde_derive,
         serde_derive_cfg,
+        serde_default,
+        generate_presence_set,
+        generate_retain_fields,
+        per_message_descriptor,
+        repeated_boxed_slice,
+        bytes_wrapper,
+        repeated_index_impl,
+        primary_key_field,
+        display_format,
+        generate_map_conversions,
+        map_key_field,
+        nan_eq,
+        impl_eq,
+        impl_hash,
+        typestate_builder,
+        enum_lookup_threshold,
+        generate_parts,
+        nested_type_strategy,
+        implement_traits,
         lite_runtime,
         gen_mod_rs,
+        split_impls,
         inside_protobuf,
         _future_options: (),
-    }
+    })
 }
 
-pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
+pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> anyhow::Result<Customize> {
     let expose_oneof = None;
+    let generate_oneof_case_enum = None;
     let expose_fields = rustproto::exts::expose_fields_field.get(source);
     let generate_accessors = rustproto::exts::generate_accessors_field.get(source);
     let generate_getter = rustproto::exts::generate_getter_field.get(source);
+    let scalar_getter_style =
+        ScalarGetterStyle::from_rustproto(rustproto::exts::scalar_getter_style_field.get(source))?;
+    let generate_option_getters = rustproto::exts::generate_option_getters_field.get(source);
+    let track_caller_accessors = rustproto::exts::track_caller_accessors_field.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes_field.get(source);
     let carllerche_bytes_for_string =
         rustproto::exts::carllerche_bytes_for_string_field.get(source);
+    let repeated_type = rustproto::exts::repeated_type_field.get(source);
     let serde_derive = None;
     let serde_derive_cfg = None;
+    let serde_default = None;
+    let generate_presence_set = None;
+    let generate_retain_fields = None;
+    let per_message_descriptor = None;
+    let repeated_boxed_slice = None;
+    let bytes_wrapper = None;
+    let repeated_index_impl = None;
+    let primary_key_field = None;
+    let display_format = None;
+    let generate_map_conversions = rustproto::exts::generate_map_conversions_field.get(source);
+    let map_key_field = rustproto::exts::map_key_field.get(source);
+    let nan_eq = None;
+    let impl_eq = None;
+    let impl_hash = None;
+    let typestate_builder = None;
+    let enum_lookup_threshold = None;
+    let generate_parts = None;
+    let nested_type_strategy = None;
+    let implement_traits = None;
     let lite_runtime = None;
     let gen_mod_rs = None;
+    let split_impls = None;
     let inside_protobuf = None;
-    Customize {
+    Ok(Customize {
         expose_oneof,
+        generate_oneof_case_enum,
         expose_fields,
         generate_accessors,
         generate_getter,
+        scalar_getter_style,
+        generate_option_getters,
+        track_caller_accessors,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
+        repeated_type,
         serde_derive,
         serde_derive_cfg,
+        serde_default,
+        generate_presence_set,
+        generate_retain_fields,
+        per_message_descriptor,
+        repeated_boxed_slice,
+        bytes_wrapper,
+        repeated_index_impl,
+        primary_key_field,
+        display_format,
+        generate_map_conversions,
+        map_key_field,
+        nan_eq,
+        impl_eq,
+        impl_hash,
+        typestate_builder,
+        enum_lookup_threshold,
+        generate_parts,
+        nested_type_strategy,
+        implement_traits,
         lite_runtime,
         gen_mod_rs,
+        split_impls,
         inside_protobuf,
         _future_options: (),
-    }
+    })
 }
 
-pub fn customize_from_rustproto_for_file(source: &FileOptions) -> Customize {
+pub fn customize_from_rustproto_for_file(source: &FileOptions) -> anyhow::Result<Customize> {
     let expose_oneof = rustproto::exts::expose_oneof_all.get(source);
+    let generate_oneof_case_enum = rustproto::exts::generate_oneof_case_enum_all.get(source);
     let expose_fields = rustproto::exts::expose_fields_all.get(source);
     let generate_accessors = rustproto::exts::generate_accessors_all.get(source);
     let generate_getter = rustproto::exts::generate_getter_all.get(source);
+    let scalar_getter_style =
+        ScalarGetterStyle::from_rustproto(rustproto::exts::scalar_getter_style_all.get(source))?;
+    let generate_option_getters = rustproto::exts::generate_option_getters_all.get(source);
+    let track_caller_accessors = rustproto::exts::track_caller_accessors_all.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes_all.get(source);
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string_all.get(source);
+    let repeated_type = rustproto::exts::repeated_type_all.get(source);
     let serde_derive = rustproto::exts::serde_derive_all.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg_all.get(source);
+    let serde_default = rustproto::exts::serde_default_all.get(source);
+    let generate_presence_set = rustproto::exts::generate_presence_set_all.get(source);
+    let generate_retain_fields = rustproto::exts::generate_retain_fields_all.get(source);
+    let per_message_descriptor = rustproto::exts::per_message_descriptor_all.get(source);
+    let repeated_boxed_slice = rustproto::exts::repeated_boxed_slice_all.get(source);
+    let bytes_wrapper = None;
+    let repeated_index_impl = None;
+    let primary_key_field = None;
+    let display_format = None;
+    let generate_map_conversions = rustproto::exts::generate_map_conversions_all.get(source);
+    let map_key_field = None;
+    let nan_eq = rustproto::exts::nan_eq_all.get(source);
+    let impl_eq = rustproto::exts::impl_eq_all.get(source);
+    let impl_hash = rustproto::exts::impl_hash_all.get(source);
+    let typestate_builder = rustproto::exts::typestate_builder_all.get(source);
+    let enum_lookup_threshold = rustproto::exts::enum_lookup_threshold_all.get(source);
+    let generate_parts = rustproto::exts::generate_parts_all.get(source);
+    let nested_type_strategy =
+        NestedTypeStrategy::from_rustproto(rustproto::exts::nested_type_strategy_all.get(source))?;
+    let implement_traits = None;
     let lite_runtime = rustproto::exts::lite_runtime_all.get(source);
     let gen_mod_rs = None;
+    let split_impls = None;
     let inside_protobuf = None;
-    Customize {
+    Ok(Customize {
         expose_oneof,
+        generate_oneof_case_enum,
         expose_fields,
         generate_accessors,
         generate_getter,
+        scalar_getter_style,
+        generate_option_getters,
+        track_caller_accessors,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
+        repeated_type,
         serde_derive,
         serde_derive_cfg,
+        serde_default,
+        generate_presence_set,
+        generate_retain_fields,
+        per_message_descriptor,
+        repeated_boxed_slice,
+        bytes_wrapper,
+        repeated_index_impl,
+        primary_key_field,
+        display_format,
+        generate_map_conversions,
+        map_key_field,
+        nan_eq,
+        impl_eq,
+        impl_hash,
+        typestate_builder,
+        enum_lookup_threshold,
+        generate_parts,
+        nested_type_strategy,
+        implement_traits,
         lite_runtime,
         inside_protobuf,
         gen_mod_rs,
+        split_impls,
         _future_options: (),
-    }
+    })
 }
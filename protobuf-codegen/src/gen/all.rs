@@ -20,9 +20,7 @@ pub(crate) fn gen_all(
 ) -> anyhow::Result<Vec<compiler_plugin::GenResult>> {
     let file_descriptors = FileDescriptor::new_dynamic_fds(file_descriptors.to_vec());
 
-    let root_scope = RootScope {
-        file_descriptors: &file_descriptors,
-    };
+    let root_scope = RootScope::new(&file_descriptors, customize)?;
 
     let mut results: Vec<compiler_plugin::GenResult> = Vec::new();
     let files_map: HashMap<&ProtoPath, &FileDescriptor> = file_descriptors
@@ -38,17 +36,18 @@ pub(crate) fn gen_all(
             file_name,
             files_map.keys()
         ));
-        let gen_file_result = gen_file(file, &files_map, &root_scope, customize, parser);
+        let gen_file_result = gen_file(file, &files_map, &root_scope, customize, parser)?;
         results.push(gen_file_result.compiler_plugin_result);
+        results.extend(gen_file_result.impls_result);
         mods.push(gen_file_result.mod_name);
     }
 
     if customize.inside_protobuf.unwrap_or(false) {
-        results.push(gen_well_known_types_mod(&file_descriptors));
+        results.push(gen_well_known_types_mod(&file_descriptors)?);
     }
 
     if customize.gen_mod_rs.unwrap_or(false) {
-        results.push(gen_mod_rs(&mods));
+        results.push(gen_mod_rs(&mods, customize));
     }
 
     Ok(results)
@@ -143,7 +143,7 @@ impl<'a> CodeWriter<'a> {
 
     pub fn impl_self_block<S: AsRef<str>, F>(&mut self, name: S, cb: F)
     where
-        F: Fn(&mut CodeWriter),
+        F: FnOnce(&mut CodeWriter),
     {
         self.expr_block(&format!("impl {}", name.as_ref()), cb);
     }
@@ -197,7 +197,10 @@ impl<'a> CodeWriter<'a> {
         match vis {
             Visibility::Public => self.pub_field_decl(name, field_type),
             Visibility::Default => self.field_decl(name, field_type),
-            Visibility::Path(..) => unimplemented!(),
+            Visibility::Path(p) if p.is_empty() => self.field_decl(name, field_type),
+            Visibility::Path(p) => {
+                self.write_line(&format!("pub(in {}) {}: {},", p, name, field_type))
+            }
         }
     }
 
@@ -334,7 +337,7 @@ impl<'a> CodeWriter<'a> {
 
     pub fn pub_mod<F>(&mut self, name: &str, cb: F)
     where
-        F: Fn(&mut CodeWriter),
+        F: FnOnce(&mut CodeWriter),
     {
         self.expr_block(&format!("pub mod {}", name), cb)
     }
@@ -0,0 +1,88 @@
+//! Parser of `display_format` option templates.
+
+/// Template converted to Rust format string.
+#[derive(Debug, PartialEq)]
+pub(crate) struct DisplayFormat {
+    /// Format string with positional arguments like `User {0} ({1})`.
+    pub format: String,
+    /// Field names referenced by positional arguments.
+    pub fields: Vec<String>,
+}
+
+/// Parse template like `User {id} ({name:>10})`.
+pub(crate) fn parse_display_format(template: &str) -> Result<DisplayFormat, String> {
+    let mut format = String::new();
+    let mut fields: Vec<String> = Vec::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err(format!("unclosed placeholder in `{}`", template)),
+                    }
+                }
+                let (name, spec) = match placeholder.find(':') {
+                    Some(colon) => placeholder.split_at(colon),
+                    None => (&placeholder[..], ""),
+                };
+                if name.is_empty() {
+                    return Err(format!("empty placeholder in `{}`", template));
+                }
+                let index = match fields.iter().position(|f| f == name) {
+                    Some(index) => index,
+                    None => {
+                        fields.push(name.to_owned());
+                        fields.len() - 1
+                    }
+                };
+                format.push_str(&format!("{{{}{}}}", index, spec));
+            }
+            '}' => return Err(format!("unmatched `}}` in `{}`", template)),
+            c => format.push(c),
+        }
+    }
+    Ok(DisplayFormat { format, fields })
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_display_format;
+    use super::DisplayFormat;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Ok(DisplayFormat {
+                format: "User {0} ({1:>5}) {{{0}}}".to_owned(),
+                fields: vec!["id".to_owned(), "name".to_owned()],
+            }),
+            parse_display_format("User {id} ({name:>5}) {{{id}}}")
+        );
+        assert_eq!(
+            Ok(DisplayFormat {
+                format: "plain".to_owned(),
+                fields: Vec::new(),
+            }),
+            parse_display_format("plain")
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse_display_format("{id").is_err());
+        assert!(parse_display_format("{}").is_err());
+        assert!(parse_display_format("id}").is_err());
+    }
+}
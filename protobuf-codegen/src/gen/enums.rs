@@ -12,7 +12,9 @@ use crate::gen::scope::EnumValueWithContext;
 use crate::gen::scope::EnumWithScope;
 use crate::gen::scope::RootScope;
 use crate::gen::scope::WithScope;
+use crate::gen::scope::ENUM_DEFAULT_CONST;
 use crate::gen::serde;
+use crate::gen::split_impls;
 use crate::Customize;
 
 #[derive(Clone)]
@@ -52,6 +54,7 @@ impl<'a> EnumValueGen<'a> {
 // Codegen for enum definition
 pub(crate) struct EnumGen<'a> {
     enum_with_scope: &'a EnumWithScope<'a>,
+    default_value: EnumValueWithContext<'a>,
     file_index: &'a FileIndex,
     type_name: RustIdentWithPath,
     lite_runtime: bool,
@@ -68,7 +71,7 @@ impl<'a> EnumGen<'a> {
         _root_scope: &RootScope,
         path: &'a [i32],
         info: Option<&'a SourceCodeInfo>,
-    ) -> EnumGen<'a> {
+    ) -> anyhow::Result<EnumGen<'a>> {
         let lite_runtime = customize.lite_runtime.unwrap_or_else(|| {
             enum_with_scope
                 .get_scope()
@@ -79,15 +82,16 @@ impl<'a> EnumGen<'a> {
                 == file_options::OptimizeMode::LITE_RUNTIME
         });
 
-        EnumGen {
+        Ok(EnumGen {
             enum_with_scope,
+            default_value: enum_with_scope.default_value()?,
             type_name: enum_with_scope.rust_name().to_path(),
             lite_runtime,
             customize: customize.clone(),
             path,
             info,
             file_index,
-        }
+        })
     }
 
     fn index_in_file(&self) -> u32 {
@@ -125,8 +129,16 @@ impl<'a> EnumGen<'a> {
         r
     }
 
-    pub fn write(&self, w: &mut CodeWriter) {
+    /// Write enum, and its implementations with `impls` writer if specified.
+    pub fn write(&self, w: &mut CodeWriter, impls: Option<&mut CodeWriter>) {
         self.write_enum(w);
+        match impls {
+            Some(impls) => self.write_impls(impls),
+            None => self.write_impls(w),
+        }
+    }
+
+    fn write_impls(&self, w: &mut CodeWriter) {
         if self.allow_alias() {
             w.write_line("");
             self.write_impl_eq(w);
@@ -145,10 +157,25 @@ impl<'a> EnumGen<'a> {
 
     fn write_impl_self(&self, w: &mut CodeWriter) {
         w.impl_self_block(&format!("{}", self.type_name), |w| {
+            if self.enum_with_scope.has_default_const() {
+                self.write_default_const(w);
+                w.write_line("");
+            }
             self.write_generated_enum_descriptor_data(w);
         });
     }
 
+    fn write_default_const(&self, w: &mut CodeWriter) {
+        let default_value = self.default_value.clone();
+        w.write_line("/// Default value of this enum, also returned by `Default::default()`.");
+        w.write_line(&format!(
+            "pub const {}: {} = {};",
+            ENUM_DEFAULT_CONST,
+            self.type_name,
+            EnumValueGen::parse(default_value, &self.type_name).rust_name_outer(),
+        ));
+    }
+
     fn write_enum(&self, w: &mut CodeWriter) {
         w.all_documentation(self.info, self.path);
 
@@ -171,9 +198,21 @@ impl<'a> EnumGen<'a> {
             &self.customize,
             "derive(::serde::Serialize, ::serde::Deserialize)",
         );
+        static VALUE_FIELD_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
+        let value_field_number = *VALUE_FIELD_NUMBER.get(|| {
+            protobuf::reflect::MessageDescriptor::for_type::<EnumDescriptorProto>()
+                .get_field_by_name("value")
+                .expect("`value` must exist")
+                .get_proto()
+                .get_number()
+        });
+
         let ref type_name = self.type_name;
         w.expr_block(&format!("pub enum {}", type_name), |w| {
-            for value in self.values_all() {
+            for (id, value) in self.values_all().into_iter().enumerate() {
+                let mut path = self.path.to_vec();
+                path.extend_from_slice(&[value_field_number, id as i32]);
+                w.all_documentation(self.info, &path);
                 if self.allow_alias() {
                     w.write_line(&format!(
                         "{}, // {}",
@@ -208,6 +247,39 @@ impl<'a> EnumGen<'a> {
         });
     }
 
+    fn use_lookup_table(&self) -> bool {
+        match self.customize.enum_lookup_threshold {
+            Some(threshold) => self.values_unique().len() > threshold as usize,
+            None => false,
+        }
+    }
+
+    /// Body of `from_i32` for huge enums: binary search in array sorted by number.
+    fn write_from_i32_lookup_table(&self, w: &mut CodeWriter) {
+        let mut values = self.values_unique();
+        values.sort_by_key(|v| v.number());
+        w.write_line(&format!(
+            "static values_by_number: &'static [(i32, {})] = &[",
+            self.type_name
+        ));
+        w.indented(|w| {
+            for value in &values {
+                w.write_line(&format!(
+                    "({}, {}),",
+                    value.number(),
+                    value.rust_name_outer()
+                ));
+            }
+        });
+        w.write_line("];");
+        w.write_line("values_by_number");
+        w.indented(|w| {
+            w.write_line(".binary_search_by_key(&value, |&(n, _)| n)");
+            w.write_line(".ok()");
+            w.write_line(".map(|i| values_by_number[i].1)");
+        });
+    }
+
     fn write_impl_enum(&self, w: &mut CodeWriter) {
         let ref type_name = self.type_name;
         w.impl_for_block(
@@ -224,17 +296,21 @@ impl<'a> EnumGen<'a> {
                         type_name
                     ),
                     |w| {
-                        w.match_expr("value", |w| {
-                            let values = self.values_unique();
-                            for value in values {
-                                w.write_line(&format!(
-                                    "{} => ::std::option::Option::Some({}),",
-                                    value.number(),
-                                    value.rust_name_outer()
-                                ));
-                            }
-                            w.write_line(&format!("_ => {}", EXPR_NONE));
-                        });
+                        if self.use_lookup_table() {
+                            self.write_from_i32_lookup_table(w);
+                        } else {
+                            w.match_expr("value", |w| {
+                                let values = self.values_unique();
+                                for value in values {
+                                    w.write_line(&format!(
+                                        "{} => ::std::option::Option::Some({}),",
+                                        value.number(),
+                                        value.rust_name_outer()
+                                    ));
+                                }
+                                w.write_line(&format!("_ => {}", EXPR_NONE));
+                            });
+                        }
                     },
                 );
 
@@ -283,11 +359,9 @@ impl<'a> EnumGen<'a> {
             protobuf_crate_path(&self.customize)
         );
         w.fn_block(
-            Visibility::Path(
-                self.enum_with_scope
-                    .get_scope()
-                    .rust_path_to_file()
-                    .to_reverse(),
+            split_impls::file_visibility(
+                &self.customize,
+                &self.enum_with_scope.get_scope().rust_path_to_file(),
             ),
             &sig,
             |w| {
@@ -348,10 +422,10 @@ impl<'a> EnumGen<'a> {
     }
 
     fn write_impl_default(&self, w: &mut CodeWriter) {
-        let first_value = &self.enum_with_scope.values()[0];
-        if first_value.proto.get_proto().get_number() != 0 {
+        let default_value = &self.default_value;
+        if default_value.proto.get_proto().get_number() != 0 {
             // This warning is emitted only for proto2
-            // (because in proto3 first enum variant number is always 0).
+            // (because in proto3 default enum variant number is always 0).
             // `Default` implemented unconditionally to simplify certain
             // generic operations, e. g. reading a map.
             // Also, note that even in proto2 some operations fallback to
@@ -367,7 +441,7 @@ impl<'a> EnumGen<'a> {
                     w.write_line(&format!(
                         "{}::{}",
                         &self.type_name,
-                        &first_value.rust_name()
+                        &default_value.rust_name_or_default_const()
                     ))
                 });
             },
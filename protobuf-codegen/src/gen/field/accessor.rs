@@ -36,6 +36,16 @@ impl AccessorFn {
 }
 
 impl FieldGen<'_> {
+    // getter which returns copy scalars by value
+    fn make_accessor_fn_get(&self) -> String {
+        let message = self.proto_field.message.rust_name();
+        if self.get_xxx_returns_ref_to_copy() {
+            format!("|m: &{}| {{ *m.get_{}() }}", message, self.rust_name)
+        } else {
+            format!("{}::get_{}", message, self.rust_name)
+        }
+    }
+
     fn make_accessor_fns_lambda(&self) -> Vec<String> {
         let message = self.proto_field.message.rust_name();
         vec![
@@ -49,7 +59,7 @@ impl FieldGen<'_> {
         vec![
             format!("|m: &{}| {{ &m.{} }}", message, self.rust_name),
             format!("|m: &mut {}| {{ &mut m.{} }}", message, self.rust_name),
-            format!("{}::get_{}", message, self.rust_name),
+            self.make_accessor_fn_get(),
         ]
     }
 
@@ -66,7 +76,7 @@ impl FieldGen<'_> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
-            format!("{}::get_{}", message, self.rust_name),
+            self.make_accessor_fn_get(),
         ]
     }
 
@@ -74,7 +84,7 @@ impl FieldGen<'_> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
-            format!("{}::get_{}", message, self.rust_name),
+            self.make_accessor_fn_get(),
             format!("{}::set_{}", message, self.rust_name),
         ]
     }
@@ -83,7 +93,7 @@ impl FieldGen<'_> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
-            format!("{}::get_{}", message, self.rust_name),
+            self.make_accessor_fn_get(),
             format!("{}::mut_{}", message, self.rust_name),
             format!("{}::set_{}", message, self.rust_name),
         ]
@@ -100,8 +110,10 @@ impl FieldGen<'_> {
 
     fn accessor_fn_repeated(&self, repeated_field: &RepeatedField) -> AccessorFn {
         let RepeatedField { .. } = repeated_field;
-        let name = match repeated_field.kind() {
+        let name = match repeated_field.kind {
             RepeatedFieldKind::Vec => "make_vec_simpler_accessor",
+            RepeatedFieldKind::SmallVec(..) => "make_smallvec_simpler_accessor",
+            RepeatedFieldKind::BoxedSlice => "make_boxed_slice_simpler_accessor",
         };
         AccessorFn {
             name: name.to_owned(),
@@ -7,6 +7,7 @@ use protobuf_parse::ProtobufAbsolutePath;
 
 use crate::customize::customize_from_rustproto_for_field;
 use crate::customize::Customize;
+use crate::customize::ScalarGetterStyle;
 use crate::gen::code_writer::CodeWriter;
 use crate::gen::code_writer::Visibility;
 use crate::gen::file_and_mod::FileAndMod;
@@ -14,8 +15,11 @@ use crate::gen::inside::protobuf_crate_path;
 use crate::gen::map::map_entry;
 use crate::gen::message::RustTypeMessage;
 use crate::gen::oneof::OneofField;
+use crate::gen::repeated_type::parse_repeated_type;
 use crate::gen::rust;
+use crate::gen::rust::EXPR_BOXED_SLICE_NEW;
 use crate::gen::rust::EXPR_NONE;
+use crate::gen::rust::EXPR_SMALLVEC_NEW;
 use crate::gen::rust::EXPR_VEC_NEW;
 use crate::gen::rust_name::RustIdent;
 use crate::gen::rust_name::RustIdentWithPath;
@@ -28,6 +32,7 @@ use crate::gen::scope::MessageWithScope;
 use crate::gen::scope::RootScope;
 use crate::gen::scope::WithScope;
 use crate::gen::serde;
+use crate::gen::split_impls;
 use crate::gen::syntax::Syntax;
 
 mod accessor;
@@ -240,10 +245,14 @@ impl<'a> SingularField<'a> {
     }
 }
 
-/// Repeated field can be `Vec<T>` or `RepeatedField<T>`.
-#[derive(Eq, PartialEq, Copy, Clone)]
+/// Repeated field can be `Vec<T>`, `SmallVec<[T; N]>` or `Box<[T]>`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum RepeatedFieldKind {
     Vec,
+    /// `SmallVec` with given number of inline elements
+    SmallVec(usize),
+    /// `Box<[T]>`, read into a temporary `Vec`
+    BoxedSlice,
 }
 
 impl RepeatedFieldKind {
@@ -251,12 +260,16 @@ impl RepeatedFieldKind {
         let element_type = Box::new(element_type);
         match self {
             RepeatedFieldKind::Vec => RustType::Vec(element_type),
+            RepeatedFieldKind::SmallVec(inline) => RustType::SmallVec(element_type, *inline),
+            RepeatedFieldKind::BoxedSlice => RustType::BoxedSlice(element_type),
         }
     }
 
     fn default(&self) -> String {
         match self {
             RepeatedFieldKind::Vec => EXPR_VEC_NEW.to_owned(),
+            RepeatedFieldKind::SmallVec(..) => EXPR_SMALLVEC_NEW.to_owned(),
+            RepeatedFieldKind::BoxedSlice => EXPR_BOXED_SLICE_NEW.to_owned(),
         }
     }
 }
@@ -265,20 +278,17 @@ impl RepeatedFieldKind {
 pub(crate) struct RepeatedField<'a> {
     pub elem: FieldElem<'a>,
     pub packed: bool,
+    pub kind: RepeatedFieldKind,
 }
 
 impl<'a> RepeatedField<'a> {
-    fn kind(&self) -> RepeatedFieldKind {
-        RepeatedFieldKind::Vec
-    }
-
     fn rust_type(&self, reference: &FileAndMod) -> RustType {
-        self.kind()
+        self.kind
             .wrap_element(self.elem.rust_storage_elem_type(reference))
     }
 
     fn default(&self) -> String {
-        self.kind().default()
+        self.kind.default()
     }
 }
 
@@ -362,7 +372,7 @@ impl<'a> FieldElemEnum<'a> {
     fn enum_rust_type(&self, reference: &FileAndMod) -> RustType {
         RustType::Enum(
             self.rust_name_relative(reference),
-            self.default_value.rust_name(),
+            self.default_value.rust_name_or_default_const(),
             self.default_value.proto.get_proto().get_number(),
         )
     }
@@ -370,7 +380,7 @@ impl<'a> FieldElemEnum<'a> {
     fn enum_or_unknown_rust_type(&self, reference: &FileAndMod) -> RustType {
         RustType::EnumOrUnknown(
             self.rust_name_relative(reference),
-            self.default_value.rust_name(),
+            self.default_value.rust_name_or_default_const(),
             self.default_value.proto.get_proto().get_number(),
         )
     }
@@ -420,6 +430,14 @@ impl<'a> FieldElem<'a> {
         type_is_copy(self.proto_type())
     }
 
+    pub(crate) fn is_float(&self) -> bool {
+        match self.proto_type() {
+            field_descriptor_proto::Type::TYPE_FLOAT
+            | field_descriptor_proto::Type::TYPE_DOUBLE => true,
+            _ => false,
+        }
+    }
+
     pub fn rust_storage_elem_type(&self, reference: &FileAndMod) -> RustType {
         match *self {
             FieldElem::Primitive(t, PrimitiveTypeVariant::Default) => rust_name(t),
@@ -478,9 +496,9 @@ fn field_elem<'a>(
     parse_map: bool,
     customize: &Customize,
     current_file_path: &RustRelativePath,
-) -> FieldElem<'a> {
+) -> anyhow::Result<FieldElem<'a>> {
     if field.field.get_proto().get_field_type() == field_descriptor_proto::Type::TYPE_GROUP {
-        FieldElem::Group
+        Ok(FieldElem::Group)
     } else if field.field.get_proto().has_type_name() {
         let message_or_enum = root_scope.find_message_or_enum(&ProtobufAbsolutePath::from(
             field.field.get_proto().get_type_name(),
@@ -493,16 +511,16 @@ fn field_elem<'a>(
                 let entry_key_value =
                     if let (true, Some((key, value))) = (parse_map, map_entry(&message)) {
                         Some(Box::new(EntryKeyValue(
-                            field_elem(&key, root_scope, false, customize, current_file_path),
-                            field_elem(&value, root_scope, false, customize, current_file_path),
+                            field_elem(&key, root_scope, false, customize, current_file_path)?,
+                            field_elem(&value, root_scope, false, customize, current_file_path)?,
                         )))
                     } else {
                         None
                     };
-                FieldElem::Message(FieldElemMessage {
+                Ok(FieldElem::Message(FieldElemMessage {
                     map_entry: entry_key_value,
                     message: message.clone(),
-                })
+                }))
             }
             (
                 field_descriptor_proto::Type::TYPE_ENUM,
@@ -511,9 +529,9 @@ fn field_elem<'a>(
                 let default_value = if field.field.get_proto().has_default_value() {
                     enum_with_scope.value_by_name(field.field.get_proto().get_default_value())
                 } else {
-                    enum_with_scope.values()[0].clone()
+                    enum_with_scope.default_value()?
                 };
-                FieldElem::Enum(FieldElemEnum { default_value })
+                Ok(FieldElem::Enum(FieldElemEnum { default_value }))
             }
             _ => panic!(
                 "unknown named type: {:?}",
@@ -540,7 +558,7 @@ fn field_elem<'a>(
             t => FieldElem::Primitive(t, PrimitiveTypeVariant::Default),
         };
 
-        elem
+        Ok(elem)
     } else {
         panic!(
             "neither type_name, nor field_type specified for field: {}",
@@ -575,11 +593,11 @@ impl<'a> FieldGen<'a> {
         customize: &Customize,
         path: Vec<i32>,
         info: Option<&'a SourceCodeInfo>,
-    ) -> FieldGen<'a> {
+    ) -> anyhow::Result<FieldGen<'a>> {
         let mut customize = customize.clone();
         customize.update_with(&customize_from_rustproto_for_field(
             field.field.get_proto().options.get_or_default(),
-        ));
+        )?);
 
         let elem = field_elem(
             &field,
@@ -587,7 +605,7 @@ impl<'a> FieldGen<'a> {
             true,
             &customize,
             &field.message.scope.rust_path_to_file(),
-        );
+        )?;
 
         let syntax = field.message.scope.file_scope.syntax();
 
@@ -603,7 +621,10 @@ impl<'a> FieldGen<'a> {
         let generate_accessors = customize
             .generate_accessors
             .unwrap_or(default_generate_accessors)
-            || field.is_oneof();
+            || field.is_oneof()
+            || (customize.typestate_builder.unwrap_or(false)
+                && field.field.get_proto().get_label()
+                    == field_descriptor_proto::Label::LABEL_REQUIRED);
 
         let default_generate_getter = generate_accessors || field_may_have_custom_default_value;
         let generate_getter =
@@ -632,6 +653,21 @@ impl<'a> FieldGen<'a> {
                         .options
                         .get_or_default()
                         .get_packed(),
+                    kind: match customize.repeated_type {
+                        Some(ref repeated_type) => {
+                            parse_repeated_type(repeated_type).map_err(|e| {
+                                anyhow::anyhow!(
+                                    "incorrect repeated type of field `{}`: {}",
+                                    field.name(),
+                                    e
+                                )
+                            })?
+                        }
+                        None if customize.repeated_boxed_slice.unwrap_or(false) => {
+                            RepeatedFieldKind::BoxedSlice
+                        }
+                        None => RepeatedFieldKind::Vec,
+                    },
                 }),
             }
         } else if let Some(oneof) = field.oneof() {
@@ -658,7 +694,7 @@ impl<'a> FieldGen<'a> {
             FieldKind::Singular(SingularField { elem, flag })
         };
 
-        FieldGen {
+        Ok(FieldGen {
             _root_scope: root_scope,
             syntax: field.message.get_scope().file_scope.syntax(),
             rust_name: rust_field_name_for_protobuf_field_name(&field.field.get_name()),
@@ -673,7 +709,7 @@ impl<'a> FieldGen<'a> {
             customize,
             path,
             info,
-        }
+        })
     }
 
     // for message level
@@ -688,7 +724,7 @@ impl<'a> FieldGen<'a> {
         rt::tag_size(self.proto_field.number())
     }
 
-    fn is_singular(&self) -> bool {
+    pub(crate) fn is_singular(&self) -> bool {
         match self.kind {
             FieldKind::Singular(..) => true,
             _ => false,
@@ -1151,13 +1187,19 @@ impl<'a> FieldGen<'a> {
 
     /// Field visibility in message struct
     fn visibility(&self) -> Visibility {
+        let private = || {
+            split_impls::private_visibility(
+                &self.customize,
+                &self.proto_field.message.scope.rust_path_to_file(),
+            )
+        };
         if self.expose_field {
             Visibility::Public
         } else {
             match self.kind {
-                FieldKind::Repeated(..) => Visibility::Default,
+                FieldKind::Repeated(..) => private(),
                 FieldKind::Singular(SingularField { ref flag, .. }) => match *flag {
-                    SingularFieldFlag::WithFlag { .. } => Visibility::Default,
+                    SingularFieldFlag::WithFlag { .. } => private(),
                     SingularFieldFlag::WithoutFlag => Visibility::Public,
                 },
                 FieldKind::Map(..) => Visibility::Public,
@@ -1166,11 +1208,16 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    /// Doc comment from `.proto` file, for struct field or oneof enum variant.
+    pub(crate) fn write_documentation(&self, w: &mut CodeWriter) {
+        w.all_documentation(self.info, &self.path);
+    }
+
     pub fn write_struct_field(&self, w: &mut CodeWriter) {
         if self.proto_type == field_descriptor_proto::Type::TYPE_GROUP {
             w.comment(&format!("{}: <group>", &self.rust_name));
         } else {
-            w.all_documentation(self.info, &self.path);
+            self.write_documentation(w);
 
             self.write_serde_attr(w);
             let vis = self.visibility();
@@ -1190,6 +1237,17 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    /// Public field of message parts struct, see `Customize::generate_parts`.
+    pub(crate) fn write_parts_field(&self, w: &mut CodeWriter) {
+        self.write_documentation(w);
+        w.pub_field_decl(
+            self.rust_name.get(),
+            &self
+                .full_storage_type(&self.get_file_and_mod())
+                .to_code(&self.customize),
+        );
+    }
+
     fn write_serde_attr(&self, w: &mut CodeWriter) {
         let mut tags = Vec::new();
         if self.rust_name.get() != &self.serde_name {
@@ -1201,6 +1259,20 @@ impl<'a> FieldGen<'a> {
             _ => {}
         }
 
+        if self.customize.serde_default.unwrap_or(false) {
+            let storage_type = self.full_storage_type(
+                &self
+                    .proto_field
+                    .message
+                    .scope
+                    .get_file_and_mod(self.customize.clone()),
+            );
+            tags.push(format!(
+                r#"skip_serializing_if = "{}""#,
+                serde::skip_serializing_if(&storage_type, &self.customize)
+            ));
+        }
+
         if !tags.is_empty() {
             serde::write_serde_attr(w, &self.customize, &format!("serde({})", tags.join(", ")));
         }
@@ -1312,6 +1384,39 @@ impl<'a> FieldGen<'a> {
         w.if_stmt(self_field_is_none, cb)
     }
 
+    /// Field is set: singular field has value (or is not default
+    /// for fields without presence), repeated field is not empty,
+    /// or oneof is set to this field.
+    pub fn write_if_self_field_is_present<F>(&self, w: &mut CodeWriter, cb: F)
+    where
+        F: Fn(&mut CodeWriter),
+    {
+        match self.kind {
+            FieldKind::Oneof(ref oneof) => {
+                let file_and_mod = self.get_file_and_mod();
+                w.if_let_stmt(
+                    &format!(
+                        "::std::option::Option::Some({}(..))",
+                        oneof.variant_path(&file_and_mod.relative_mod.into_path())
+                    ),
+                    &format!("self.{}", oneof.oneof_field_name),
+                    cb,
+                );
+            }
+            FieldKind::Repeated(..) | FieldKind::Map(..) => {
+                self.write_if_self_field_is_not_empty(w, cb);
+            }
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithFlag { .. },
+                ..
+            }) => {
+                let self_field_is_some = self.self_field_is_some();
+                w.if_stmt(self_field_is_some, cb);
+            }
+            FieldKind::Singular(ref s) => self.write_if_let_self_field_is_some(s, w, |_, w| cb(w)),
+        }
+    }
+
     // repeated or singular
     pub fn write_for_self_field<F>(&self, w: &mut CodeWriter, varn: &str, cb: F)
     where
@@ -1336,6 +1441,15 @@ impl<'a> FieldGen<'a> {
                     |w| cb(w, &oneof_field.elem.rust_storage_elem_type(&file_and_mod)),
                 )
             }
+            FieldKind::Repeated(RepeatedField {
+                kind: RepeatedFieldKind::BoxedSlice,
+                ..
+            }) => {
+                let v_type = self.full_storage_iter_elem_type(&file_and_mod);
+                let self_field = self.self_field();
+                // `&Box<[T]>` is not `IntoIterator`
+                w.for_stmt(&format!("{}.iter()", self_field), varn, |w| cb(w, &v_type));
+            }
             _ => {
                 let v_type = self.full_storage_iter_elem_type(&file_and_mod);
                 let self_field = self.self_field();
@@ -1577,12 +1691,12 @@ impl<'a> FieldGen<'a> {
             _ => "",
         };
         w.write_line(&format!(
-            "{}::rt::read_repeated_{}{}_into{}(wire_type, is, &mut self.{})?;",
+            "{}::rt::read_repeated_{}{}_into{}(wire_type, is, &mut {})?;",
             protobuf_crate_path(&self.customize),
             carllerche,
             type_name_for_fn,
             into_what_suffix,
-            self.rust_name,
+            self.merge_from_repeated_target(r),
         ));
     }
 
@@ -1733,6 +1847,50 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    /// Name of local `Vec` which `Box<[T]>` field is read into in `merge_from`.
+    fn boxed_slice_vec_var(&self) -> String {
+        format!("{}_vec", self.proto_field.name())
+    }
+
+    /// Expression repeated field elements are read into in `merge_from`.
+    fn merge_from_repeated_target(&self, field: &RepeatedField) -> String {
+        match field.kind {
+            RepeatedFieldKind::BoxedSlice => self.boxed_slice_vec_var(),
+            RepeatedFieldKind::Vec | RepeatedFieldKind::SmallVec(..) => self.self_field(),
+        }
+    }
+
+    /// Move `Box<[T]>` field into a `Vec` before reading fields in `merge_from`,
+    /// to avoid reallocating the slice for each read element.
+    pub fn write_merge_from_boxed_slice_begin(&self, w: &mut CodeWriter) {
+        if let FieldKind::Repeated(RepeatedField {
+            kind: RepeatedFieldKind::BoxedSlice,
+            ..
+        }) = self.kind
+        {
+            w.write_line(&format!(
+                "let mut {} = ::std::mem::take(&mut {}).into_vec();",
+                self.boxed_slice_vec_var(),
+                self.self_field()
+            ));
+        }
+    }
+
+    /// Move elements read in `merge_from` back into `Box<[T]>` field.
+    pub fn write_merge_from_boxed_slice_end(&self, w: &mut CodeWriter) {
+        if let FieldKind::Repeated(RepeatedField {
+            kind: RepeatedFieldKind::BoxedSlice,
+            ..
+        }) = self.kind
+        {
+            w.write_line(&format!(
+                "{} = {}.into_boxed_slice();",
+                self.self_field(),
+                self.boxed_slice_vec_var()
+            ));
+        }
+    }
+
     // Write `merge_from` part for this repeated field
     fn write_merge_from_repeated(&self, wire_type_var: &str, w: &mut CodeWriter) {
         let field = match self.kind {
@@ -1748,19 +1906,19 @@ impl<'a> FieldGen<'a> {
             }
             FieldElem::Enum(..) => {
                 w.write_line(&format!(
-                    "{}::rt::read_repeated_enum_or_unknown_into({}, is, &mut self.{})?",
+                    "{}::rt::read_repeated_enum_or_unknown_into({}, is, &mut {})?",
                     protobuf_crate_path(&self.customize),
                     wire_type_var,
-                    self.rust_name,
+                    self.merge_from_repeated_target(field),
                 ));
             }
             _ => {
                 w.write_line(&format!(
-                    "{}::rt::read_repeated_{}_into({}, is, &mut self.{})?;",
+                    "{}::rt::read_repeated_{}_into({}, is, &mut {})?;",
                     protobuf_crate_path(&self.customize),
                     protobuf_name(self.proto_type),
                     wire_type_var,
-                    self.rust_name
+                    self.merge_from_repeated_target(field),
                 ));
             }
         }
@@ -2092,7 +2250,178 @@ impl<'a> FieldGen<'a> {
         });
     }
 
+    /// `get_xxx` returns a reference to a copy scalar field,
+    /// see [`ScalarGetterStyle`].
+    pub(crate) fn get_xxx_returns_ref_to_copy(&self) -> bool {
+        if self.customize.scalar_getter_style != Some(ScalarGetterStyle::ByRef) {
+            return false;
+        }
+        match self.kind {
+            FieldKind::Singular(SingularField { ref elem, .. })
+            | FieldKind::Oneof(OneofField { ref elem, .. }) => match elem {
+                FieldElem::Primitive(..) => elem.is_copy(),
+                _ => false,
+            },
+            FieldKind::Repeated(..) | FieldKind::Map(..) => false,
+        }
+    }
+
+    pub(crate) fn write_message_field_get_ref_to_copy(&self, w: &mut CodeWriter) {
+        let default_value = format!("&{}", self.get_xxx_default_value_rust());
+        match self.kind {
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithFlag { .. },
+                ..
+            }) => {
+                w.match_expr(&self.self_field(), |w| {
+                    w.case_expr("::std::option::Option::Some(ref v)", "v");
+                    w.case_expr("::std::option::Option::None", &default_value);
+                });
+            }
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithoutFlag,
+                ..
+            }) => {
+                w.write_line(&format!("&{}", self.self_field()));
+            }
+            FieldKind::Oneof(ref o) => {
+                w.match_expr(&format!("self.{}", o.oneof_field_name), |w| {
+                    w.case_expr(
+                        format!(
+                            "::std::option::Option::Some({}(ref v))",
+                            o.variant_path(
+                                &self
+                                    .proto_field
+                                    .message
+                                    .scope
+                                    .rust_path_to_file()
+                                    .clone()
+                                    .into_path()
+                            ),
+                        ),
+                        "v",
+                    );
+                    w.case_expr("_", &default_value);
+                });
+            }
+            FieldKind::Repeated(..) | FieldKind::Map(..) => unreachable!(),
+        }
+    }
+
+    /// Type of `Entity::PrimaryKey` when this field is a primary key.
+    pub(crate) fn primary_key_type(&self) -> anyhow::Result<RustType> {
+        let elem = match self.kind {
+            FieldKind::Singular(SingularField { ref elem, .. })
+            | FieldKind::Oneof(OneofField { ref elem, .. }) => Some(elem),
+            FieldKind::Repeated(..) | FieldKind::Map(..) => None,
+        };
+        match elem {
+            Some(FieldElem::Primitive(field_descriptor_proto::Type::TYPE_STRING, ..)) => {
+                Ok(RustType::Str)
+            }
+            Some(FieldElem::Primitive(t, ..))
+                if *t != field_descriptor_proto::Type::TYPE_FLOAT
+                    && *t != field_descriptor_proto::Type::TYPE_DOUBLE
+                    && *t != field_descriptor_proto::Type::TYPE_BYTES =>
+            {
+                Ok(rust_name(*t))
+            }
+            _ => Err(anyhow::anyhow!(
+                "primary key field `{}` must be singular string, integer or bool field",
+                self.proto_field.name()
+            )),
+        }
+    }
+
+    /// Body of `Entity::primary_key` function when this field is a primary key
+    /// of type `key_type` returned by [`primary_key_type`](Self::primary_key_type).
+    pub(crate) fn write_primary_key_body(&self, key_type: &RustType, w: &mut CodeWriter) {
+        match key_type {
+            RustType::Str => self.write_message_field_get_body(w),
+            _ => self.write_message_field_get_ref_to_copy(w),
+        }
+    }
+
+    /// Expression comparing this field of `self` and `other`
+    /// with `float` and `double` values compared by bit pattern.
+    ///
+    /// Must not be called for oneof fields, oneof enum is compared as a whole.
+    pub(crate) fn nan_eq_expr(&self) -> String {
+        let a = format!("self.{}", self.rust_name);
+        let b = format!("other.{}", self.rust_name);
+        match self.kind {
+            FieldKind::Singular(SingularField { ref elem, ref flag }) if elem.is_float() => {
+                match flag {
+                    SingularFieldFlag::WithFlag { .. } => format!(
+                        "{}.map(|v| v.to_bits()) == {}.map(|v| v.to_bits())",
+                        a, b
+                    ),
+                    SingularFieldFlag::WithoutFlag => {
+                        format!("{}.to_bits() == {}.to_bits()", a, b)
+                    }
+                }
+            }
+            FieldKind::Repeated(RepeatedField { ref elem, .. }) if elem.is_float() => format!(
+                "{a}.len() == {b}.len() && {a}.iter().zip({b}.iter()).all(|(a, b)| a.to_bits() == b.to_bits())",
+                a = a,
+                b = b,
+            ),
+            FieldKind::Map(MapField { ref value, .. }) if value.is_float() => format!(
+                "{a}.len() == {b}.len() && {a}.iter().all(|(k, v)| {b}.get(k).map_or(false, |o| v.to_bits() == o.to_bits()))",
+                a = a,
+                b = b,
+            ),
+            FieldKind::Oneof(..) => unreachable!(),
+            _ => format!("{} == {}", a, b),
+        }
+    }
+
+    /// Field contains `float` or `double` values.
+    pub(crate) fn has_float_values(&self) -> bool {
+        match self.kind {
+            FieldKind::Singular(SingularField { ref elem, .. })
+            | FieldKind::Repeated(RepeatedField { ref elem, .. })
+            | FieldKind::Oneof(OneofField { ref elem, .. }) => elem.is_float(),
+            FieldKind::Map(MapField { ref value, .. }) => value.is_float(),
+        }
+    }
+
+    /// Message type of field values, if values are messages.
+    pub(crate) fn value_message(&self) -> Option<&MessageWithScope<'a>> {
+        let elem = match self.kind {
+            FieldKind::Singular(SingularField { ref elem, .. })
+            | FieldKind::Repeated(RepeatedField { ref elem, .. })
+            | FieldKind::Oneof(OneofField { ref elem, .. }) => elem,
+            FieldKind::Map(MapField { ref value, .. }) => value,
+        };
+        match elem {
+            FieldElem::Message(m) => Some(&m.message),
+            _ => None,
+        }
+    }
+
+    /// Reason why derived `Hash` cannot be implemented for this field, if so.
+    pub(crate) fn hash_unsupported_reason(&self) -> Option<&'static str> {
+        match self.kind {
+            FieldKind::Map(..) => {
+                Some("map fields are stored in `HashMap` which does not implement `Hash`")
+            }
+            _ if self.has_float_values() => Some("`f32` and `f64` do not implement `Hash`"),
+            _ => None,
+        }
+    }
+
     fn write_message_field_get(&self, w: &mut CodeWriter) {
+        if self.get_xxx_returns_ref_to_copy() {
+            let fn_def = format!(
+                "get_{}(&self) -> {}",
+                self.rust_name,
+                RustType::Ref(Box::new(self.get_xxx_return_type())).to_code(&self.customize)
+            );
+            w.pub_fn(&fn_def, |w| self.write_message_field_get_ref_to_copy(w));
+            return;
+        }
+
         let get_xxx_return_type = self.get_xxx_return_type();
         let fn_def = format!(
             "get_{}(&self) -> {}",
@@ -2100,7 +2429,12 @@ impl<'a> FieldGen<'a> {
             get_xxx_return_type.to_code(&self.customize)
         );
 
-        w.pub_fn(&fn_def, |w| match self.kind {
+        w.pub_fn(&fn_def, |w| self.write_message_field_get_body(w));
+    }
+
+    /// Body of `get_xxx` function, can be used in other functions with the same return type.
+    pub fn write_message_field_get_body(&self, w: &mut CodeWriter) {
+        match self.kind {
             FieldKind::Oneof(ref o) => {
                 self.write_message_field_get_oneof(o, w);
             }
@@ -2111,7 +2445,7 @@ impl<'a> FieldGen<'a> {
                 let self_field = self.self_field();
                 w.write_line(&format!("&{}", self_field));
             }
-        });
+        }
     }
 
     fn has_has(&self) -> bool {
@@ -2129,15 +2463,52 @@ impl<'a> FieldGen<'a> {
         }
     }
 
-    fn has_mut(&self) -> bool {
-        match self.kind {
-            FieldKind::Repeated(..) | FieldKind::Map(..) => true,
-            // TODO: string should be public, and mut is not needed
-            FieldKind::Singular(..) | FieldKind::Oneof(..) => !self.elem_type_is_copy(),
-        }
+    fn write_message_field_get_opt(&self, w: &mut CodeWriter) {
+        let get_xxx_return_type = if self.get_xxx_returns_ref_to_copy() {
+            RustType::Ref(Box::new(self.get_xxx_return_type()))
+        } else {
+            self.get_xxx_return_type()
+        };
+        let fn_def = format!(
+            "get_{}_opt(&self) -> {}",
+            self.rust_name,
+            RustType::Option(Box::new(get_xxx_return_type)).to_code(&self.customize)
+        );
+        let cond = match self.kind {
+            FieldKind::Oneof(ref oneof) => format!(
+                "let ::std::option::Option::Some({}(..)) = self.{}",
+                oneof.variant_path(
+                    &self
+                        .proto_field
+                        .message
+                        .scope
+                        .rust_path_to_file()
+                        .clone()
+                        .into_path()
+                ),
+                oneof.oneof_field_name
+            ),
+            _ => self.self_field_is_some(),
+        };
+        w.comment("Field value if the field is set, `None` otherwise");
+        w.pub_fn(&fn_def, |w| {
+            w.write_line(&format!("if {} {{", cond));
+            w.indented(|w| {
+                w.block("::std::option::Option::Some({", "})", |w| {
+                    if self.get_xxx_returns_ref_to_copy() {
+                        self.write_message_field_get_ref_to_copy(w);
+                    } else {
+                        self.write_message_field_get_body(w);
+                    }
+                });
+            });
+            w.write_line("} else {");
+            w.indented(|w| w.write_line(EXPR_NONE));
+            w.write_line("}");
+        });
     }
 
-    fn has_take(&self) -> bool {
+    fn has_mut(&self) -> bool {
         match self.kind {
             FieldKind::Repeated(..) | FieldKind::Map(..) => true,
             // TODO: string should be public, and mut is not needed
@@ -2238,6 +2609,56 @@ impl<'a> FieldGen<'a> {
         );
     }
 
+    /// Setter of required field in typestate builder, calls `set_xxx` of the message.
+    pub(crate) fn write_typestate_builder_set(
+        &self,
+        builder_name: &str,
+        return_type: &str,
+        w: &mut CodeWriter,
+    ) {
+        let set_xxx_param_type = self.set_xxx_param_type(&self.get_file_and_mod());
+        w.pub_fn(
+            &format!(
+                "{}(mut self, v: {}) -> {}",
+                self.rust_name,
+                set_xxx_param_type.to_code(&self.customize),
+                return_type
+            ),
+            |w| {
+                w.write_line(&format!("self.message.set_{}(v);", self.rust_name));
+                w.expr_block(builder_name, |w| {
+                    w.field_entry("message", "self.message");
+                    w.field_entry("_state", "::std::marker::PhantomData");
+                });
+            },
+        );
+    }
+
+    /// `Index<usize>` implementation for the message which indexes this repeated field.
+    pub(crate) fn write_repeated_index_impl(&self, message_type: &str, w: &mut CodeWriter) {
+        let elem_type = self
+            .elem()
+            .rust_storage_elem_type(&self.get_file_and_mod())
+            .to_code(&self.customize);
+        w.impl_for_block("::std::ops::Index<usize>", message_type, |w| {
+            w.write_line(&format!("type Output = {};", elem_type));
+            w.write_line("");
+            w.def_fn(
+                &format!("index(&self, index: usize) -> &{}", elem_type),
+                |w| {
+                    w.write_line(&format!("&{}[index]", self.self_field()));
+                },
+            );
+        });
+    }
+
+    /// `#[track_caller]` for accessors which contain `unwrap` or `panic!`.
+    fn write_track_caller_attr(&self, w: &mut CodeWriter) {
+        if self.customize.track_caller_accessors.unwrap_or(false) {
+            w.write_line("#[track_caller]");
+        }
+    }
+
     fn write_message_field_mut_singular(&self, s: &SingularField, w: &mut CodeWriter) {
         match s {
             SingularField {
@@ -2283,6 +2704,14 @@ impl<'a> FieldGen<'a> {
                 mut_xxx_return_type.to_code(&self.customize)
             ),
         };
+        match self.kind {
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithFlag { .. },
+                ..
+            })
+            | FieldKind::Oneof(..) => self.write_track_caller_attr(w),
+            _ => {}
+        }
         w.pub_fn(&fn_def, |w| {
             match self.kind {
                 FieldKind::Repeated(..) | FieldKind::Map(..) => {
@@ -2481,7 +2910,33 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    // `take_xxx` for field of copy type returns the same value as `get_xxx`
+    fn write_message_field_take_copy(&self, w: &mut CodeWriter) {
+        w.comment("Take field");
+        w.pub_fn(
+            &format!(
+                "take_{}(&mut self) -> {}",
+                self.rust_name,
+                self.get_xxx_return_type().to_code(&self.customize)
+            ),
+            |w| {
+                w.stmt_block("let v =", |w| self.write_message_field_get_body(w));
+                match self.kind {
+                    // Other variant of the oneof is left unchanged
+                    FieldKind::Oneof(..) => {
+                        self.write_if_self_field_is_present(w, |w| self.write_clear(w))
+                    }
+                    _ => self.write_clear(w),
+                }
+                w.write_line("v");
+            },
+        );
+    }
+
     fn write_message_field_take(&self, w: &mut CodeWriter) {
+        if !self.has_mut() {
+            return self.write_message_field_take_copy(w);
+        }
         let take_xxx_return_type = self.take_xxx_return_type(
             &self
                 .proto_field
@@ -2490,6 +2945,9 @@ impl<'a> FieldGen<'a> {
                 .get_file_and_mod(self.customize.clone()),
         );
         w.comment("Take field");
+        if let FieldKind::Oneof(..) = self.kind {
+            self.write_track_caller_attr(w);
+        }
         w.pub_fn(
             &format!(
                 "take_{}(&mut self) -> {}",
@@ -2510,6 +2968,34 @@ impl<'a> FieldGen<'a> {
         );
     }
 
+    fn write_message_field_swap(&self, w: &mut CodeWriter) {
+        let mut_xxx_return_type = self.mut_xxx_return_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        let fn_def = match mut_xxx_return_type {
+            RustType::Ref(ref param) => format!(
+                "swap_{}(&mut self, v: &mut {})",
+                self.rust_name,
+                param.to_code(&self.customize)
+            ),
+            _ => panic!(
+                "not a ref: {}",
+                mut_xxx_return_type.to_code(&self.customize)
+            ),
+        };
+        w.comment("Swap field with given value without cloning, field is set after swap");
+        w.pub_fn(&fn_def, |w| {
+            w.write_line(&format!(
+                "::std::mem::swap(self.mut_{}(), v)",
+                self.rust_name
+            ));
+        });
+    }
+
     pub fn write_message_single_field_accessors(&self, w: &mut CodeWriter) {
         if self.generate_accessors || self.generate_getter {
             w.write_line("");
@@ -2522,6 +3008,11 @@ impl<'a> FieldGen<'a> {
             self.write_message_field_get(w);
         }
 
+        if self.customize.generate_option_getters.unwrap_or(false) && self.has_has() {
+            w.write_line("");
+            self.write_message_field_get_opt(w);
+        }
+
         if !self.generate_accessors {
             return;
         }
@@ -2545,10 +3036,203 @@ impl<'a> FieldGen<'a> {
             self.write_message_field_mut(w);
         }
 
-        if self.has_take() {
+        w.write_line("");
+        self.write_message_field_take(w);
+
+        if self.has_mut() {
             w.write_line("");
-            self.write_message_field_take(w);
+            self.write_message_field_swap(w);
+        }
+    }
+}
+
+impl<'a> FieldGen<'a> {
+    fn write_map_conversions(&self, map: &MapField, w: &mut CodeWriter) {
+        let reference = self.get_file_and_mod();
+        let entry_type = format!(
+            "({}, {})",
+            map.key
+                .rust_storage_elem_type(&reference)
+                .to_code(&self.customize),
+            map.value
+                .rust_storage_elem_type(&reference)
+                .to_code(&self.customize),
+        );
+        let vec_type = format!("::std::vec::Vec<{}>", entry_type);
+
+        w.write_line("");
+        w.comment(&format!(
+            "Entries of map field `{}` in map iteration order",
+            self.rust_name
+        ));
+        w.pub_fn(
+            &format!("{}_to_vec(&self) -> {}", self.rust_name, vec_type),
+            |w| {
+                w.write_line(&format!(
+                    "{}.iter().map(|(k, v)| (k.clone(), v.clone())).collect()",
+                    self.self_field()
+                ));
+            },
+        );
+
+        w.write_line("");
+        w.comment(&format!(
+            "Entries of map field `{}` sorted by key",
+            self.rust_name
+        ));
+        w.pub_fn(
+            &format!("{}_to_sorted_vec(&self) -> {}", self.rust_name, vec_type),
+            |w| {
+                w.write_line(&format!("let mut r = self.{}_to_vec();", self.rust_name));
+                w.write_line("r.sort_by(|a, b| a.0.cmp(&b.0));");
+                w.write_line("r");
+            },
+        );
+
+        w.write_line("");
+        w.comment(&format!(
+            "Replace map field `{}` with given entries, later entries override earlier entries with the same key",
+            self.rust_name
+        ));
+        w.pub_fn(
+            &format!(
+                "set_{}_from_vec(&mut self, entries: {})",
+                self.rust_name, vec_type
+            ),
+            |w| {
+                w.write_line(&format!(
+                    "{} = entries.into_iter().collect();",
+                    self.self_field()
+                ));
+            },
+        );
+    }
+
+    /// Type and expression of owned value of this field of message `var`,
+    /// used as a key in `xxx_to_map` function.
+    fn map_key_type_and_expr(&self, var: &str) -> anyhow::Result<(RustType, String)> {
+        let not_key = || {
+            anyhow::anyhow!(
+                "map key field `{}` must be singular string, integer or bool field",
+                self.proto_field.name()
+            )
+        };
+        let (t, flag) = match self.kind {
+            FieldKind::Singular(SingularField {
+                elem: FieldElem::Primitive(t, ..),
+                flag,
+            }) => (t, flag),
+            _ => return Err(not_key()),
+        };
+        let field = format!("{}.{}", var, self.rust_name);
+        match t {
+            field_descriptor_proto::Type::TYPE_STRING => {
+                let expr = match flag {
+                    SingularFieldFlag::WithFlag { .. } => format!(
+                        "::std::borrow::ToOwned::to_owned({}.as_deref().unwrap_or({}))",
+                        field,
+                        self.get_xxx_default_value_rust()
+                    ),
+                    SingularFieldFlag::WithoutFlag => {
+                        format!("::std::borrow::ToOwned::to_owned(&*{})", field)
+                    }
+                };
+                Ok((RustType::String, expr))
+            }
+            field_descriptor_proto::Type::TYPE_FLOAT
+            | field_descriptor_proto::Type::TYPE_DOUBLE
+            | field_descriptor_proto::Type::TYPE_BYTES => Err(not_key()),
+            t => {
+                let expr = match flag {
+                    SingularFieldFlag::WithFlag { .. } => {
+                        format!("{}.unwrap_or({})", field, self.get_xxx_default_value_rust())
+                    }
+                    SingularFieldFlag::WithoutFlag => field,
+                };
+                Ok((rust_name(t), expr))
+            }
+        }
+    }
+
+    fn write_repeated_to_map(
+        &self,
+        key_field_name: &str,
+        w: &mut CodeWriter,
+    ) -> anyhow::Result<()> {
+        let elem = match self.kind {
+            FieldKind::Repeated(RepeatedField {
+                elem: FieldElem::Message(ref m),
+                ..
+            }) => m,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "field `{}` has `map_key_field` option, but it is not a repeated message field",
+                    self.proto_field.name()
+                ))
+            }
+        };
+        let key_field = match elem
+            .message
+            .fields()
+            .into_iter()
+            .find(|f| f.name() == key_field_name)
+        {
+            Some(key_field) => key_field,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "map key field `{}` of field `{}` is not found in message `{}`",
+                    key_field_name,
+                    self.proto_field.name(),
+                    elem.message.message.get_name()
+                ))
+            }
+        };
+        let key_field = FieldGen::parse(
+            key_field,
+            self._root_scope,
+            &elem.message.customize(self._root_scope.customize)?,
+            Vec::new(),
+            None,
+        )?;
+        let (key_type, key_expr) = key_field.map_key_type_and_expr("e")?;
+        let elem_type = elem
+            .rust_type(&self.get_file_and_mod())
+            .to_code(&self.customize);
+
+        w.write_line("");
+        w.comment(&format!(
+            "Elements of field `{}` keyed by `{}` field, later elements override earlier elements with the same key",
+            self.rust_name, key_field_name
+        ));
+        w.pub_fn(
+            &format!(
+                "{}_to_map(&self) -> ::std::collections::HashMap<{}, {}>",
+                self.rust_name,
+                key_type.to_code(&self.customize),
+                elem_type
+            ),
+            |w| {
+                w.write_line(&format!(
+                    "{}.iter().map(|e| ({}, e.clone())).collect()",
+                    self.self_field(),
+                    key_expr
+                ));
+            },
+        );
+        Ok(())
+    }
+
+    /// Write `generate_map_conversions` and `map_key_field` functions if enabled.
+    pub fn write_conversions(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
+        if let FieldKind::Map(ref map) = self.kind {
+            if self.customize.generate_map_conversions.unwrap_or(false) {
+                self.write_map_conversions(map, w);
+            }
+        }
+        if let Some(ref key_field_name) = self.customize.map_key_field {
+            self.write_repeated_to_map(key_field_name, w)?;
         }
+        Ok(())
     }
 }
 
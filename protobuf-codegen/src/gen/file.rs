@@ -17,11 +17,14 @@ use crate::gen::message::MessageGen;
 use crate::gen::paths::proto_path_to_rust_mod;
 use crate::gen::scope::FileScope;
 use crate::gen::scope::RootScope;
+use crate::gen::split_impls;
 use crate::proto_name_to_rs;
 use crate::Customize;
 
 pub(crate) struct GenFileResult {
     pub(crate) compiler_plugin_result: compiler_plugin::GenResult,
+    /// File with implementations when `split_impls` option is enabled.
+    pub(crate) impls_result: Option<compiler_plugin::GenResult>,
     pub(crate) mod_name: String,
 }
 
@@ -31,15 +34,15 @@ pub(crate) fn gen_file(
     root_scope: &RootScope,
     customize: &Customize,
     parser: &str,
-) -> GenFileResult {
+) -> anyhow::Result<GenFileResult> {
     // TODO: use it
     let mut customize = customize.clone();
     // options specified in invocation have precedence over options specified in file
     customize.update_with(&customize_from_rustproto_for_file(
         file_descriptor.proto().options.get_or_default(),
-    ));
+    )?);
 
-    let file_scope = FileScope { file_descriptor };
+    let file_scope = FileScope::new(file_descriptor, &customize)?;
     let scope = file_scope.to_scope();
     let lite_runtime = customize.lite_runtime.unwrap_or_else(|| {
         file_descriptor
@@ -52,10 +55,21 @@ pub(crate) fn gen_file(
 
     let file_index = FileIndex::index(&file_scope);
 
+    let mod_name = proto_path_to_rust_mod(file_descriptor.proto().get_name());
+    let split_impls = customize.split_impls.unwrap_or(false);
+
     let mut v = Vec::new();
+    let mut impls_v = Vec::new();
 
     {
         let mut w = CodeWriter::new(&mut v);
+        let mut impls = match split_impls {
+            true => Some(CodeWriter::new(&mut impls_v)),
+            false => None,
+        };
+        if let Some(impls) = &mut impls {
+            split_impls::write_impls_file_header(impls, mod_name.get(), parser);
+        }
 
         w.write_generated_by("rust-protobuf", env!("CARGO_PKG_VERSION"), parser);
 
@@ -99,8 +113,8 @@ pub(crate) fn gen_file(
                     &customize,
                     &path,
                     file_descriptor.proto().source_code_info.as_ref(),
-                )
-                .write(&mut w);
+                )?
+                .write(&mut w, impls.as_mut())?;
             }
         }
 
@@ -125,23 +139,30 @@ pub(crate) fn gen_file(
                 root_scope,
                 &path,
                 file_descriptor.proto().source_code_info.as_ref(),
-            )
-            .write(&mut w);
+            )?
+            .write(&mut w, impls.as_mut());
         }
 
         write_extensions(file_descriptor, &root_scope, &mut w, &customize);
 
         if !lite_runtime {
             w.write_line("");
-            write_file_descriptor_data(file_descriptor, &customize, &mut w);
+            write_file_descriptor_data(&file_scope, &customize, &mut w);
         }
     }
 
-    GenFileResult {
+    Ok(GenFileResult {
         compiler_plugin_result: compiler_plugin::GenResult {
             name: proto_name_to_rs(file_descriptor.proto().get_name()),
             content: v,
         },
-        mod_name: proto_path_to_rust_mod(file_descriptor.proto().get_name()).into_string(),
-    }
+        impls_result: match split_impls {
+            true => Some(compiler_plugin::GenResult {
+                name: format!("{}.rs", split_impls::impls_mod_name(mod_name.get())),
+                content: impls_v,
+            }),
+            false => None,
+        },
+        mod_name: mod_name.into_string(),
+    })
 }
@@ -1,6 +1,5 @@
 use std::fmt::Write as _;
 
-use protobuf::reflect::FileDescriptor;
 use protobuf::Message;
 
 use crate::gen::code_writer::CodeWriter;
@@ -30,11 +29,7 @@ fn escape_byte(s: &mut String, b: u8) {
     }
 }
 
-fn write_file_descriptor(
-    file_descriptor: &FileDescriptor,
-    customize: &Customize,
-    w: &mut CodeWriter,
-) {
+fn write_file_descriptor(scope: &FileScope, customize: &Customize, w: &mut CodeWriter) {
     w.write_line("/// `FileDescriptor` object which allows dynamic access to files");
     w.pub_fn(
         &format!(
@@ -55,15 +50,13 @@ fn write_file_descriptor(
                 "});",
                 |w| {
                     w.write_line(&format!("let mut deps = {};", EXPR_VEC_NEW));
-                    for f in &file_descriptor.proto().dependency {
+                    for f in &scope.file_descriptor.proto().dependency {
                         w.write_line(&format!(
                             "deps.push({}());",
                             proto_path_to_fn_file_descriptor(f, customize)
                         ));
                     }
 
-                    let scope = FileScope { file_descriptor };
-
                     w.write_line(&format!("let mut messages = {};", EXPR_VEC_NEW));
                     for m in scope.find_messages_except_map() {
                         if m.is_map() {
@@ -105,11 +98,11 @@ fn write_file_descriptor(
 }
 
 pub(crate) fn write_file_descriptor_data(
-    file: &FileDescriptor,
+    scope: &FileScope,
     customize: &Customize,
     w: &mut CodeWriter,
 ) {
-    let fdp_bytes = file.proto().write_to_bytes().unwrap();
+    let fdp_bytes = scope.file_descriptor.proto().write_to_bytes().unwrap();
     w.write_line("static file_descriptor_proto_data: &'static [u8] = b\"\\");
     w.indented(|w| {
         const MAX_LINE_LEN: usize = 72;
@@ -163,5 +156,5 @@ pub(crate) fn write_file_descriptor_data(
         },
     );
     w.write_line("");
-    write_file_descriptor(file, &customize, w);
+    write_file_descriptor(scope, &customize, w);
 }
@@ -3,18 +3,25 @@ use std::fmt;
 use protobuf::descriptor::*;
 use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::MessageDescriptor;
+use protobuf_parse::camel_case;
 use protobuf_parse::snake_case;
 
 use crate::customize::customize_from_rustproto_for_message;
+use crate::customize::ImplementTrait;
+use crate::customize::NestedTypeStrategy;
 use crate::gen::code_writer::*;
+use crate::gen::display_format::parse_display_format;
 use crate::gen::enums::*;
 use crate::gen::field::FieldGen;
 use crate::gen::field::FieldKind;
+use crate::gen::field::RepeatedField;
+use crate::gen::field::RepeatedFieldKind;
 use crate::gen::file_and_mod::FileAndMod;
 use crate::gen::file_index::FileIndex;
 use crate::gen::inside::protobuf_crate_path;
 use crate::gen::oneof::OneofGen;
 use crate::gen::oneof::OneofVariantGen;
+use crate::gen::paths::proto_path_to_rust_mod;
 use crate::gen::rust::is_rust_keyword;
 use crate::gen::rust::EXPR_NONE;
 use crate::gen::rust::EXPR_VEC_NEW;
@@ -25,6 +32,7 @@ use crate::gen::scope::MessageWithScope;
 use crate::gen::scope::RootScope;
 use crate::gen::scope::WithScope;
 use crate::gen::serde;
+use crate::gen::split_impls;
 use crate::Customize;
 
 /// Protobuf message Rust type name
@@ -78,7 +86,7 @@ impl<'a> MessageGen<'a> {
         customize: &Customize,
         path: &'a [i32],
         info: Option<&'a SourceCodeInfo>,
-    ) -> MessageGen<'a> {
+    ) -> anyhow::Result<MessageGen<'a>> {
         let message_descriptor = file_descriptor
             .message_by_package_relative_name(&format!("{}", message.protobuf_name_to_package()))
             .unwrap();
@@ -86,7 +94,7 @@ impl<'a> MessageGen<'a> {
         let mut customize = customize.clone();
         customize.update_with(&customize_from_rustproto_for_message(
             message.message.get_proto().options.get_or_default(),
-        ));
+        )?);
 
         static FIELD_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
         let field_number = *FIELD_NUMBER.get(|| {
@@ -106,7 +114,7 @@ impl<'a> MessageGen<'a> {
                 path.extend_from_slice(&[field_number, id as i32]);
                 FieldGen::parse(field, root_scope, &customize, path, info)
             })
-            .collect();
+            .collect::<anyhow::Result<_>>()?;
         let lite_runtime = customize.lite_runtime.unwrap_or_else(|| {
             message
                 .get_file_descriptor()
@@ -115,7 +123,7 @@ impl<'a> MessageGen<'a> {
                 .get_optimize_for()
                 == file_options::OptimizeMode::LITE_RUNTIME
         });
-        MessageGen {
+        Ok(MessageGen {
             message_descriptor,
             file_descriptor,
             message,
@@ -127,7 +135,7 @@ impl<'a> MessageGen<'a> {
             customize,
             path,
             info,
-        }
+        })
     }
 
     fn index_in_file(&self) -> u32 {
@@ -309,11 +317,16 @@ impl<'a> MessageGen<'a> {
         w.def_fn(
             &format!("default_instance() -> &'static {}", self.type_name),
             |w| {
-                let has_map_field = self.fields.iter().any(|f| match f.kind {
+                // `HashMap::new`, `SmallVec::new` and `Box::default` are not `const fn`
+                let has_non_const_field = self.fields.iter().any(|f| match f.kind {
                     FieldKind::Map(..) => true,
+                    FieldKind::Repeated(RepeatedField {
+                        kind: RepeatedFieldKind::SmallVec(..) | RepeatedFieldKind::BoxedSlice,
+                        ..
+                    }) => true,
                     _ => false,
                 });
-                if has_map_field {
+                if has_non_const_field {
                     self.write_default_instance_lazy(w)
                 } else {
                     self.write_default_instance_static(w)
@@ -347,23 +360,291 @@ impl<'a> MessageGen<'a> {
         });
     }
 
-    fn write_field_accessors(&self, w: &mut CodeWriter) {
+    fn write_field_accessors(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
         for f in self.fields_except_group() {
             f.write_message_single_field_accessors(w);
+            f.write_conversions(w)?;
+        }
+        Ok(())
+    }
+
+    fn write_fields_present(&self, w: &mut CodeWriter) {
+        let presence_set = format!("{}::FieldPresenceSet", protobuf_crate_path(&self.customize));
+        w.comment("Field numbers of fields which are set in this message");
+        w.pub_fn(&format!("fields_present(&self) -> {}", presence_set), |w| {
+            w.write_line(&format!("let mut r = {}::new();", presence_set));
+            for f in self.fields_except_group() {
+                f.write_if_self_field_is_present(w, |w| {
+                    w.write_line(&format!("r.insert({});", f.proto_field.number()));
+                });
+            }
+            w.write_line("r");
+        });
+    }
+
+    fn write_message_descriptor_proto(&self, w: &mut CodeWriter) {
+        // Find indices by names, because indices in `self.path` skip map entries
+        let mut expr = format!(
+            "{}()",
+            self.message
+                .get_scope()
+                .rust_path_to_file()
+                .to_reverse()
+                .append_ident("file_descriptor_proto".into())
+        );
+        let mut messages = &self.file_descriptor.proto().message_type;
+        let mut field = "message_type";
+        for name in self.message.name_to_package().split('.') {
+            let index = messages
+                .iter()
+                .position(|m| m.get_name() == name)
+                .expect("message not found in file descriptor");
+            expr.push_str(&format!(".{}[{}]", field, index));
+            messages = &messages[index].nested_type;
+            field = "nested_type";
+        }
+
+        w.comment("`DescriptorProto` of this message, part of file `FileDescriptorProto`");
+        w.pub_fn(
+            &format!(
+                "message_descriptor_proto() -> &'static {}::descriptor::DescriptorProto",
+                protobuf_crate_path(&self.customize)
+            ),
+            |w| {
+                w.write_line(&format!("&{}", expr));
+            },
+        );
+    }
+
+    fn write_retain_fields(&self, w: &mut CodeWriter) {
+        w.comment("Clear all fields except fields with given numbers, and unknown fields");
+        w.pub_fn("retain_fields(&mut self, keep: &[u32])", |w| {
+            for f in self.fields_except_group() {
+                w.if_stmt(
+                    &format!("!keep.contains(&{})", f.proto_field.number()),
+                    |w| match f.kind {
+                        // Oneof is cleared only if this variant is set
+                        FieldKind::Oneof(..) => {
+                            f.write_if_self_field_is_present(w, |w| f.write_clear(w))
+                        }
+                        _ => f.write_clear(w),
+                    },
+                );
+            }
+            w.write_line(&format!(
+                "{}::Clear::clear(&mut self.unknown_fields);",
+                protobuf_crate_path(&self.customize)
+            ));
+        });
+    }
+
+    fn write_impl_entity(&self, primary_key_field: &str, w: &mut CodeWriter) -> anyhow::Result<()> {
+        let fields = self.fields_except_group();
+        let field = fields
+            .iter()
+            .find(|f| f.proto_field.name() == primary_key_field)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "primary key field `{}` is not found in message `{}`",
+                    primary_key_field,
+                    self.message.get_name()
+                )
+            })?;
+        let key_type = field.primary_key_type()?;
+        let key_type_code = key_type.to_code(&self.customize);
+        w.impl_for_block(
+            &format!("{}::Entity", protobuf_crate_path(&self.customize)),
+            &format!("{}", self.type_name),
+            |w| {
+                w.write_line(&format!("type PrimaryKey = {};", key_type_code));
+                w.write_line("");
+                w.def_fn(&format!("primary_key(&self) -> &{}", key_type_code), |w| {
+                    field.write_primary_key_body(&key_type, w)
+                });
+            },
+        );
+        Ok(())
+    }
+
+    fn write_impl_display(&self, template: &str, w: &mut CodeWriter) -> anyhow::Result<()> {
+        let display_format = parse_display_format(template).map_err(|e| {
+            anyhow::anyhow!(
+                "incorrect display format of message `{}`: {}",
+                self.message.get_name(),
+                e
+            )
+        })?;
+        let all_fields = self.fields_except_group();
+        let fields: Vec<&FieldGen> = display_format
+            .fields
+            .iter()
+            .map(|name| {
+                let field = all_fields
+                    .iter()
+                    .find(|f| f.proto_field.name() == name)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "display format of message `{}` references unknown field `{}`",
+                            self.message.get_name(),
+                            name
+                        )
+                    })?;
+                match field.kind {
+                    FieldKind::Singular(..) | FieldKind::Oneof(..) => Ok(*field),
+                    FieldKind::Repeated(..) | FieldKind::Map(..) => Err(anyhow::anyhow!(
+                        "display format of message `{}` references repeated field `{}`",
+                        self.message.get_name(),
+                        name
+                    )),
+                }
+            })
+            .collect::<anyhow::Result<_>>()?;
+        w.impl_for_block("::std::fmt::Display", &format!("{}", self.type_name), |w| {
+            w.def_fn(
+                "fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result",
+                |w| {
+                    let mut args = String::new();
+                    for (i, field) in fields.iter().enumerate() {
+                        w.stmt_block(&format!("let v{} =", i), |w| {
+                            field.write_message_field_get_body(w)
+                        });
+                        args.push_str(&format!(", v{}", i));
+                    }
+                    w.write_line(&format!("write!(f, {:?}{})", display_format.format, args));
+                },
+            );
+        });
+        Ok(())
+    }
+
+    fn write_implement_trait(&self, implement_trait: &ImplementTrait, w: &mut CodeWriter) {
+        let descriptor = format!(
+            "<{} as {}::Message>::descriptor_static()",
+            self.type_name,
+            protobuf_crate_path(&self.customize)
+        );
+        let body = implement_trait
+            .body_template
+            .replace("$full_name", self.message.message.full_name())
+            .replace("$name", self.message.message.get_name())
+            .replace("$rust_name", &format!("{}", self.type_name))
+            .replace("$descriptor", &descriptor);
+        w.impl_for_block(
+            &implement_trait.trait_path,
+            &format!("{}", self.type_name),
+            |w| {
+                for line in body.trim_matches('\n').lines() {
+                    w.write_line(line.trim_end());
+                }
+            },
+        );
+    }
+
+    /// The only field of message with `bytes_wrapper` option.
+    fn bytes_wrapper_field(&self) -> anyhow::Result<&FieldGen> {
+        match self.fields_except_group().as_slice() {
+            [field]
+                if field.proto_type == field_descriptor_proto::Type::TYPE_BYTES
+                    && field.is_singular() =>
+            {
+                Ok(field)
+            }
+            _ => Err(anyhow::anyhow!(
+                "message `{}` has `bytes_wrapper` option, \
+                but it does not have exactly one singular `bytes` field",
+                self.message.get_name()
+            )),
+        }
+    }
+
+    fn write_impl_bytes_wrapper(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
+        let field = self.bytes_wrapper_field()?;
+        w.impl_for_block(
+            "::std::convert::AsRef<[u8]>",
+            &format!("{}", self.type_name),
+            |w| {
+                w.def_fn("as_ref(&self) -> &[u8]", |w| {
+                    field.write_message_field_get_body(w);
+                });
+            },
+        );
+        w.write_line("");
+        w.impl_for_block("::std::ops::Deref", &format!("{}", self.type_name), |w| {
+            w.write_line("type Target = [u8];");
+            w.write_line("");
+            w.def_fn("deref(&self) -> &[u8]", |w| {
+                w.write_line("::std::convert::AsRef::as_ref(self)");
+            });
+        });
+        Ok(())
+    }
+
+    fn repeated_index_impl_field(&self) -> anyhow::Result<&FieldGen> {
+        let repeated: Vec<_> = self
+            .fields_except_group()
+            .into_iter()
+            .filter(|f| match f.kind {
+                FieldKind::Repeated(..) => true,
+                _ => false,
+            })
+            .collect();
+        match repeated.as_slice() {
+            [field] => Ok(field),
+            _ => Err(anyhow::anyhow!(
+                "message `{}` has `repeated_index_impl` option, \
+                but it has {} repeated fields instead of exactly one",
+                self.message.get_name(),
+                repeated.len()
+            )),
         }
     }
 
-    fn write_impl_self(&self, w: &mut CodeWriter) {
+    fn write_impl_repeated_index(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
+        let field = self.repeated_index_impl_field()?;
+        field.write_repeated_index_impl(&format!("{}", self.type_name), w);
+        Ok(())
+    }
+
+    fn write_impl_self(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
+        let mut r = Ok(());
         w.impl_self_block(&format!("{}", self.type_name), |w| {
             // TODO: new should probably be a part of Message trait
             w.pub_fn(&format!("new() -> {}", self.type_name), |w| {
                 w.write_line("::std::default::Default::default()");
             });
 
-            self.write_field_accessors(w);
+            r = self.write_field_accessors(w);
+            if !self.typestate_builder_fields().is_empty() {
+                w.write_line("");
+                self.write_typestate_builder_fn(w);
+            }
+            if self.generate_parts() {
+                w.write_line("");
+                self.write_into_parts(w);
+            }
+            if self.customize.generate_presence_set.unwrap_or(false) {
+                w.write_line("");
+                self.write_fields_present(w);
+            }
+            if self.customize.generate_retain_fields.unwrap_or(false) {
+                w.write_line("");
+                self.write_retain_fields(w);
+            }
+            // File descriptor is not generated for lite runtime
+            if self.customize.per_message_descriptor.unwrap_or(false) && !self.lite_runtime {
+                w.write_line("");
+                self.write_message_descriptor_proto(w);
+            }
+            if self.customize.generate_oneof_case_enum.unwrap_or(false) {
+                for oneof in self.oneofs() {
+                    w.write_line("");
+                    oneof.write_which_fn(w);
+                }
+            }
             w.write_line("");
             self.write_generated_message_descriptor_data(w);
         });
+        r
     }
 
     fn write_unknown_fields(&self, w: &mut CodeWriter) {
@@ -391,6 +672,9 @@ impl<'a> MessageGen<'a> {
             protobuf_crate_path(&self.customize),
         );
         w.def_fn(&sig, |w| {
+            for f in &self.fields_except_group() {
+                f.write_merge_from_boxed_slice_begin(w);
+            }
             w.while_block("!is.eof()?", |w| {
                 w.write_line(&format!("let (field_number, wire_type) = is.read_tag_unpack()?;"));
                 w.match_block("field_number", |w| {
@@ -405,6 +689,9 @@ impl<'a> MessageGen<'a> {
                     });
                 });
             });
+            for f in &self.fields_except_group() {
+                f.write_merge_from_boxed_slice_end(w);
+            }
             w.write_line("::std::result::Result::Ok(())");
         });
     }
@@ -434,7 +721,10 @@ impl<'a> MessageGen<'a> {
             protobuf_crate_path(&self.customize)
         );
         w.fn_block(
-            Visibility::Path(self.message.get_scope().rust_path_to_file().to_reverse()),
+            split_impls::file_visibility(
+                &self.customize,
+                &self.message.get_scope().rust_path_to_file(),
+            ),
             &sig,
             |w| {
                 let fields = self.fields_except_group();
@@ -567,12 +857,285 @@ impl<'a> MessageGen<'a> {
         self.fields.len() <= 500
     }
 
-    fn write_struct(&self, w: &mut CodeWriter) {
+    fn nan_eq(&self) -> bool {
+        self.customize.nan_eq.unwrap_or(false)
+    }
+
+    /// Field of message type which is generated without trait implemented by given option.
+    fn field_of_message_without(
+        &self,
+        option: fn(&Customize) -> Option<bool>,
+    ) -> anyhow::Result<Option<(&FieldGen, &MessageWithScope)>> {
+        for f in &self.fields {
+            if let Some(m) = f.value_message() {
+                if option(&m.customize(self.root_scope.customize)?) != Some(true) {
+                    return Ok(Some((f, m)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn impl_eq(&self) -> anyhow::Result<bool> {
+        let impl_eq = self.customize.impl_eq.unwrap_or(false);
+        if !impl_eq {
+            return Ok(false);
+        }
+        if !self.nan_eq() {
+            if let Some(f) = self.fields.iter().find(|f| f.has_float_values()) {
+                return Err(anyhow::anyhow!(
+                    "`impl_eq` requires `nan_eq` for message `{}` with floating point field `{}`",
+                    self.message.get_name(),
+                    f.proto_field.name()
+                ));
+            }
+        }
+        if let Some((f, m)) = self.field_of_message_without(|c| c.impl_eq)? {
+            return Err(anyhow::anyhow!(
+                "`impl_eq` cannot be used for message `{}`: field `{}`: message `{}` does not implement `Eq`",
+                self.message.get_name(),
+                f.proto_field.name(),
+                m.message.full_name()
+            ));
+        }
+        Ok(true)
+    }
+
+    fn impl_hash(&self) -> anyhow::Result<bool> {
+        let impl_hash = self.customize.impl_hash.unwrap_or(false);
+        if !impl_hash {
+            return Ok(false);
+        }
+        if let Some((f, reason)) = self
+            .fields
+            .iter()
+            .find_map(|f| Some((f, f.hash_unsupported_reason()?)))
+        {
+            return Err(anyhow::anyhow!(
+                "`impl_hash` cannot be used for message `{}`: field `{}`: {}",
+                self.message.get_name(),
+                f.proto_field.name(),
+                reason
+            ));
+        }
+        if let Some((f, m)) = self.field_of_message_without(|c| c.impl_hash)? {
+            return Err(anyhow::anyhow!(
+                "`impl_hash` cannot be used for message `{}`: field `{}`: message `{}` does not implement `Hash`",
+                self.message.get_name(),
+                f.proto_field.name(),
+                m.message.full_name()
+            ));
+        }
+        Ok(true)
+    }
+
+    /// Required fields tracked by typestate builder, empty if builder is not generated.
+    fn typestate_builder_fields(&self) -> Vec<&FieldGen> {
+        if !self.customize.typestate_builder.unwrap_or(false) {
+            return Vec::new();
+        }
+        self.fields
+            .iter()
+            .filter(|f| {
+                f.proto_field.field.get_proto().get_label()
+                    == field_descriptor_proto::Label::LABEL_REQUIRED
+                    && f.proto_type != field_descriptor_proto::Type::TYPE_GROUP
+            })
+            .collect()
+    }
+
+    fn typestate_builder_name(&self) -> String {
+        format!("{}Builder", self.type_name)
+    }
+
+    /// Builder type with given states of required fields.
+    fn typestate_builder_type(&self, states: &[String]) -> String {
+        format!("{}<{}>", self.typestate_builder_name(), states.join(", "))
+    }
+
+    fn typestate_builder_state_params(&self) -> Vec<String> {
+        self.typestate_builder_fields()
+            .iter()
+            .map(|f| format!("{}State", camel_case(f.proto_field.name())))
+            .collect()
+    }
+
+    fn write_typestate_builder_struct(&self, w: &mut CodeWriter) {
+        let params = self.typestate_builder_state_params();
+        w.write_line("");
+        w.write_line(&format!(
+            "/// Builder of [`{}`] which checks at compile time that all required fields are set.",
+            self.type_name,
+        ));
+        w.write_line("///");
+        w.write_line("/// Type parameters are states of required fields,");
+        w.write_line("/// `build` function is available when all required fields are set.");
+        w.pub_struct(&self.typestate_builder_type(&params), |w| {
+            w.field_decl("message", &format!("{}", self.type_name));
+            // trailing comma makes one-element tuple
+            let trailing_comma = if params.len() == 1 { "," } else { "" };
+            w.field_decl(
+                "_state",
+                &format!(
+                    "::std::marker::PhantomData<({}{})>",
+                    params.join(", "),
+                    trailing_comma
+                ),
+            );
+        });
+    }
+
+    fn write_typestate_builder_fn(&self, w: &mut CodeWriter) {
+        let unset = format!(
+            "{}::rt::RequiredFieldUnset",
+            protobuf_crate_path(&self.customize)
+        );
+        let states = vec![unset; self.typestate_builder_fields().len()];
+        w.comment("Builder which checks at compile time that all required fields are set");
+        w.pub_fn(
+            &format!("builder() -> {}", self.typestate_builder_type(&states)),
+            |w| {
+                w.expr_block(&self.typestate_builder_name(), |w| {
+                    w.field_entry("message", &format!("{}::new()", self.type_name));
+                    w.field_entry("_state", "::std::marker::PhantomData");
+                });
+            },
+        );
+    }
+
+    fn write_typestate_builder_impls(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        let params = self.typestate_builder_state_params();
+        for (i, field) in self.typestate_builder_fields().into_iter().enumerate() {
+            let mut other_params = params.clone();
+            other_params.remove(i);
+            let mut before = params.clone();
+            before[i] = format!("{}::rt::RequiredFieldUnset", protobuf_crate);
+            let mut after = params.clone();
+            after[i] = format!("{}::rt::RequiredFieldSet", protobuf_crate);
+
+            w.write_line("");
+            let impl_params = if other_params.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", other_params.join(", "))
+            };
+            w.expr_block(
+                &format!(
+                    "impl{} {}",
+                    impl_params,
+                    self.typestate_builder_type(&before)
+                ),
+                |w| {
+                    field.write_typestate_builder_set(
+                        &self.typestate_builder_name(),
+                        &self.typestate_builder_type(&after),
+                        w,
+                    );
+                },
+            );
+        }
+
+        let set = vec![format!("{}::rt::RequiredFieldSet", protobuf_crate); params.len()];
+        w.write_line("");
+        w.impl_self_block(&self.typestate_builder_type(&set), |w| {
+            w.comment("Message with all required fields set");
+            w.pub_fn(&format!("build(self) -> {}", self.type_name), |w| {
+                w.write_line("self.message");
+            });
+        });
+    }
+
+    fn generate_parts(&self) -> bool {
+        self.customize.generate_parts.unwrap_or(false)
+    }
+
+    fn parts_name(&self) -> String {
+        format!("{}Parts", self.type_name)
+    }
+
+    /// Names of fields moved between message and parts struct.
+    fn parts_field_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .fields_except_oneof_and_group()
+            .iter()
+            .map(|f| f.rust_name.get().to_owned())
+            .collect();
+        for oneof in self.oneofs() {
+            names.push(oneof.oneof.field_name().to_string());
+        }
+        names.push("unknown_fields".to_owned());
+        names
+    }
+
+    fn write_parts_struct(&self, w: &mut CodeWriter) {
+        w.write_line("");
+        w.write_line(&format!(
+            "/// Fields of [`{}`] as owned values, returned by [`{}::into_parts`].",
+            self.type_name, self.type_name,
+        ));
+        w.write_line("///");
+        w.write_line("/// Oneofs are stored as `Option` of oneof enum.");
+        let mut derive = Vec::new();
+        if self.supports_derive_partial_eq() && !self.nan_eq() {
+            derive.push("PartialEq");
+        }
+        derive.extend(&["Clone", "Default", "Debug"]);
+        w.derive(&derive);
+        w.pub_struct(&self.parts_name(), |w| {
+            for field in self.fields_except_oneof_and_group() {
+                field.write_parts_field(w);
+            }
+            for oneof in self.oneofs() {
+                w.pub_field_decl(
+                    &oneof.oneof.field_name().to_string(),
+                    &oneof.full_storage_type().to_code(&self.customize),
+                );
+            }
+            w.pub_field_decl(
+                "unknown_fields",
+                &format!("{}::UnknownFields", protobuf_crate_path(&self.customize)),
+            );
+        });
+    }
+
+    fn write_into_parts(&self, w: &mut CodeWriter) {
+        w.comment("Move fields into struct with public fields");
+        w.pub_fn(&format!("into_parts(self) -> {}", self.parts_name()), |w| {
+            w.expr_block(&self.parts_name(), |w| {
+                for name in self.parts_field_names() {
+                    w.field_entry(&name, &format!("self.{}", name));
+                }
+            });
+        });
+    }
+
+    fn write_parts_impl(&self, w: &mut CodeWriter) {
+        w.impl_self_block(&self.parts_name(), |w| {
+            w.comment("Assemble message from parts");
+            w.pub_fn(&format!("into_message(self) -> {}", self.type_name), |w| {
+                w.expr_block(&format!("{}", self.type_name), |w| {
+                    for name in self.parts_field_names() {
+                        w.field_entry(&name, &format!("self.{}", name));
+                    }
+                    w.field_entry("cached_size", "::std::default::Default::default()");
+                });
+            });
+        });
+    }
+
+    fn write_struct(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
         let mut derive = Vec::new();
-        if self.supports_derive_partial_eq() {
+        if self.supports_derive_partial_eq() && !self.nan_eq() {
             derive.push("PartialEq");
+            if self.impl_eq()? {
+                derive.push("Eq");
+            }
         }
         derive.extend(&["Clone", "Default"]);
+        if self.impl_hash()? {
+            derive.push("Hash");
+        }
         if self.lite_runtime {
             derive.push("Debug");
         }
@@ -582,6 +1145,9 @@ impl<'a> MessageGen<'a> {
             &self.customize,
             "derive(::serde::Serialize, ::serde::Deserialize)",
         );
+        if self.customize.serde_default.unwrap_or(false) {
+            serde::write_serde_attr(w, &self.customize, "serde(default)");
+        }
         w.pub_struct(&format!("{}", self.type_name), |w| {
             if !self.fields_except_oneof().is_empty() {
                 w.comment("message fields");
@@ -594,8 +1160,24 @@ impl<'a> MessageGen<'a> {
                 for oneof in self.oneofs() {
                     let vis = match self.expose_oneof() {
                         true => Visibility::Public,
-                        false => Visibility::Default,
+                        false => split_impls::private_visibility(
+                            &self.customize,
+                            &self.message.get_scope().rust_path_to_file(),
+                        ),
                     };
+                    if self.customize.serde_default.unwrap_or(false) {
+                        serde::write_serde_attr(
+                            w,
+                            &self.customize,
+                            &format!(
+                                r#"serde(skip_serializing_if = "{}")"#,
+                                serde::skip_serializing_if(
+                                    &oneof.full_storage_type(),
+                                    &self.customize
+                                )
+                            ),
+                        );
+                    }
                     w.field_decl_vis(
                         vis,
                         &oneof.oneof.field_name().to_string(),
@@ -616,6 +1198,7 @@ impl<'a> MessageGen<'a> {
                 &format!("{}::rt::CachedSize", protobuf_crate_path(&self.customize)),
             );
         });
+        Ok(())
     }
 
     fn write_impl_default_for_amp(&self, w: &mut CodeWriter) {
@@ -648,20 +1231,83 @@ impl<'a> MessageGen<'a> {
         );
     }
 
-    pub fn write(&self, w: &mut CodeWriter) {
+    fn write_nan_eq_impl_partial_eq(&self, w: &mut CodeWriter) {
+        w.impl_for_block(
+            "::std::cmp::PartialEq",
+            &format!("{}", self.type_name),
+            |w| {
+                w.def_fn("eq(&self, other: &Self) -> bool", |w| {
+                    let mut exprs: Vec<String> = self
+                        .fields_except_oneof()
+                        .into_iter()
+                        .map(|f| f.nan_eq_expr())
+                        .collect();
+                    for oneof in self.oneofs() {
+                        let name = oneof.oneof.field_name();
+                        exprs.push(format!("self.{} == other.{}", name, name));
+                    }
+                    exprs.push("self.unknown_fields == other.unknown_fields".to_owned());
+
+                    // `cached_size` is not compared, like in derived implementation
+                    w.write_line(&exprs[0]);
+                    w.indented(|w| {
+                        for expr in &exprs[1..] {
+                            w.write_line(&format!("&& {}", expr));
+                        }
+                    });
+                });
+            },
+        );
+    }
+
+    /// Write message, and its implementations with `impls` writer if specified.
+    pub fn write(
+        &self,
+        w: &mut CodeWriter,
+        mut impls: Option<&mut CodeWriter>,
+    ) -> anyhow::Result<()> {
         w.all_documentation(self.info, self.path);
-        self.write_struct(w);
+        self.write_struct(w)?;
+        if !self.typestate_builder_fields().is_empty() {
+            self.write_typestate_builder_struct(w);
+        }
+        if self.generate_parts() {
+            self.write_parts_struct(w);
+        }
 
+        match impls.as_deref_mut() {
+            Some(impls) => self.write_impls(impls)?,
+            None => self.write_impls(w)?,
+        }
+
+        self.write_nested(w, impls)
+    }
+
+    fn write_impls(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
         w.write_line("");
         self.write_impl_default_for_amp(w);
 
         if !self.supports_derive_partial_eq() {
             w.write_line("");
             self.write_dummy_impl_partial_eq(w);
+        } else if self.nan_eq() {
+            w.write_line("");
+            self.write_nan_eq_impl_partial_eq(w);
+            if self.impl_eq()? {
+                w.write_line("");
+                w.write_line(&format!("impl ::std::cmp::Eq for {} {{}}", self.type_name));
+            }
         }
 
         w.write_line("");
-        self.write_impl_self(w);
+        self.write_impl_self(w)?;
+        if !self.typestate_builder_fields().is_empty() {
+            self.write_typestate_builder_impls(w);
+        }
+        if self.generate_parts() {
+            w.write_line("");
+            self.write_parts_impl(w);
+        }
         w.write_line("");
         self.write_impl_message(w);
         w.write_line("");
@@ -672,7 +1318,34 @@ impl<'a> MessageGen<'a> {
         }
         w.write_line("");
         self.write_impl_value(w);
+        if self.customize.bytes_wrapper.unwrap_or(false) {
+            w.write_line("");
+            self.write_impl_bytes_wrapper(w)?;
+        }
+        if self.customize.repeated_index_impl.unwrap_or(false) {
+            w.write_line("");
+            self.write_impl_repeated_index(w)?;
+        }
+        if let Some(ref primary_key_field) = self.customize.primary_key_field {
+            w.write_line("");
+            self.write_impl_entity(primary_key_field, w)?;
+        }
+        if let Some(ref display_format) = self.customize.display_format {
+            w.write_line("");
+            self.write_impl_display(display_format, w)?;
+        }
+        for implement_trait in self.customize.implement_traits.iter().flatten() {
+            w.write_line("");
+            self.write_implement_trait(implement_trait, w);
+        }
+        Ok(())
+    }
 
+    fn write_nested(
+        &self,
+        w: &mut CodeWriter,
+        impls: Option<&mut CodeWriter>,
+    ) -> anyhow::Result<()> {
         let mod_name = message_name_to_nested_mod_name(&self.message.message.get_name());
 
         let oneofs = self.oneofs();
@@ -688,82 +1361,119 @@ impl<'a> MessageGen<'a> {
             .collect();
         let nested_enums = self.message.to_scope().get_enums();
 
-        if !oneofs.is_empty() || !nested_messages.is_empty() || !nested_enums.is_empty() {
-            w.write_line("");
-            w.write_line(&format!(
-                "/// Nested message and enums of message `{}`",
-                self.message.message.get_name()
-            ));
-            w.pub_mod(mod_name.get(), |w| {
-                let mut first = true;
+        if oneofs.is_empty() && nested_messages.is_empty() && nested_enums.is_empty() {
+            return Ok(());
+        }
 
-                for oneof in &oneofs {
-                    w.write_line("");
-                    oneof.write(w);
-                }
+        match self.message.scope.file_scope.nested_type_strategy {
+            NestedTypeStrategy::Modules => {}
+            NestedTypeStrategy::FlattenUnderscore | NestedTypeStrategy::FlattenCamel => {
+                // nested types are declared next to the message
+                w.write_line("");
+                return self.write_nested_items(&oneofs, &nested_messages, w, impls);
+            }
+        }
 
-                static NESTED_TYPE_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
-                let nested_type_number = *NESTED_TYPE_NUMBER.get(|| {
-                    protobuf::reflect::MessageDescriptor::for_type::<DescriptorProto>()
-                        .get_field_by_name("nested_type")
-                        .expect("`nested_type` must exist")
-                        .get_proto()
-                        .get_number()
+        w.write_line("");
+        w.write_line(&format!(
+            "/// Nested message and enums of message `{}`",
+            self.message.message.get_name()
+        ));
+        let mut r = Ok(());
+        w.pub_mod(mod_name.get(), |w| match impls {
+            Some(impls) => {
+                let file_mod = proto_path_to_rust_mod(self.file_descriptor.proto().get_name());
+                let mod_path = self.message.to_scope().rust_path_to_file();
+                split_impls::write_nested_impls_mod(impls, file_mod.get(), &mod_path, |impls| {
+                    r = self.write_nested_items(&oneofs, &nested_messages, w, Some(impls))
                 });
+            }
+            None => r = self.write_nested_items(&oneofs, &nested_messages, w, None),
+        });
+        r
+    }
 
-                let mut path = self.path.to_vec();
-                path.extend(&[nested_type_number, 0]);
-                for (id, nested) in nested_messages.iter().enumerate() {
-                    let len = path.len() - 1;
-                    path[len] = id as i32;
+    fn write_nested_items(
+        &self,
+        oneofs: &[OneofGen],
+        nested_messages: &[MessageWithScope],
+        w: &mut CodeWriter,
+        mut impls: Option<&mut CodeWriter>,
+    ) -> anyhow::Result<()> {
+        let mut first = true;
 
-                    if !first {
-                        w.write_line("");
-                    }
-                    first = false;
-                    MessageGen::new(
-                        &self.file_descriptor,
-                        nested,
-                        self.file_index,
-                        self.root_scope,
-                        &self.customize,
-                        &path,
-                        self.info,
-                    )
-                    .write(w);
-                }
+        for oneof in oneofs {
+            w.write_line("");
+            oneof.write(w, impls.as_deref_mut());
+        }
 
-                static ENUM_TYPE_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
-                let enum_type_number = *ENUM_TYPE_NUMBER.get(|| {
-                    protobuf::reflect::MessageDescriptor::for_type::<DescriptorProto>()
-                        .get_field_by_name("enum_type")
-                        .expect("`enum_type` must exist")
-                        .get_proto()
-                        .get_number()
-                });
+        static NESTED_TYPE_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
+        let nested_type_number = *NESTED_TYPE_NUMBER.get(|| {
+            protobuf::reflect::MessageDescriptor::for_type::<DescriptorProto>()
+                .get_field_by_name("nested_type")
+                .expect("`nested_type` must exist")
+                .get_proto()
+                .get_number()
+        });
 
-                let len = path.len() - 2;
-                path[len] = enum_type_number;
-                for (id, enum_type) in self.message.to_scope().get_enums().iter().enumerate() {
-                    let len = path.len() - 1;
-                    path[len] = id as i32;
+        let mut nested_customize = self.customize.clone();
+        nested_customize.bytes_wrapper = None;
+        nested_customize.repeated_index_impl = None;
+        nested_customize.primary_key_field = None;
+        nested_customize.display_format = None;
 
-                    if !first {
-                        w.write_line("");
-                    }
-                    first = false;
-                    EnumGen::new(
-                        enum_type,
-                        self.file_index,
-                        &self.customize,
-                        self.root_scope,
-                        &path,
-                        self.info,
-                    )
-                    .write(w);
-                }
-            });
+        let mut path = self.path.to_vec();
+        path.extend(&[nested_type_number, 0]);
+        for (id, nested) in nested_messages.iter().enumerate() {
+            let len = path.len() - 1;
+            path[len] = id as i32;
+
+            if !first {
+                w.write_line("");
+            }
+            first = false;
+            MessageGen::new(
+                &self.file_descriptor,
+                nested,
+                self.file_index,
+                self.root_scope,
+                &nested_customize,
+                &path,
+                self.info,
+            )?
+            .write(w, impls.as_deref_mut())?;
+        }
+
+        static ENUM_TYPE_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
+        let enum_type_number = *ENUM_TYPE_NUMBER.get(|| {
+            protobuf::reflect::MessageDescriptor::for_type::<DescriptorProto>()
+                .get_field_by_name("enum_type")
+                .expect("`enum_type` must exist")
+                .get_proto()
+                .get_number()
+        });
+
+        let len = path.len() - 2;
+        path[len] = enum_type_number;
+        for (id, enum_type) in self.message.to_scope().get_enums().iter().enumerate() {
+            let len = path.len() - 1;
+            path[len] = id as i32;
+
+            if !first {
+                w.write_line("");
+            }
+            first = false;
+            EnumGen::new(
+                enum_type,
+                self.file_index,
+                &self.customize,
+                self.root_scope,
+                &path,
+                self.info,
+            )?
+            .write(w, impls.as_deref_mut());
         }
+        Ok(())
     }
 }
 
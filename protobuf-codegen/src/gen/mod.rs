@@ -1,5 +1,6 @@
 pub(crate) mod all;
 pub(crate) mod code_writer;
+pub(crate) mod display_format;
 pub(crate) mod enums;
 pub(crate) mod extensions;
 pub(crate) mod field;
@@ -13,11 +14,13 @@ pub(crate) mod message;
 pub(crate) mod mod_rs;
 pub(crate) mod oneof;
 pub(crate) mod paths;
+pub(crate) mod repeated_type;
 pub(crate) mod rust;
 pub(crate) mod rust_name;
 pub(crate) mod rust_types_values;
 pub(crate) mod scope;
 pub(crate) mod serde;
+pub(crate) mod split_impls;
 pub(crate) mod strx;
 pub(crate) mod syntax;
 pub(crate) mod well_known_types;
@@ -1,17 +1,45 @@
 use crate::compiler_plugin;
 use crate::gen::code_writer::CodeWriter;
+use crate::gen::split_impls::impls_mod_name;
+use crate::Customize;
 
-pub(crate) fn gen_mod_rs(mods: &[String]) -> compiler_plugin::GenResult {
+fn write_mods(w: &mut CodeWriter, mods: &[String], customize: &Customize) {
+    for m in mods {
+        w.write_line(&format!("pub mod {};", m));
+        if customize.split_impls.unwrap_or(false) {
+            w.write_line(&format!("mod {};", impls_mod_name(m)));
+        }
+    }
+}
+
+pub(crate) fn gen_mod_rs(mods: &[String], customize: &Customize) -> compiler_plugin::GenResult {
     let mut v = Vec::new();
     let mut w = CodeWriter::new(&mut v);
     w.comment(&format!("{}generated", "@"));
     w.write_line("");
-    for m in mods {
-        w.write_line(&format!("pub mod {};", m));
-    }
+    write_mods(&mut w, mods, customize);
     drop(w);
     compiler_plugin::GenResult {
         name: "mod.rs".to_owned(),
         content: v,
     }
 }
+
+/// Generate `<module_name>.rs` file declaring module `module_name`
+/// with generated modules located in `<module_name>` subdirectory.
+pub(crate) fn gen_include_rs(
+    module_name: &str,
+    mods: &[String],
+    customize: &Customize,
+) -> compiler_plugin::GenResult {
+    let mut v = Vec::new();
+    let mut w = CodeWriter::new(&mut v);
+    w.comment(&format!("{}generated", "@"));
+    w.write_line("");
+    w.pub_mod(module_name, |w| write_mods(w, mods, customize));
+    drop(w);
+    compiler_plugin::GenResult {
+        name: format!("{}.rs", module_name),
+        content: v,
+    }
+}
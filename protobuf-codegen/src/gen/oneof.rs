@@ -224,19 +224,29 @@ impl<'a> OneofGen<'a> {
     }
 
     fn get_file_and_mod(&self) -> FileAndMod {
-        let mut file_and_mod = self
+        // oneof enum is declared in the message scope
+        self.message
             .message
-            .message
-            .scope
-            .get_file_and_mod(self.customize.clone());
-        file_and_mod
-            .relative_mod
-            .push_ident(self.message.message.mod_name());
-        file_and_mod
+            .to_scope()
+            .get_file_and_mod(self.customize.clone())
+    }
+
+    fn nan_eq(&self) -> bool {
+        self.customize.nan_eq.unwrap_or(false)
     }
 
     fn write_enum(&self, w: &mut CodeWriter) {
-        let derive = vec!["Clone", "PartialEq", "Debug"];
+        let mut derive = vec!["Clone"];
+        if !self.nan_eq() {
+            derive.push("PartialEq");
+            if self.customize.impl_eq.unwrap_or(false) {
+                derive.push("Eq");
+            }
+        }
+        if self.customize.impl_hash.unwrap_or(false) {
+            derive.push("Hash");
+        }
+        derive.push("Debug");
         w.derive(&derive);
         serde::write_serde_attr(
             w,
@@ -245,6 +255,7 @@ impl<'a> OneofGen<'a> {
         );
         w.pub_enum(&self.oneof.rust_name().ident.to_string(), |w| {
             for variant in self.variants_except_group() {
+                variant.field.write_documentation(w);
                 w.write_line(&format!(
                     "{}({}),",
                     variant.field.rust_name,
@@ -256,6 +267,65 @@ impl<'a> OneofGen<'a> {
         });
     }
 
+    /// Name of enum of oneof cases without values.
+    fn case_enum_rust_name(&self) -> RustIdentWithPath {
+        let mut name = self.oneof.rust_name();
+        name.ident = RustIdent::from(format!("{}Case", name.ident));
+        name
+    }
+
+    fn write_case_enum(&self, w: &mut CodeWriter) {
+        w.write_line(&format!(
+            "/// Which field of oneof `{}` is set, returned by `which_{}`",
+            self.oneof.oneof.get_name(),
+            self.oneof.field_name(),
+        ));
+        w.derive(&["Clone", "Copy", "PartialEq", "Eq", "Hash", "Debug"]);
+        w.pub_enum(&self.case_enum_rust_name().ident.to_string(), |w| {
+            w.write_line("NotSet,");
+            for variant in self.variants_except_group() {
+                w.write_line(&format!("{},", variant.field.rust_name));
+            }
+        });
+    }
+
+    /// Write `which_xxx` function of the message.
+    pub fn write_which_fn(&self, w: &mut CodeWriter) {
+        let reference = self
+            .oneof
+            .message
+            .scope
+            .get_file_and_mod(self.customize.clone())
+            .relative_mod
+            .into_path();
+        let oneof_type = self.type_name_relative(&reference);
+        let case_type = make_path(&reference, &self.case_enum_rust_name());
+        w.comment(&format!(
+            "Which field of oneof `{}` is set",
+            self.oneof.oneof.get_name()
+        ));
+        w.pub_fn(
+            &format!("which_{}(&self) -> {}", self.oneof.field_name(), case_type),
+            |w| {
+                w.match_expr(format!("self.{}", self.oneof.field_name()), |w| {
+                    w.case_expr(
+                        "::std::option::Option::None",
+                        format!("{}::NotSet", case_type),
+                    );
+                    for variant in self.variants_except_group() {
+                        w.case_expr(
+                            format!(
+                                "::std::option::Option::Some({}::{}(..))",
+                                oneof_type, variant.field.rust_name
+                            ),
+                            format!("{}::{}", case_type, variant.field.rust_name),
+                        );
+                    }
+                });
+            },
+        );
+    }
+
     fn write_impl_oneof(&self, w: &mut CodeWriter) {
         w.impl_for_block(
             &format!("{}::Oneof", protobuf_crate_path(&self.customize)),
@@ -266,8 +336,56 @@ impl<'a> OneofGen<'a> {
         );
     }
 
-    pub fn write(&self, w: &mut CodeWriter) {
+    fn write_nan_eq_impl_partial_eq(&self, w: &mut CodeWriter) {
+        let name = self.oneof.rust_name().ident.to_string();
+        let variants = self.variants_except_group();
+        w.impl_for_block("::std::cmp::PartialEq", &name, |w| {
+            w.def_fn("eq(&self, other: &Self) -> bool", |w| {
+                w.match_expr("(self, other)", |w| {
+                    for variant in &variants {
+                        let eq = match variant.field.elem().is_float() {
+                            true => "a.to_bits() == b.to_bits()",
+                            false => "a == b",
+                        };
+                        w.case_expr(
+                            format!(
+                                "({n}::{v}(a), {n}::{v}(b))",
+                                n = name,
+                                v = variant.field.rust_name
+                            ),
+                            eq,
+                        );
+                    }
+                    if variants.len() > 1 {
+                        w.case_expr("_", "false");
+                    }
+                });
+            });
+        });
+        if self.customize.impl_eq.unwrap_or(false) {
+            w.write_line("");
+            w.write_line(&format!("impl ::std::cmp::Eq for {} {{}}", name));
+        }
+    }
+
+    /// Write oneof enum, and its implementations with `impls` writer if specified.
+    pub fn write(&self, w: &mut CodeWriter, impls: Option<&mut CodeWriter>) {
         self.write_enum(w);
+        if self.customize.generate_oneof_case_enum.unwrap_or(false) {
+            w.write_line("");
+            self.write_case_enum(w);
+        }
+        match impls {
+            Some(impls) => self.write_impls(impls),
+            None => self.write_impls(w),
+        }
+    }
+
+    fn write_impls(&self, w: &mut CodeWriter) {
+        if self.nan_eq() {
+            w.write_line("");
+            self.write_nan_eq_impl_partial_eq(w);
+        }
         w.write_line("");
         self.write_impl_oneof(w);
     }
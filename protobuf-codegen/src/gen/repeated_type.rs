@@ -0,0 +1,64 @@
+//! Parser of `repeated_type` option values.
+
+use crate::gen::field::RepeatedFieldKind;
+
+/// Parse type like `Vec` or `smallvec::SmallVec<[_; 4]>`.
+pub(crate) fn parse_repeated_type(repeated_type: &str) -> Result<RepeatedFieldKind, String> {
+    let compact: String = repeated_type
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let compact = compact.trim_start_matches("::");
+    match compact {
+        "Vec" | "Vec<_>" | "std::vec::Vec" | "std::vec::Vec<_>" => {
+            return Ok(RepeatedFieldKind::Vec)
+        }
+        _ => {}
+    }
+    let inline = compact
+        .strip_prefix("smallvec::SmallVec<[_;")
+        .and_then(|s| s.strip_suffix("]>"))
+        .ok_or_else(|| {
+            format!(
+                "unknown repeated type `{}`, expecting `Vec` or `smallvec::SmallVec<[_; N]>`",
+                repeated_type
+            )
+        })?;
+    let inline = inline.parse().map_err(|_| {
+        format!(
+            "incorrect inline element count in repeated type `{}`",
+            repeated_type
+        )
+    })?;
+    Ok(RepeatedFieldKind::SmallVec(inline))
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_repeated_type;
+    use crate::gen::field::RepeatedFieldKind;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Ok(RepeatedFieldKind::Vec), parse_repeated_type("Vec"));
+        assert_eq!(
+            Ok(RepeatedFieldKind::Vec),
+            parse_repeated_type("::std::vec::Vec<_>")
+        );
+        assert_eq!(
+            Ok(RepeatedFieldKind::SmallVec(2)),
+            parse_repeated_type("smallvec::SmallVec<[_; 2]>")
+        );
+        assert_eq!(
+            Ok(RepeatedFieldKind::SmallVec(16)),
+            parse_repeated_type("::smallvec::SmallVec<[_;16]>")
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse_repeated_type("VecDeque<_>").is_err());
+        assert!(parse_repeated_type("smallvec::SmallVec<[_; N]>").is_err());
+        assert!(parse_repeated_type("smallvec::SmallVec<[u32; 2]>").is_err());
+    }
+}
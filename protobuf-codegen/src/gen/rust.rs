@@ -104,6 +104,8 @@ pub fn quote_escape_bytes(bytes: &[u8]) -> String {
 
 pub(crate) const EXPR_NONE: &str = "::std::option::Option::None";
 pub(crate) const EXPR_VEC_NEW: &str = "::std::vec::Vec::new()";
+pub(crate) const EXPR_SMALLVEC_NEW: &str = "::smallvec::SmallVec::new()";
+pub(crate) const EXPR_BOXED_SLICE_NEW: &str = "::std::boxed::Box::default()";
 
 #[cfg(test)]
 mod test {
@@ -80,6 +80,10 @@ impl RustRelativePath {
         self.path.iter().cloned().next()
     }
 
+    pub fn last(&self) -> Option<RustIdent> {
+        self.path.last().cloned()
+    }
+
     pub fn remove_first(&mut self) -> Option<RustIdent> {
         if self.path.is_empty() {
             None
@@ -7,7 +7,9 @@ use crate::customize::Customize;
 use crate::gen::file_and_mod::FileAndMod;
 use crate::gen::inside::protobuf_crate_path;
 use crate::gen::message::RustTypeMessage;
+use crate::gen::rust::EXPR_BOXED_SLICE_NEW;
 use crate::gen::rust::EXPR_NONE;
+use crate::gen::rust::EXPR_SMALLVEC_NEW;
 use crate::gen::rust::EXPR_VEC_NEW;
 use crate::gen::rust_name::RustIdent;
 use crate::gen::rust_name::RustIdentWithPath;
@@ -26,6 +28,10 @@ pub(crate) enum RustType {
     Float(u32),
     Bool,
     Vec(Box<RustType>),
+    // smallvec::SmallVec<[T; N]>
+    SmallVec(Box<RustType>, usize),
+    // Box<[T]>
+    BoxedSlice(Box<RustType>),
     HashMap(Box<RustType>, Box<RustType>),
     String,
     // [T], not &[T]
@@ -63,6 +69,14 @@ impl RustType {
             RustType::Float(bits) => format!("f{}", bits),
             RustType::Bool => format!("bool"),
             RustType::Vec(ref param) => format!("::std::vec::Vec<{}>", param.to_code(customize)),
+            RustType::SmallVec(ref param, inline) => format!(
+                "::smallvec::SmallVec<[{}; {}]>",
+                param.to_code(customize),
+                inline
+            ),
+            RustType::BoxedSlice(ref param) => {
+                format!("::std::boxed::Box<[{}]>", param.to_code(customize))
+            }
             RustType::HashMap(ref key, ref value) => format!(
                 "::std::collections::HashMap<{}, {}>",
                 key.to_code(customize),
@@ -199,6 +213,8 @@ impl RustType {
             RustType::Float(..) => "0.".to_string(),
             RustType::Bool => "false".to_string(),
             RustType::Vec(..) => EXPR_VEC_NEW.to_string(),
+            RustType::SmallVec(..) => EXPR_SMALLVEC_NEW.to_string(),
+            RustType::BoxedSlice(..) => EXPR_BOXED_SLICE_NEW.to_string(),
             RustType::HashMap(..) => "::std::collections::HashMap::new()".to_string(),
             RustType::String => "::std::string::String::new()".to_string(),
             RustType::Bytes => "::bytes::Bytes::new()".to_string(),
@@ -241,10 +257,12 @@ impl RustType {
         match *self {
             RustType::Option(..) => format!("{} = {}", v, EXPR_NONE),
             RustType::Vec(..)
+            | RustType::SmallVec(..)
             | RustType::Bytes
             | RustType::String
             | RustType::MessageField(..)
             | RustType::HashMap(..) => format!("{}.clear()", v),
+            RustType::BoxedSlice(..) => format!("{} = {}", v, EXPR_BOXED_SLICE_NEW),
             RustType::Chars => format!(
                 "{}::Clear::clear(&mut {})",
                 protobuf_crate_path(customize),
@@ -323,6 +341,12 @@ impl RustType {
                        RustType::Slice(ref y) => x == y,
                        _ => false,
                    } => return Ok(format!("&{}", v)),
+            (&RustType::SmallVec(ref x, _), &RustType::Ref(ref t))
+            | (&RustType::BoxedSlice(ref x), &RustType::Ref(ref t))
+                if match **t {
+                       RustType::Slice(ref y) => x == y,
+                       _ => false,
+                   } => return Ok(format!("&{}", v)),
             (&RustType::Bytes, &RustType::Ref(ref t))
                 if match **t {
                        RustType::Slice(ref y) => **y == RustType::u8(),
@@ -331,6 +355,8 @@ impl RustType {
             (&RustType::Ref(ref t1), &RustType::Ref(ref t2))
                 if match (&**t1, &**t2) {
                        (&RustType::Vec(ref x), &RustType::Slice(ref y)) => x == y,
+                       (&RustType::SmallVec(ref x, _), &RustType::Slice(ref y)) => x == y,
+                       (&RustType::BoxedSlice(ref x), &RustType::Slice(ref y)) => x == y,
                        _ => false,
                    } => return Ok(format!("&{}", v)),
             (&RustType::Enum(..), &RustType::Int(true, 32)) => {
@@ -368,7 +394,9 @@ impl RustType {
     pub fn ref_type(&self) -> RustType {
         RustType::Ref(Box::new(match self {
             &RustType::String | &RustType::Chars => RustType::Str,
-            &RustType::Vec(ref p) => RustType::Slice(p.clone()),
+            &RustType::Vec(ref p)
+            | &RustType::SmallVec(ref p, _)
+            | &RustType::BoxedSlice(ref p) => RustType::Slice(p.clone()),
             &RustType::Bytes => RustType::Slice(Box::new(RustType::u8())),
             &RustType::Message(ref p) => RustType::Message(p.clone()),
             &RustType::Uniq(ref p) => RustType::Uniq(p.clone()),
@@ -388,6 +416,8 @@ impl RustType {
     pub fn iter_elem_type(&self) -> RustType {
         match self {
             &RustType::Vec(ref ty)
+            | &RustType::SmallVec(ref ty, _)
+            | &RustType::BoxedSlice(ref ty)
             | &RustType::Option(ref ty)
             | &RustType::MessageField(ref ty) => RustType::Ref(ty.clone()),
             x => panic!("cannot iterate {:?}", x),
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use protobuf::descriptor::FileDescriptorProto;
 use protobuf::reflect::EnumDescriptor;
 use protobuf::reflect::EnumValueDescriptor;
@@ -5,11 +7,15 @@ use protobuf::reflect::FieldDescriptor;
 use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::MessageDescriptor;
 use protobuf::reflect::OneofDescriptor;
+use protobuf::rustproto;
 use protobuf_parse::ProtobufAbsolutePath;
 use protobuf_parse::ProtobufIdent;
 use protobuf_parse::ProtobufRelativePath;
 
+use crate::customize::customize_from_rustproto_for_file;
+use crate::customize::customize_from_rustproto_for_message;
 use crate::customize::Customize;
+use crate::customize::NestedTypeStrategy;
 use crate::gen::field::rust_field_name_for_protobuf_field_name;
 use crate::gen::file_and_mod::FileAndMod;
 use crate::gen::map::map_entry;
@@ -22,19 +28,32 @@ use crate::gen::rust_name::RustIdentWithPath;
 use crate::gen::rust_name::RustRelativePath;
 use crate::gen::strx::capitalize;
 use crate::gen::syntax::Syntax;
+use crate::gen::well_known_types::WELL_KNOWN_TYPES_PROTO_FILE_FULL_NAMES;
 
 pub(crate) struct RootScope<'a> {
     pub file_descriptors: &'a [FileDescriptor],
+    pub customize: &'a Customize,
+    packages: Vec<FileScope<'a>>,
 }
 
 impl<'a> RootScope<'a> {
-    fn packages(&'a self) -> Vec<FileScope<'a>> {
-        self.file_descriptors
+    pub fn new(
+        file_descriptors: &'a [FileDescriptor],
+        customize: &'a Customize,
+    ) -> anyhow::Result<RootScope<'a>> {
+        let packages = file_descriptors
             .iter()
-            .map(|fd| FileScope {
-                file_descriptor: fd,
-            })
-            .collect()
+            .map(|fd| FileScope::new(fd, customize))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(RootScope {
+            file_descriptors,
+            customize,
+            packages,
+        })
+    }
+
+    fn packages(&'a self) -> Vec<FileScope<'a>> {
+        self.packages.clone()
     }
 
     // find enum by fully qualified name
@@ -70,9 +89,63 @@ impl<'a> RootScope<'a> {
 #[derive(Clone, Debug)]
 pub(crate) struct FileScope<'a> {
     pub file_descriptor: &'a FileDescriptor,
+    pub nested_type_strategy: NestedTypeStrategy,
 }
 
 impl<'a> FileScope<'a> {
+    /// `customize` is codegen invocation customize, file options override it.
+    pub fn new(
+        file_descriptor: &'a FileDescriptor,
+        customize: &Customize,
+    ) -> anyhow::Result<FileScope<'a>> {
+        let nested_type_strategy = NestedTypeStrategy::from_rustproto(
+            rustproto::exts::nested_type_strategy_all
+                .get(file_descriptor.proto().options.get_or_default()),
+        )?
+        .or(customize.nested_type_strategy)
+        .unwrap_or(NestedTypeStrategy::Modules);
+        let file_scope = FileScope {
+            file_descriptor,
+            nested_type_strategy,
+        };
+        file_scope.check_flattened_names()?;
+        Ok(file_scope)
+    }
+
+    /// Flattened names of nested types may clash with other type names,
+    /// e. g. nested `Outer.Inner` and top-level `OuterInner` with `FlattenCamel`.
+    fn check_flattened_names(&self) -> anyhow::Result<()> {
+        if self.nested_type_strategy == NestedTypeStrategy::Modules {
+            return Ok(());
+        }
+        let mut names: HashMap<String, String> = HashMap::new();
+        let mut types = Vec::new();
+        for m in self.find_messages_except_map() {
+            types.push((m.rust_name().to_string(), m.name_to_package()));
+            for oneof in m.oneofs() {
+                types.push((
+                    oneof.rust_name().ident.to_string(),
+                    format!("{}.{}", m.name_to_package(), oneof.oneof.get_name()),
+                ));
+            }
+        }
+        for e in self.find_enums() {
+            types.push((e.rust_name().to_string(), e.name_to_package()));
+        }
+        for (rust_name, proto_name) in types {
+            if let Some(other) = names.insert(rust_name.clone(), proto_name.clone()) {
+                return Err(anyhow::anyhow!(
+                    "file `{}`: `{}` and `{}` have the same flattened Rust name `{}`",
+                    self.file_descriptor.proto().get_name(),
+                    other,
+                    proto_name,
+                    rust_name
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn get_package(&self) -> ProtobufAbsolutePath {
         ProtobufRelativePath::from(self.file_descriptor.proto().get_package()).into_absolute()
     }
@@ -243,11 +316,31 @@ impl<'a> Scope<'a> {
     }
 
     pub fn rust_path_to_file(&self) -> RustRelativePath {
-        RustRelativePath::from_components(
-            self.path
-                .iter()
-                .map(|m| message_name_to_nested_mod_name(m.get_name())),
-        )
+        match self.file_scope.nested_type_strategy {
+            NestedTypeStrategy::Modules => RustRelativePath::from_components(
+                self.path
+                    .iter()
+                    .map(|m| message_name_to_nested_mod_name(m.get_name())),
+            ),
+            // flattened types are declared at file level
+            NestedTypeStrategy::FlattenUnderscore | NestedTypeStrategy::FlattenCamel => {
+                RustRelativePath::from_components(Vec::<RustIdent>::new())
+            }
+        }
+    }
+
+    /// Rust type name of a message, enum or oneof with given name declared in this scope,
+    /// without path and keyword escaping.
+    pub fn rust_type_name_in_scope(&self, name: &str) -> String {
+        let separator = match self.file_scope.nested_type_strategy {
+            NestedTypeStrategy::Modules => return capitalize(name),
+            NestedTypeStrategy::FlattenUnderscore => "_",
+            NestedTypeStrategy::FlattenCamel => "",
+        };
+        let mut components: Vec<String> =
+            self.path.iter().map(|m| capitalize(m.get_name())).collect();
+        components.push(capitalize(name));
+        components.join(separator)
     }
 
     pub fn path_str(&self) -> String {
@@ -322,7 +415,9 @@ pub(crate) trait WithScope<'a> {
 
     // rust type name of this descriptor
     fn rust_name(&self) -> RustIdent {
-        let mut rust_name = capitalize(self.get_name().get());
+        let mut rust_name = self
+            .get_scope()
+            .rust_type_name_in_scope(self.get_name().get());
 
         if is_rust_keyword(&rust_name) {
             rust_name.insert_str(0, self.escape_prefix());
@@ -378,6 +473,31 @@ impl<'a> MessageWithScope<'a> {
         self.clone().into_scope()
     }
 
+    /// Options this message is generated with when code generator is invoked
+    /// with given options.
+    ///
+    /// Messages of files bundled in `protobuf` crate are generated with default options.
+    pub fn customize(&self, root: &Customize) -> anyhow::Result<Customize> {
+        let file = self.get_file_descriptor();
+        let bundled = match file.get_name() {
+            "rustproto.proto" | "google/protobuf/descriptor.proto" => true,
+            name => WELL_KNOWN_TYPES_PROTO_FILE_FULL_NAMES.contains(&name),
+        };
+        if bundled && root.inside_protobuf != Some(true) {
+            return Ok(Customize::default());
+        }
+        let mut customize = root.clone();
+        customize.update_with(&customize_from_rustproto_for_file(
+            file.options.get_or_default(),
+        )?);
+        for message in self.scope.path.iter().chain(Some(&self.message)) {
+            customize.update_with(&customize_from_rustproto_for_message(
+                message.get_proto().options.get_or_default(),
+            )?);
+        }
+        Ok(customize)
+    }
+
     pub fn fields(&self) -> Vec<FieldWithContext<'a>> {
         self.message
             .fields()
@@ -456,8 +576,38 @@ impl<'a> EnumWithScope<'a> {
             .find(|v| v.proto.get_proto().get_name() == name)
             .unwrap()
     }
+
+    /// Default value of the enum: value with number `0` in proto3,
+    /// first value in proto2.
+    ///
+    /// Error is returned if proto3 enum has no value with number `0`.
+    pub fn default_value(&self) -> anyhow::Result<EnumValueWithContext<'a>> {
+        let values = self.values();
+        match self.scope.file_scope.syntax() {
+            Syntax::PROTO2 => Ok(values.into_iter().next().unwrap()),
+            Syntax::PROTO3 => values
+                .into_iter()
+                .find(|v| v.proto.get_proto().get_number() == 0)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "proto3 enum `{}` must have a value with number 0",
+                        self.en.full_name()
+                    )
+                }),
+        }
+    }
+
+    /// Generated enum has `DEFAULT` constant unless a variant has the same name.
+    pub fn has_default_const(&self) -> bool {
+        self.values()
+            .iter()
+            .all(|v| v.rust_name().get() != ENUM_DEFAULT_CONST)
+    }
 }
 
+/// Name of the generated enum constant holding the default value.
+pub(crate) const ENUM_DEFAULT_CONST: &str = "DEFAULT";
+
 #[derive(Clone, Debug)]
 pub(crate) struct EnumValueWithContext<'a> {
     pub en: EnumWithScope<'a>,
@@ -473,6 +623,21 @@ impl<'a> EnumValueWithContext<'a> {
         r.push_str(self.proto.get_name());
         RustIdent::new(&r)
     }
+
+    /// Name of this variant, or `DEFAULT` constant if this is the enum default value.
+    pub fn rust_name_or_default_const(&self) -> RustIdent {
+        let is_default = match self.en.default_value() {
+            Ok(default_value) => {
+                self.proto.get_proto().get_number() == default_value.proto.get_proto().get_number()
+            }
+            Err(_) => false,
+        };
+        if self.en.has_default_const() && is_default {
+            RustIdent::new(ENUM_DEFAULT_CONST)
+        } else {
+            self.rust_name()
+        }
+    }
 }
 
 impl<'a> WithScope<'a> for EnumWithScope<'a> {
@@ -573,12 +738,9 @@ impl<'a> OneofWithContext<'a> {
     // rust type name of enum
     pub fn rust_name(&self) -> RustIdentWithPath {
         // TODO: escape name
-        let type_name = RustIdent::from(capitalize(self.oneof.get_name()));
-        self.message
-            .to_scope()
-            .rust_path_to_file()
-            .into_path()
-            .with_ident(type_name)
+        let scope = self.message.to_scope();
+        let type_name = RustIdent::from(scope.rust_type_name_in_scope(self.oneof.get_name()));
+        scope.rust_path_to_file().into_path().with_ident(type_name)
     }
 
     pub fn variants(&'a self) -> Vec<OneofVariantWithContext<'a>> {
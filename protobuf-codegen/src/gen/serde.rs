@@ -1,5 +1,7 @@
 use crate::customize::Customize;
 use crate::gen::code_writer::CodeWriter;
+use crate::gen::inside::protobuf_crate_path;
+use crate::gen::rust_types_values::RustType;
 
 /// Write serde attr according to specified codegen option.
 pub(crate) fn write_serde_attr(w: &mut CodeWriter, customize: &Customize, attr: &str) {
@@ -11,3 +13,19 @@ pub(crate) fn write_serde_attr(w: &mut CodeWriter, customize: &Customize, attr:
         }
     }
 }
+
+/// Path to function checking if field of given type has default value,
+/// for `skip_serializing_if` serde attribute.
+pub(crate) fn skip_serializing_if(storage_type: &RustType, customize: &Customize) -> String {
+    match storage_type {
+        RustType::Option(..) => "::std::option::Option::is_none".to_owned(),
+        RustType::MessageField(..) => {
+            format!("{}::MessageField::is_none", protobuf_crate_path(customize))
+        }
+        RustType::Vec(..) => "::std::vec::Vec::is_empty".to_owned(),
+        RustType::SmallVec(..) => "::smallvec::SmallVec::is_empty".to_owned(),
+        RustType::HashMap(..) => "::std::collections::HashMap::is_empty".to_owned(),
+        RustType::String => "::std::string::String::is_empty".to_owned(),
+        _ => format!("{}::rt::is_default", protobuf_crate_path(customize)),
+    }
+}
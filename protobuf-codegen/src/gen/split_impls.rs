@@ -0,0 +1,77 @@
+//! Writing implementations of generated types into a separate module.
+//!
+//! With `split_impls` option, the file generated for `foo.proto` contains
+//! type definitions, and implementations are written into `foo_impls.rs`,
+//! which is a sibling module of `foo`. Impls module contains a module
+//! for each nested module of `foo`, and each module glob-imports items of
+//! corresponding module of `foo`, so paths in implementations resolve
+//! the same way they do in `foo`.
+
+use crate::gen::code_writer::CodeWriter;
+use crate::gen::code_writer::Visibility;
+use crate::gen::rust_name::RustIdent;
+use crate::gen::rust_name::RustRelativePath;
+use crate::Customize;
+
+/// Name of the module with implementations of types of module `mod_name`.
+pub(crate) fn impls_mod_name(mod_name: &str) -> String {
+    format!("{}_impls", mod_name)
+}
+
+/// Visibility of items which are used by implementations
+/// but are not public otherwise, like private fields.
+///
+/// `mod_path` is a path from file module to the module containing the item.
+pub(crate) fn private_visibility(customize: &Customize, mod_path: &RustRelativePath) -> Visibility {
+    if customize.split_impls.unwrap_or(false) {
+        // visible in the parent of file module which contains impls module
+        Visibility::Path(mod_path.to_reverse().append_ident(RustIdent::super_ident()))
+    } else {
+        Visibility::Default
+    }
+}
+
+/// Visibility of items which are accessed from the file module.
+///
+/// `mod_path` is a path from file module to the module containing the item.
+pub(crate) fn file_visibility(customize: &Customize, mod_path: &RustRelativePath) -> Visibility {
+    match private_visibility(customize, mod_path) {
+        Visibility::Default => Visibility::Path(mod_path.to_reverse()),
+        vis => vis,
+    }
+}
+
+/// Write the beginning of impls file for file module `file_mod`.
+pub(crate) fn write_impls_file_header(w: &mut CodeWriter, file_mod: &str, parser: &str) {
+    w.write_generated_by("rust-protobuf", env!("CARGO_PKG_VERSION"), parser);
+    w.write_line("#![allow(unused_imports)]");
+    w.write_line("");
+    w.write_line(&format!(
+        "//! Implementations of types generated into `{}.rs`",
+        file_mod
+    ));
+    w.write_line("");
+    w.write_line(&format!("use super::{}::*;", file_mod));
+}
+
+/// Write impls module for nested module `mod_path` of file module `file_mod`.
+pub(crate) fn write_nested_impls_mod<F>(
+    w: &mut CodeWriter,
+    file_mod: &str,
+    mod_path: &RustRelativePath,
+    cb: F,
+) where
+    F: FnOnce(&mut CodeWriter),
+{
+    let types_mod = mod_path
+        .to_reverse()
+        .append_ident(RustIdent::super_ident())
+        .append_ident(RustIdent::from(file_mod))
+        .append(mod_path.clone());
+    w.write_line("");
+    w.pub_mod(mod_path.last().unwrap().get(), |w| {
+        // reexported for paths from other impls modules like `outer::Inner`
+        w.write_line(&format!("pub use {}::*;", types_mod));
+        cb(w);
+    });
+}
@@ -3,6 +3,7 @@ use protobuf_parse::ProtobufAbsolutePath;
 use protobuf_parse::ProtobufRelativePath;
 
 use crate::compiler_plugin;
+use crate::customize::Customize;
 use crate::gen::code_writer::CodeWriter;
 use crate::gen::paths::proto_path_to_rust_mod;
 use crate::gen::scope::FileScope;
@@ -107,7 +108,7 @@ fn find_file_descriptor<'a>(
 
 pub(crate) fn gen_well_known_types_mod(
     file_descriptors: &[FileDescriptor],
-) -> compiler_plugin::GenResult {
+) -> anyhow::Result<compiler_plugin::GenResult> {
     let mut v = Vec::new();
 
     {
@@ -134,7 +135,7 @@ pub(crate) fn gen_well_known_types_mod(
 
             let rust_mod = proto_path_to_rust_mod(p);
 
-            let file_scope = FileScope { file_descriptor };
+            let file_scope = FileScope::new(file_descriptor, &Customize::default())?;
 
             for m in file_scope.to_scope().get_messages() {
                 w.write_line(&format!("pub use self::{}::{};", rust_mod, m.rust_name()));
@@ -162,10 +163,10 @@ pub(crate) fn gen_well_known_types_mod(
         })
     }
 
-    compiler_plugin::GenResult {
+    Ok(compiler_plugin::GenResult {
         name: "well_known_types_mod.rs".to_string(),
         content: v,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -18,6 +18,8 @@ enum Error {
     OutputDoesNotExistOrNotAccssible(String, #[source] io::Error),
     #[error("failed to create file `{0}`: {1}")]
     FailedToWriteFile(String, #[source] io::Error),
+    #[error("generated code for `{0}` is not valid UTF-8")]
+    GeneratedCodeIsNotUtf8(String),
 }
 
 #[doc(hidden)]
@@ -27,6 +29,7 @@ pub fn gen_and_write(
     files_to_generate: &[ProtoPathBuf],
     out_dir: &Path,
     customize: &Customize,
+    post_process: Option<&dyn Fn(&Path, String) -> String>,
 ) -> anyhow::Result<()> {
     match out_dir.metadata() {
         Ok(m) => {
@@ -43,10 +46,18 @@ pub fn gen_and_write(
 
     let results = gen_all(file_descriptors, parser, files_to_generate, customize)?;
 
-    for r in &results {
+    for r in results {
         let mut file_path = out_dir.to_owned();
         file_path.push(&r.name);
-        fs::write(&file_path, r.content.as_slice())
+        let content = match post_process {
+            Some(post_process) => {
+                let content = String::from_utf8(r.content)
+                    .map_err(|_| Error::GeneratedCodeIsNotUtf8(file_path.display().to_string()))?;
+                post_process(&file_path, content).into_bytes()
+            }
+            None => r.content,
+        };
+        fs::write(&file_path, content)
             .map_err(|e| Error::FailedToWriteFile(file_path.display().to_string(), e))?;
     }
 
@@ -0,0 +1,599 @@
+use std::fmt;
+
+use protobuf_parse::pure::parse_and_typecheck_custom;
+use protobuf_parse::pure::ProtoPathResolver;
+use protobuf_parse::pure::ResolvedProtoFile;
+use protobuf_parse::ProtoPath;
+use protobuf_parse::ProtoPathBuf;
+
+use crate::gen::all::gen_all;
+use crate::CodegenError;
+use crate::Customize;
+
+/// Name of the `.proto` file passed to [`generate_from_str`].
+const INPUT_NAME: &str = "embedded.proto";
+
+/// Resolver which serves the single in-memory file.
+struct StrResolver<'a> {
+    proto_text: &'a str,
+}
+
+impl<'a> fmt::Display for StrResolver<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "embedded .proto text")
+    }
+}
+
+impl<'a> ProtoPathResolver for StrResolver<'a> {
+    fn resolve(&self, path: &ProtoPath) -> anyhow::Result<Option<ResolvedProtoFile>> {
+        if path == INPUT_NAME {
+            Ok(Some(ResolvedProtoFile {
+                path: INPUT_NAME.to_owned(),
+                content: self.proto_text.as_bytes().to_vec(),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Generate Rust code from `.proto` file content.
+///
+/// The file is parsed with pure Rust parser. It must be self-contained:
+/// only built-in files like `google/protobuf/timestamp.proto`
+/// or `rustproto.proto` can be imported.
+///
+/// Returns the content of generated `.rs` file. This function does not
+/// touch the file system, so it can be used from procedural macros.
+///
+/// # Examples
+///
+/// ```
+/// let rs = protobuf_codegen::generate_from_str(
+///     "syntax = 'proto3'; message Foo { int32 bar = 1; }",
+///     protobuf_codegen::Customize::default(),
+/// )
+/// .unwrap();
+/// assert!(rs.contains("pub struct Foo"));
+/// ```
+pub fn generate_from_str(proto_text: &str, customize: Customize) -> Result<String, CodegenError> {
+    let input = ProtoPathBuf::new(INPUT_NAME.to_owned())
+        .map_err(|e| CodegenError::ParseFailed(e.into()))?;
+    let file_descriptors = parse_and_typecheck_custom(&[input.clone()], StrResolver { proto_text })
+        .map_err(|e| CodegenError::ParseFailed(e.into()))?;
+
+    let customize = Customize {
+        gen_mod_rs: Some(false),
+        ..customize
+    };
+    let parser = format!("protobuf-codegen={}", env!("CARGO_PKG_VERSION"));
+    let mut results = gen_all(&file_descriptors, &parser, &[input], &customize)
+        .map_err(|e| CodegenError::GenFailed(e.into()))?;
+    // The first result is the file for the input, others are `mod.rs` files, if any.
+    let result = results.remove(0);
+    String::from_utf8(result.content)
+        .map_err(|_| CodegenError::GenFailed("generated code is not valid UTF-8".into()))
+}
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::source_code_info::Location;
+    use protobuf::descriptor::SourceCodeInfo;
+    use protobuf_parse::pure::parse_and_typecheck_custom;
+    use protobuf_parse::ProtoPathBuf;
+
+    use super::generate_from_str;
+    use super::StrResolver;
+    use super::INPUT_NAME;
+    use crate::gen::all::gen_all;
+    use crate::CodegenError;
+    use crate::Customize;
+    use crate::NestedTypeStrategy;
+
+    /// Generate code with given comments, because pure parser does not fill `SourceCodeInfo`.
+    fn generate_with_comments(proto_text: &str, comments: &[(&[i32], &str)]) -> String {
+        let input = ProtoPathBuf::new(INPUT_NAME.to_owned()).unwrap();
+        let mut file_descriptors =
+            parse_and_typecheck_custom(&[input.clone()], StrResolver { proto_text }).unwrap();
+        let mut info = SourceCodeInfo::new();
+        for (path, comment) in comments {
+            let mut location = Location::new();
+            location.path = path.to_vec();
+            location.set_leading_comments(comment.to_string());
+            info.location.push(location);
+        }
+        file_descriptors[0].source_code_info = ::protobuf::MessageField::some(info);
+        let results = gen_all(&file_descriptors, "test", &[input], &Customize::default()).unwrap();
+        String::from_utf8(results[0].content.clone()).unwrap()
+    }
+
+    #[test]
+    fn generate() {
+        let rs = generate_from_str(
+            r#"
+                syntax = "proto3";
+                import "google/protobuf/timestamp.proto";
+                message Event {
+                    string name = 1;
+                    google.protobuf.Timestamp time = 2;
+                }
+            "#,
+            Customize::default(),
+        )
+        .unwrap();
+        assert!(rs.contains("pub struct Event"));
+        assert!(rs.contains("::protobuf::well_known_types::Timestamp"));
+    }
+
+    #[test]
+    fn import_not_allowed() {
+        let r = generate_from_str(
+            "syntax = 'proto3'; import 'other.proto';",
+            Customize::default(),
+        );
+        match r {
+            Err(CodegenError::ParseFailed(_)) => {}
+            r => panic!("expecting parse error: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn no_syntax_is_proto2() {
+        let rs = generate_from_str(
+            "message Foo { required int32 a = 1; optional string b = 2; }",
+            Customize::default(),
+        )
+        .unwrap();
+        assert!(rs.contains("a: ::std::option::Option<i32>,"));
+        assert!(rs.contains("pub fn has_b(&self) -> bool"));
+        assert!(rs.contains("if self.a.is_none()"));
+    }
+
+    #[test]
+    fn unknown_syntax() {
+        let r = generate_from_str("syntax = 'proto4';", Customize::default());
+        match r {
+            Err(CodegenError::ParseFailed(e)) => {
+                assert!(format!("{:?}", e).contains("unknown syntax `proto4`"))
+            }
+            r => panic!("expecting parse error: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn enum_default_const() {
+        let rs = generate_from_str(
+            "syntax = 'proto3'; enum E { ZERO = 0; ONE = 1; } message M { E e = 1; }",
+            Customize::default(),
+        )
+        .unwrap();
+        assert!(rs.contains("pub const DEFAULT: E = E::ZERO;"));
+        assert!(rs.contains("E::DEFAULT"));
+    }
+
+    #[test]
+    fn proto3_enum_without_zero() {
+        for proto in &[
+            "syntax = 'proto3'; enum E { ONE = 1; }",
+            "syntax = 'proto3'; message M { E e = 1; } enum E { ONE = 1; }",
+        ] {
+            match generate_from_str(proto, Customize::default()) {
+                Err(CodegenError::GenFailed(e)) => assert_eq!(
+                    "proto3 enum `E` must have a value with number 0",
+                    e.to_string()
+                ),
+                r => panic!("expecting codegen error: {:?}", r.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn track_caller_accessors() {
+        let proto =
+            "message M { optional M m = 1; repeated int32 r = 2; oneof o { string s = 3; } }";
+        let rs = generate_from_str(proto, Customize::default()).unwrap();
+        assert!(!rs.contains("#[track_caller]"));
+
+        let customize = Customize {
+            generate_accessors: Some(true),
+            track_caller_accessors: Some(true),
+            ..Default::default()
+        };
+        let rs = generate_from_str(proto, customize).unwrap();
+        assert!(rs.contains("    #[track_caller]\n    pub fn mut_m(&mut self)"));
+        assert!(rs.contains("    #[track_caller]\n    pub fn mut_s(&mut self)"));
+        assert!(rs.contains("    #[track_caller]\n    pub fn take_s(&mut self)"));
+        assert!(!rs.contains("    #[track_caller]\n    pub fn mut_r(&mut self)"));
+        assert!(!rs.contains("    #[track_caller]\n    pub fn take_m(&mut self)"));
+    }
+
+    fn assert_gen_failed(proto: &str, customize: Customize, expected: &str) {
+        match generate_from_str(proto, customize) {
+            Err(CodegenError::GenFailed(e)) => assert_eq!(expected, e.to_string()),
+            r => panic!("expecting codegen error: {:?}", r.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn impl_hash_errors() {
+        let customize = Customize {
+            impl_hash: Some(true),
+            ..Default::default()
+        };
+        assert_gen_failed(
+            "message M { map<string, int32> m = 1; }",
+            customize.clone(),
+            "`impl_hash` cannot be used for message `M`: field `m`: \
+            map fields are stored in `HashMap` which does not implement `Hash`",
+        );
+        assert_gen_failed(
+            "message M { oneof o { float f = 1; } }",
+            customize.clone(),
+            "`impl_hash` cannot be used for message `M`: field `f`: \
+            `f32` and `f64` do not implement `Hash`",
+        );
+        assert_gen_failed(
+            r#"
+                import "google/protobuf/timestamp.proto";
+                message M { repeated google.protobuf.Timestamp t = 1; }
+            "#,
+            customize.clone(),
+            "`impl_hash` cannot be used for message `M`: field `t`: \
+            message `google.protobuf.Timestamp` does not implement `Hash`",
+        );
+        assert_gen_failed(
+            r#"
+                import "rustproto.proto";
+                message M { optional N n = 1; }
+                message N { option (rustproto.impl_hash) = false; }
+            "#,
+            customize.clone(),
+            "`impl_hash` cannot be used for message `M`: field `n`: \
+            message `N` does not implement `Hash`",
+        );
+        let rs = generate_from_str("message M { optional M m = 1; }", customize).unwrap();
+        assert!(rs.contains("#[derive(PartialEq,Clone,Default,Hash)]"));
+    }
+
+    #[test]
+    fn impl_eq_errors() {
+        let customize = Customize {
+            impl_eq: Some(true),
+            ..Default::default()
+        };
+        assert_gen_failed(
+            "message M { optional double d = 1; }",
+            customize.clone(),
+            "`impl_eq` requires `nan_eq` for message `M` with floating point field `d`",
+        );
+        assert_gen_failed(
+            r#"
+                import "google/protobuf/duration.proto";
+                message M { map<string, google.protobuf.Duration> m = 1; }
+            "#,
+            customize.clone(),
+            "`impl_eq` cannot be used for message `M`: field `m`: \
+            message `google.protobuf.Duration` does not implement `Eq`",
+        );
+        let rs = generate_from_str("message M { map<string, M> m = 1; }", customize).unwrap();
+        assert!(rs.contains("#[derive(PartialEq,Eq,Clone,Default)]"));
+    }
+
+    #[test]
+    fn typestate_builder() {
+        let proto = "message M { required int32 a = 1; required string b = 2; optional int32 c = 3; } message N { optional int32 c = 1; }";
+        let rs = generate_from_str(proto, Customize::default()).unwrap();
+        assert!(!rs.contains("MBuilder"));
+
+        let customize = Customize {
+            typestate_builder: Some(true),
+            ..Default::default()
+        };
+        let rs = generate_from_str(proto, customize).unwrap();
+        assert!(rs.contains("pub struct MBuilder<AState, BState> {"));
+        assert!(rs.contains(
+            "pub fn builder() -> MBuilder<::protobuf::rt::RequiredFieldUnset, ::protobuf::rt::RequiredFieldUnset> {"
+        ));
+        assert!(rs.contains("impl<BState> MBuilder<::protobuf::rt::RequiredFieldUnset, BState> {"));
+        assert!(rs.contains(
+            "pub fn a(mut self, v: i32) -> MBuilder<::protobuf::rt::RequiredFieldSet, BState> {"
+        ));
+        assert!(rs.contains(
+            "impl MBuilder<::protobuf::rt::RequiredFieldSet, ::protobuf::rt::RequiredFieldSet> {"
+        ));
+        assert!(!rs.contains("NBuilder"));
+    }
+
+    #[test]
+    fn repeated_index_impl_two_repeated_fields() {
+        let customize = Customize {
+            repeated_index_impl: Some(true),
+            ..Default::default()
+        };
+        assert_gen_failed(
+            "message M { repeated int32 a = 1; repeated int32 b = 2; }",
+            customize,
+            "message `M` has `repeated_index_impl` option, \
+            but it has 2 repeated fields instead of exactly one",
+        );
+    }
+
+    #[test]
+    fn bytes_wrapper_without_bytes_field() {
+        let customize = Customize {
+            bytes_wrapper: Some(true),
+            ..Default::default()
+        };
+        match generate_from_str("message M { optional string s = 1; }", customize) {
+            Err(CodegenError::GenFailed(e)) => assert_eq!(
+                "message `M` has `bytes_wrapper` option, \
+                but it does not have exactly one singular `bytes` field",
+                e.to_string()
+            ),
+            r => panic!("expecting codegen error: {:?}", r.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn primary_key_field_errors() {
+        let generate = |primary_key_field: &str| {
+            let customize = Customize {
+                primary_key_field: Some(primary_key_field.to_owned()),
+                ..Default::default()
+            };
+            match generate_from_str(
+                "message M { optional string s = 1; optional double d = 2; }",
+                customize,
+            ) {
+                Err(CodegenError::GenFailed(e)) => e.to_string(),
+                r => panic!("expecting codegen error: {:?}", r.map(|_| ())),
+            }
+        };
+        assert_eq!(
+            "primary key field `x` is not found in message `M`",
+            generate("x")
+        );
+        assert_eq!(
+            "primary key field `d` must be singular string, integer or bool field",
+            generate("d")
+        );
+    }
+
+    #[test]
+    fn map_key_field_errors() {
+        let generate = |field: &str| {
+            let proto = format!(
+                "import \"rustproto.proto\";
+                enum E {{ A = 0; }}
+                message Item {{
+                    optional string s = 1;
+                    optional double d = 2;
+                    optional bytes b = 3;
+                    optional E e = 4;
+                }}
+                message M {{ {} }}",
+                field
+            );
+            match generate_from_str(&proto, Customize::default()) {
+                Err(CodegenError::GenFailed(e)) => e.to_string(),
+                r => panic!("expecting codegen error: {:?}", r.map(|_| ())),
+            }
+        };
+        assert_eq!(
+            "field `i` has `map_key_field` option, but it is not a repeated message field",
+            generate("repeated int32 i = 1 [(rustproto.map_key_field) = \"s\"];")
+        );
+        assert_eq!(
+            "field `i` has `map_key_field` option, but it is not a repeated message field",
+            generate("optional Item i = 1 [(rustproto.map_key_field) = \"s\"];")
+        );
+        assert_eq!(
+            "map key field `x` of field `i` is not found in message `Item`",
+            generate("repeated Item i = 1 [(rustproto.map_key_field) = \"x\"];")
+        );
+        for key in ["d", "b", "e"] {
+            assert_eq!(
+                format!(
+                    "map key field `{}` must be singular string, integer or bool field",
+                    key
+                ),
+                generate(&format!(
+                    "repeated Item i = 1 [(rustproto.map_key_field) = \"{}\"];",
+                    key
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn display_format_errors() {
+        let generate = |display_format: &str| {
+            let customize = Customize {
+                display_format: Some(display_format.to_owned()),
+                ..Default::default()
+            };
+            match generate_from_str(
+                "message M { optional string s = 1; repeated int32 r = 2; }",
+                customize,
+            ) {
+                Err(CodegenError::GenFailed(e)) => e.to_string(),
+                r => panic!("expecting codegen error: {:?}", r.map(|_| ())),
+            }
+        };
+        assert_eq!(
+            "display format of message `M` references unknown field `x`",
+            generate("{s} {x}")
+        );
+        assert_eq!(
+            "display format of message `M` references repeated field `r`",
+            generate("{r}")
+        );
+    }
+
+    #[test]
+    fn incorrect_repeated_type() {
+        let customize = Customize {
+            repeated_type: Some("LinkedList".to_owned()),
+            ..Default::default()
+        };
+        match generate_from_str("message M { repeated int32 r = 1; }", customize) {
+            Err(CodegenError::GenFailed(e)) => assert_eq!(
+                "incorrect repeated type of field `r`: unknown repeated type `LinkedList`, \
+                expecting `Vec` or `smallvec::SmallVec<[_; N]>`",
+                e.to_string()
+            ),
+            r => panic!("expecting codegen error: {:?}", r.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn message_options_are_not_inherited_by_nested_messages() {
+        let rs = generate_from_str(
+            r#"
+                import "rustproto.proto";
+                message Blob {
+                    option (rustproto.bytes_wrapper) = true;
+                    optional bytes data = 1;
+                    message Nested { optional int32 n = 1; }
+                }
+                message Points {
+                    option (rustproto.repeated_index_impl) = true;
+                    repeated int32 points = 1;
+                    message Nested { repeated int32 a = 1; repeated int32 b = 2; }
+                }
+                message User {
+                    option (rustproto.primary_key_field) = "login";
+                    option (rustproto.display_format) = "{login}";
+                    optional string login = 1;
+                    message Nested { optional int32 n = 1; }
+                }
+            "#,
+            Customize::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            1,
+            rs.matches("impl ::std::convert::AsRef<[u8]> for").count()
+        );
+        assert_eq!(1, rs.matches("impl ::std::ops::Index<usize> for").count());
+        assert_eq!(1, rs.matches("impl ::protobuf::Entity for").count());
+        assert_eq!(1, rs.matches("impl ::std::fmt::Display for").count());
+    }
+
+    #[test]
+    fn enum_lookup_threshold() {
+        let proto = "enum Big { A = 0; C = 7; B = -3; } enum Small { X = 0; Y = 1; }";
+        let rs = generate_from_str(proto, Customize::default()).unwrap();
+        assert!(!rs.contains("values_by_number"));
+
+        let customize = Customize::default().enum_lookup_threshold(2);
+        let rs = generate_from_str(proto, customize).unwrap();
+        assert!(rs.contains(
+            "static values_by_number: &'static [(i32, Big)] = &[\n            (-3, Big::B),\n            (0, Big::A),\n            (7, Big::C),\n        ];"
+        ));
+        assert!(rs.contains("            1 => ::std::option::Option::Some(Small::Y),"));
+    }
+
+    #[test]
+    fn nested_type_strategy_flatten_camel() {
+        let proto = "message Outer { message Inner { message Leaf {} enum E { A = 0; } } optional Inner.Leaf leaf = 1; oneof o { int32 x = 2; } }";
+        let rs = generate_from_str(proto, Customize::default()).unwrap();
+        assert!(rs.contains("pub mod outer {"));
+
+        let customize = Customize::default().nested_type_strategy(NestedTypeStrategy::FlattenCamel);
+        let rs = generate_from_str(proto, customize).unwrap();
+        assert!(!rs.contains("pub mod "));
+        assert!(rs.contains("pub struct OuterInnerLeaf {"));
+        assert!(rs.contains("pub enum OuterInnerE {"));
+        assert!(rs.contains("pub enum OuterO {"));
+        assert!(rs.contains("pub leaf: ::protobuf::MessageField<OuterInnerLeaf>,"));
+    }
+
+    #[test]
+    fn nested_type_strategy_name_collision() {
+        assert_gen_failed(
+            "message Outer { message Inner {} } message OuterInner {}",
+            Customize::default().nested_type_strategy(NestedTypeStrategy::FlattenCamel),
+            "file `embedded.proto`: `OuterInner` and `Outer.Inner` \
+            have the same flattened Rust name `OuterInner`",
+        );
+        assert_gen_failed(
+            "message Outer { oneof inner { int32 x = 1; } } enum Outer_Inner { A = 0; }",
+            Customize::default().nested_type_strategy(NestedTypeStrategy::FlattenUnderscore),
+            "file `embedded.proto`: `Outer.inner` and `Outer_Inner` \
+            have the same flattened Rust name `Outer_Inner`",
+        );
+        let proto = "message Outer { message Inner {} } message OuterInner {}";
+        let customize =
+            Customize::default().nested_type_strategy(NestedTypeStrategy::FlattenUnderscore);
+        assert!(generate_from_str(proto, customize).is_ok());
+    }
+
+    #[test]
+    fn unknown_strategy_option_values() {
+        assert_gen_failed(
+            r#"
+                import "rustproto.proto";
+                option (rustproto.nested_type_strategy_all) = "flat";
+                message M {}
+            "#,
+            Customize::default(),
+            "unknown nested_type_strategy: `flat`, \
+            expecting `modules`, `flatten_underscore` or `flatten_camel`",
+        );
+        assert_gen_failed(
+            r#"
+                import "rustproto.proto";
+                message M { option (rustproto.scalar_getter_style) = "copy"; }
+            "#,
+            Customize::default(),
+            "unknown scalar_getter_style: `copy`, expecting `by_value` or `by_ref`",
+        );
+    }
+
+    #[test]
+    fn enum_value_and_oneof_variant_documentation() {
+        let rs = generate_with_comments(
+            "enum E { A = 0; B = 1; } message M { oneof o { int32 x = 1; string y = 2; } }",
+            &[
+                (&[5, 0, 2, 1], "Value B.\n"),
+                (&[4, 0, 2, 1], "Variant y.\n"),
+            ],
+        );
+        assert!(rs.contains("    A = 0,\n    /// Value B.\n    B = 1,\n"));
+        assert!(rs.contains(
+            "        x(i32),\n        /// Variant y.\n        y(::std::string::String),\n"
+        ));
+    }
+
+    #[test]
+    fn enum_option_value_must_be_ident() {
+        let proto = r#"
+            import "google/protobuf/descriptor.proto";
+            enum Level { LOW = 1; HIGH = 2; }
+            extend google.protobuf.MessageOptions { optional Level level = 51001; }
+            message M { option (level) = 2; }
+        "#;
+        match generate_from_str(proto, Customize::default()) {
+            Err(CodegenError::ParseFailed(e)) => assert!(
+                format!("{:?}", e).contains(
+                    "value of enum-typed option (level) must be an enum value name, got 2"
+                ),
+                "{:?}",
+                e
+            ),
+            r => panic!("expecting parse error: {:?}", r.map(|_| ())),
+        }
+        let rs = generate_from_str(
+            &proto.replace("option (level) = 2;", "option (level) = HIGH;"),
+            Customize::default(),
+        );
+        assert!(rs.is_ok());
+    }
+
+    #[test]
+    fn syntax_error() {
+        let r = generate_from_str("message {", Customize::default());
+        assert!(matches!(r, Err(CodegenError::ParseFailed(_))));
+    }
+}
@@ -5,9 +5,15 @@ mod compiler_plugin;
 mod customize;
 mod gen;
 pub mod gen_and_write;
+mod gen_from_str;
 pub mod protoc_gen_rust;
 
 pub use codegen::Codegen;
+pub use codegen::CodegenError;
 pub use customize::Customize;
+pub use customize::ImplementTrait;
+pub use customize::NestedTypeStrategy;
+pub use customize::ScalarGetterStyle;
 #[doc(hidden)]
 pub use gen::paths::proto_name_to_rs;
+pub use gen_from_str::generate_from_str;
@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::str;
 
 use protobuf::text_format::lexer::int;
@@ -51,8 +53,8 @@ pub(crate) enum ParserError {
     NotUtf8,
     #[error("expecting a constant")]
     ExpectConstant,
-    #[error("unknown syntax")]
-    UnknownSyntax,
+    #[error("unknown syntax `{0}`, expecting `proto2` or `proto3`")]
+    UnknownSyntax(String),
     #[error("integer overflow")]
     IntegerOverflow,
     #[error("label not allowed")]
@@ -73,6 +75,9 @@ pub(crate) enum ParserError {
     OneOfInOneOf,
     #[error("oneof in extend")]
     OneOfInExtend,
+    // Never returned to user: reported errors are collected by the recovering parser
+    #[error("parsing stopped on unrecoverable error")]
+    StopRecovery,
 }
 
 impl From<TokenizerError> for ParserError {
@@ -180,6 +185,9 @@ impl ToU8 for u32 {
 pub(crate) struct Parser<'a> {
     pub tokenizer: Tokenizer<'a>,
     syntax: Syntax,
+    /// When set, statements which cannot be parsed are skipped
+    /// and errors are collected here instead of being returned.
+    recovered_errors: Option<Rc<RefCell<Vec<ParserErrorWithLocation>>>>,
 }
 
 #[derive(Copy, Clone)]
@@ -282,6 +290,102 @@ impl<'a> Parser<'a> {
         Parser {
             tokenizer: Tokenizer::new(input, ParserLanguage::Proto),
             syntax: Syntax::Proto2,
+            recovered_errors: None,
+        }
+    }
+
+    /// Parser which skips statements it cannot parse, collecting the errors.
+    pub fn new_recovering(input: &'a str) -> Parser<'a> {
+        Parser {
+            recovered_errors: Some(Rc::new(RefCell::new(Vec::new()))),
+            ..Parser::new(input)
+        }
+    }
+
+    /// Errors collected by recovering parser.
+    pub fn take_recovered_errors(&mut self) -> Vec<ParserErrorWithLocation> {
+        match &self.recovered_errors {
+            Some(errors) => errors.replace(Vec::new()),
+            None => Vec::new(),
+        }
+    }
+
+    fn error_with_location(&self, error: anyhow::Error) -> ParserErrorWithLocation {
+        let loc = self.tokenizer.loc();
+        ParserErrorWithLocation {
+            error,
+            line: loc.line,
+            col: loc.col,
+        }
+    }
+
+    // Error recovery
+
+    /// Skip tokens up to and including the end of current statement:
+    /// `;` or a balanced `{ ... }` block, or up to `}` closing the enclosing block.
+    fn skip_statement(&mut self) -> anyhow::Result<()> {
+        let mut depth = 0;
+        let mut first = true;
+        loop {
+            if self.tokenizer.syntax_eof()? {
+                return Err(TokenizerError::UnexpectedEof.into());
+            }
+            let symbol = self.tokenizer.lookahead_if_symbol()?;
+            match symbol {
+                Some('}') if depth == 0 && !first => return Ok(()),
+                Some('}') if depth > 0 => depth -= 1,
+                Some('{') => depth += 1,
+                _ => {}
+            }
+            self.tokenizer.advance()?;
+            first = false;
+            if depth == 0 && (symbol == Some(';') || symbol == Some('}')) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Parse a statement with `parse`.
+    ///
+    /// If the parser is recovering, an error is recorded, and the statement is skipped.
+    /// If the statement cannot be skipped (because of lexer error or end of file),
+    /// [`ParserError::StopRecovery`] is returned.
+    fn next_statement_recovering<F>(&mut self, parse: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&mut Parser<'a>) -> anyhow::Result<()>,
+    {
+        let errors = match &self.recovered_errors {
+            Some(errors) => errors.clone(),
+            None => return parse(self),
+        };
+
+        let start = self.tokenizer.clone();
+        let error = match parse(self) {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+        if let Some(ParserError::StopRecovery) = error.downcast_ref() {
+            return Err(error);
+        }
+        let error = self.error_with_location(error);
+
+        self.tokenizer = start;
+        let skipped = self.skip_statement();
+        let skip_error = skipped.err().map(|e| self.error_with_location(e));
+
+        let mut errors = errors.borrow_mut();
+        let error_loc = (error.line, error.col);
+        errors.push(error);
+        match skip_error {
+            None => Ok(()),
+            Some(skip_error) => {
+                // Lexer error or end of file in the failed statement
+                // is found again when skipping
+                if (skip_error.line, skip_error.col) != error_loc {
+                    errors.push(skip_error);
+                }
+                Err(ParserError::StopRecovery.into())
+            }
         }
     }
 
@@ -445,7 +549,7 @@ impl<'a> Parser<'a> {
             } else if syntax_str == "proto3" {
                 Syntax::Proto3
             } else {
-                return Err(ParserError::UnknownSyntax.into());
+                return Err(ParserError::UnknownSyntax(syntax_str).into());
             };
             self.tokenizer.next_symbol_expect_eq(';')?;
             Ok(Some(syntax))
@@ -858,17 +962,20 @@ impl<'a> Parser<'a> {
 
             self.tokenizer.next_symbol_expect_eq('{')?;
             while self.tokenizer.lookahead_if_symbol()? != Some('}') {
-                // emptyStatement
-                if self.tokenizer.next_symbol_if_eq(';')? {
-                    continue;
-                }
+                self.next_statement_recovering(|p| {
+                    // emptyStatement
+                    if p.tokenizer.next_symbol_if_eq(';')? {
+                        return Ok(());
+                    }
 
-                if let Some(o) = self.next_option_opt()? {
-                    options.push(o);
-                    continue;
-                }
+                    if let Some(o) = p.next_option_opt()? {
+                        options.push(o);
+                        return Ok(());
+                    }
 
-                values.push(self.next_enum_field()?);
+                    values.push(p.next_enum_field()?);
+                    Ok(())
+                })?;
             }
             self.tokenizer.next_symbol_expect_eq('}')?;
             Ok(Some(Enumeration {
@@ -891,65 +998,68 @@ impl<'a> Parser<'a> {
         let mut r = MessageBody::default();
 
         while self.tokenizer.lookahead_if_symbol()? != Some('}') {
-            let loc = self.tokenizer.lookahead_loc();
+            self.next_statement_recovering(|p| {
+                let loc = p.tokenizer.lookahead_loc();
 
-            // emptyStatement
-            if self.tokenizer.next_symbol_if_eq(';')? {
-                continue;
-            }
-
-            if mode.is_most_non_fields_allowed() {
-                if let Some((field_nums, field_names)) = self.next_reserved_opt()? {
-                    r.reserved_nums.extend(field_nums);
-                    r.reserved_names.extend(field_names);
-                    continue;
+                // emptyStatement
+                if p.tokenizer.next_symbol_if_eq(';')? {
+                    return Ok(());
                 }
 
-                if let Some(oneof) = self.next_oneof_opt()? {
-                    let one_of = FieldOrOneOf::OneOf(oneof);
-                    r.fields.push(WithLoc { t: one_of, loc });
-                    continue;
-                }
+                if mode.is_most_non_fields_allowed() {
+                    if let Some((field_nums, field_names)) = p.next_reserved_opt()? {
+                        r.reserved_nums.extend(field_nums);
+                        r.reserved_names.extend(field_names);
+                        return Ok(());
+                    }
 
-                if let Some(extension_ranges) = self.next_extensions_opt()? {
-                    r.extension_ranges.extend(extension_ranges);
-                    continue;
-                }
+                    if let Some(oneof) = p.next_oneof_opt()? {
+                        let one_of = FieldOrOneOf::OneOf(oneof);
+                        r.fields.push(WithLoc { t: one_of, loc });
+                        return Ok(());
+                    }
 
-                if let Some(extensions) = self.next_extend_opt()? {
-                    r.extensions.extend(extensions);
-                    continue;
-                }
+                    if let Some(extension_ranges) = p.next_extensions_opt()? {
+                        r.extension_ranges.extend(extension_ranges);
+                        return Ok(());
+                    }
 
-                if let Some(nested_message) = self.next_message_opt()? {
-                    r.messages.push(nested_message);
-                    continue;
-                }
+                    if let Some(extensions) = p.next_extend_opt()? {
+                        r.extensions.extend(extensions);
+                        return Ok(());
+                    }
 
-                if let Some(nested_enum) = self.next_enum_opt()? {
-                    r.enums.push(nested_enum);
-                    continue;
+                    if let Some(nested_message) = p.next_message_opt()? {
+                        r.messages.push(nested_message);
+                        return Ok(());
+                    }
+
+                    if let Some(nested_enum) = p.next_enum_opt()? {
+                        r.enums.push(nested_enum);
+                        return Ok(());
+                    }
+                } else {
+                    p.tokenizer.next_ident_if_eq_error("reserved")?;
+                    p.tokenizer.next_ident_if_eq_error("oneof")?;
+                    p.tokenizer.next_ident_if_eq_error("extensions")?;
+                    p.tokenizer.next_ident_if_eq_error("extend")?;
+                    p.tokenizer.next_ident_if_eq_error("message")?;
+                    p.tokenizer.next_ident_if_eq_error("enum")?;
                 }
-            } else {
-                self.tokenizer.next_ident_if_eq_error("reserved")?;
-                self.tokenizer.next_ident_if_eq_error("oneof")?;
-                self.tokenizer.next_ident_if_eq_error("extensions")?;
-                self.tokenizer.next_ident_if_eq_error("extend")?;
-                self.tokenizer.next_ident_if_eq_error("message")?;
-                self.tokenizer.next_ident_if_eq_error("enum")?;
-            }
 
-            if mode.is_option_allowed() {
-                if let Some(option) = self.next_option_opt()? {
-                    r.options.push(option);
-                    continue;
+                if mode.is_option_allowed() {
+                    if let Some(option) = p.next_option_opt()? {
+                        r.options.push(option);
+                        return Ok(());
+                    }
+                } else {
+                    p.tokenizer.next_ident_if_eq_error("option")?;
                 }
-            } else {
-                self.tokenizer.next_ident_if_eq_error("option")?;
-            }
 
-            let field = FieldOrOneOf::Field(self.next_field(mode)?);
-            r.fields.push(WithLoc { t: field, loc });
+                let field = FieldOrOneOf::Field(p.next_field(mode)?);
+                r.fields.push(WithLoc { t: field, loc });
+                Ok(())
+            })?;
         }
 
         self.tokenizer.next_symbol_expect_eq('}')?;
@@ -1177,70 +1287,83 @@ impl<'a> Parser<'a> {
     // proto = syntax { import | package | option | topLevelDef | emptyStatement }
     // topLevelDef = message | enum | extend | service
     pub fn next_proto(&mut self) -> anyhow::Result<FileDescriptor> {
-        let syntax = self.next_syntax()?.unwrap_or(Syntax::Proto2);
-        self.syntax = syntax;
-
-        let mut imports = Vec::new();
-        let mut package = ProtobufAbsolutePath::root();
-        let mut messages = Vec::new();
-        let mut enums = Vec::new();
-        let mut extensions = Vec::new();
-        let mut options = Vec::new();
-        let mut services = Vec::new();
+        let mut r = FileDescriptor::default();
+        self.next_proto_into(&mut r)?;
+        Ok(r)
+    }
 
-        while !self.tokenizer.syntax_eof()? {
-            if let Some(import) = self.next_import_opt()? {
-                imports.push(import);
-                continue;
+    /// Parse with recovering parser until the end of file
+    /// or unrecoverable error, and return what is parsed.
+    pub fn next_proto_recovering(&mut self) -> FileDescriptor {
+        let mut r = FileDescriptor::default();
+        if let Err(e) = self.next_proto_into(&mut r) {
+            match e.downcast_ref() {
+                Some(ParserError::StopRecovery) => {}
+                _ => {
+                    let error = self.error_with_location(e);
+                    if let Some(errors) = &self.recovered_errors {
+                        errors.borrow_mut().push(error);
+                    }
+                }
             }
+        }
+        r
+    }
 
-            if let Some(next_package) = self.next_package_opt()? {
-                package = next_package;
-                continue;
-            }
+    fn next_proto_into(&mut self, r: &mut FileDescriptor) -> anyhow::Result<()> {
+        self.next_statement_recovering(|p| {
+            p.syntax = p.next_syntax()?.unwrap_or(Syntax::Proto2);
+            Ok(())
+        })?;
+        r.syntax = self.syntax;
 
-            if let Some(option) = self.next_option_opt()? {
-                options.push(option);
-                continue;
-            }
+        while !self.tokenizer.syntax_eof()? {
+            self.next_statement_recovering(|p| p.next_top_level_statement(r))?;
+        }
+        Ok(())
+    }
 
-            if let Some(message) = self.next_message_opt()? {
-                messages.push(message);
-                continue;
-            }
+    fn next_top_level_statement(&mut self, r: &mut FileDescriptor) -> anyhow::Result<()> {
+        if let Some(import) = self.next_import_opt()? {
+            r.imports.push(import);
+            return Ok(());
+        }
 
-            if let Some(enumeration) = self.next_enum_opt()? {
-                enums.push(enumeration);
-                continue;
-            }
+        if let Some(next_package) = self.next_package_opt()? {
+            r.package = next_package;
+            return Ok(());
+        }
 
-            if let Some(more_extensions) = self.next_extend_opt()? {
-                extensions.extend(more_extensions);
-                continue;
-            }
+        if let Some(option) = self.next_option_opt()? {
+            r.options.push(option);
+            return Ok(());
+        }
 
-            if let Some(service) = self.next_service_opt()? {
-                services.push(service);
-                continue;
-            }
+        if let Some(message) = self.next_message_opt()? {
+            r.messages.push(message);
+            return Ok(());
+        }
 
-            if self.tokenizer.next_symbol_if_eq(';')? {
-                continue;
-            }
+        if let Some(enumeration) = self.next_enum_opt()? {
+            r.enums.push(enumeration);
+            return Ok(());
+        }
 
-            return Err(ParserError::IncorrectInput.into());
+        if let Some(more_extensions) = self.next_extend_opt()? {
+            r.extensions.extend(more_extensions);
+            return Ok(());
         }
 
-        Ok(FileDescriptor {
-            imports,
-            package,
-            syntax,
-            messages,
-            enums,
-            extensions,
-            services,
-            options,
-        })
+        if let Some(service) = self.next_service_opt()? {
+            r.services.push(service);
+            return Ok(());
+        }
+
+        if self.tokenizer.next_symbol_if_eq(';')? {
+            return Ok(());
+        }
+
+        Err(ParserError::IncorrectInput.into())
     }
 }
 
@@ -1285,6 +1408,36 @@ mod test {
         assert_eq!(Syntax::Proto3, mess);
     }
 
+    #[test]
+    fn test_no_syntax_is_proto2() {
+        let msg = r#"
+            message Foo {
+                required int32 a = 1;
+                optional string b = 2;
+            }
+        "#;
+        let file = parse(msg, |p| p.next_proto());
+        assert_eq!(Syntax::Proto2, file.syntax);
+        match &file.messages[0].t.fields[0].t {
+            FieldOrOneOf::Field(f) => assert_eq!(Rule::Required, f.t.rule),
+            FieldOrOneOf::OneOf(..) => panic!("expecting field"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_syntax() {
+        let msg = r#"
+            syntax = "proto4";
+            message Foo {}
+        "#;
+        let err = FileDescriptor::parse(msg).err().expect("err");
+        assert_eq!(2, err.line);
+        assert_eq!(
+            "unknown syntax `proto4`, expecting `proto2` or `proto3`",
+            err.error.to_string()
+        );
+    }
+
     #[test]
     fn test_field_default_value_int() {
         let msg = r#"  optional int64 f = 4 [default = 12];  "#;
@@ -1521,6 +1674,43 @@ mod test {
         assert_eq!("bbb", mess.t.regular_fields_for_test()[2].name);
     }
 
+    #[test]
+    fn test_comments_in_field_options() {
+        let msg = r#"message Sample {
+            optional string x = 1 [/* "c1" */default/*c2*/=/* ' */"a//b"/**/,// ]
+              deprecated /* ; */ = true];
+        }"#;
+
+        let mess = parse_opt(msg, |p| p.next_message_opt());
+        let options = &mess.t.regular_fields_for_test()[0].options;
+        assert_eq!(2, options.len());
+        assert_eq!(ProtobufOptionName::simple("default"), options[0].name);
+        assert_eq!(r#""a//b""#, options[0].value.format());
+        assert_eq!(ProtobufOptionName::simple("deprecated"), options[1].name);
+        assert_eq!("true", options[1].value.format());
+    }
+
+    #[test]
+    fn test_comments_in_message_constant() {
+        let msg = r#"option (my_opt) = {
+            /* "a": 1 */ b: 2 // c: 3
+            d/**/: "/* not a comment */"
+        };"#;
+
+        let option = parse_opt(msg, |p| p.next_option_opt());
+        match option.value {
+            ProtobufConstant::Message(m) => {
+                assert_eq!(vec!["b", "d"], m.fields.keys().collect::<Vec<_>>());
+                assert_eq!("2", m.fields.get("b").unwrap().format());
+                assert_eq!(
+                    r#""/* not a comment */""#,
+                    m.fields.get("d").unwrap().format()
+                );
+            }
+            v => panic!("expecting message constant, got {:?}", v),
+        }
+    }
+
     #[test]
     fn test_incorrect_file_descriptor() {
         let msg = r#"
@@ -1532,4 +1722,65 @@ mod test {
         let err = FileDescriptor::parse(msg).err().expect("err");
         assert_eq!(4, err.line);
     }
+
+    #[test]
+    fn test_recover_statements() {
+        let msg = r#"
+            syntax = "proto3";
+            message Foo {
+                int32 a = ;
+                int32 b = 2;
+                message Nested { string = 1; }
+                enum E { X = ; Y = 1; }
+            }
+            garbage here;
+            message Bar { option x = 1 }
+        "#;
+
+        let (file, errors) = FileDescriptor::parse_collect_errors(msg);
+        let lines: Vec<u32> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(vec![4, 6, 7, 9, 10], lines, "{:?}", errors);
+
+        assert_eq!(Syntax::Proto3, file.syntax);
+        assert_eq!(2, file.messages.len());
+        let foo = &file.messages[0].t;
+        assert_eq!(1, foo.fields.len());
+        assert_eq!(1, foo.messages.len());
+        assert_eq!(1, foo.enums[0].values.len());
+    }
+
+    #[test]
+    fn test_recover_stops_at_lexer_error() {
+        let msg = r#"
+            syntax = "proto3";
+            message Foo { int32 = 1; }
+            message Bar { string s = "unterminated; }
+            message Baz {}
+        "#;
+
+        let (file, errors) = FileDescriptor::parse_collect_errors(msg);
+        let lines: Vec<u32> = errors.iter().map(|e| e.line).collect();
+        // Lexer error is reported where lexer stopped
+        assert_eq!(vec![3, 5], lines, "{:?}", errors);
+        assert_eq!(1, file.messages.len());
+    }
+
+    #[test]
+    fn test_recover_unexpected_eof() {
+        let (file, errors) =
+            FileDescriptor::parse_collect_errors("message Foo { optional int32 a = 1;");
+        assert_eq!(1, errors.len(), "{:?}", errors);
+        assert!(file.messages.is_empty());
+
+        let (_, errors) = FileDescriptor::parse_collect_errors("}");
+        assert_eq!(1, errors.len(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_no_errors_collected() {
+        let (file, errors) =
+            FileDescriptor::parse_collect_errors("message Foo { optional int32 a = 1; }");
+        assert!(errors.is_empty());
+        assert_eq!(1, file.messages[0].t.fields.len());
+    }
 }
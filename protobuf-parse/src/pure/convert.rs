@@ -10,6 +10,7 @@ use protobuf::descriptor::DescriptorProto;
 use protobuf::descriptor::FieldDescriptorProto;
 use protobuf::json::json_name;
 use protobuf::reflect::RuntimeTypeBox;
+use protobuf::text_format::lexer::Loc;
 use protobuf::text_format::lexer::StrLitDecodeError;
 use protobuf::text_format::quote_bytes_to;
 use protobuf::Message;
@@ -67,6 +68,8 @@ pub(crate) enum ConvertError {
     ExpectingEnum(ProtobufAbsolutePath),
     #[error("unknown enum value: {0}")]
     UnknownEnumValue(String),
+    #[error("value of enum-typed option {0} must be an enum value name, got {1}")]
+    EnumOptionValueMustBeIdent(String, model::ProtobufConstant),
     #[error("unknown field name: {0}")]
     UnknownFieldName(String),
 }
@@ -609,6 +612,13 @@ impl<'a> Resolver<'a> {
                 .full_name
                 .to_string(),
         );
+        // Like protoc, only set flags when `stream` keyword is present
+        if input.client_streaming {
+            output.set_client_streaming(true);
+        }
+        if input.server_streaming {
+            output.set_server_streaming(true);
+        }
         Ok(output)
     }
 
@@ -1304,7 +1314,11 @@ impl<'a> Resolver<'a> {
         };
 
         Err(match field_type {
-            TypeResolved::Message(..) | TypeResolved::Enum(..) | TypeResolved::Group(..) => {
+            TypeResolved::Enum(..) => ConvertError::EnumOptionValueMustBeIdent(
+                option_name_for_diag.to_owned(),
+                value.clone(),
+            ),
+            TypeResolved::Message(..) | TypeResolved::Group(..) => {
                 ConvertError::ConstantsOfTypeMessageEnumGroupNotImplemented
             }
             _ => ConvertError::UnsupportedExtensionType(
@@ -1400,6 +1414,24 @@ pub(crate) fn file_descriptor(
     input: &model::FileDescriptor,
     deps: &[FileDescriptorPair],
 ) -> ConvertResult<protobuf::descriptor::FileDescriptorProto> {
+    let mut errors = Vec::new();
+    let output = file_descriptor_collect_errors(name, input, deps, &mut errors);
+    match errors.into_iter().next() {
+        Some((_, e)) => Err(e),
+        None => Ok(output),
+    }
+}
+
+/// Convert a file, continuing after errors.
+///
+/// Definitions which fail to convert are omitted from the output,
+/// and errors are added to `errors` with the location of the definition, if known.
+pub(crate) fn file_descriptor_collect_errors(
+    name: &ProtoPath,
+    input: &model::FileDescriptor,
+    deps: &[FileDescriptorPair],
+    errors: &mut Vec<(Option<Loc>, ConvertError)>,
+) -> protobuf::descriptor::FileDescriptorProto {
     let resolver = Resolver {
         current_file: &input,
         deps,
@@ -1420,26 +1452,34 @@ pub(crate) fn file_descriptor(
 
     let mut extensions = Vec::new();
     for e in &input.extensions {
-        let (ext, group_messages) = resolver.extension(&resolver.current_file.package, &e.t)?;
-        extensions.push(ext);
-        messages.extend(group_messages.map(model::WithLoc::with_loc(e.loc)));
+        match resolver.extension(&resolver.current_file.package, &e.t) {
+            Ok((ext, group_messages)) => {
+                extensions.push(ext);
+                messages.extend(group_messages.map(model::WithLoc::with_loc(e.loc)));
+            }
+            Err(error) => errors.push((Some(e.loc), error)),
+        }
     }
     output.extension = extensions;
 
     for m in &input.messages {
-        let message = resolver.message(&resolver.current_file.package, &m.t)?;
-        messages.push(model::WithLoc {
-            t: message,
-            loc: m.loc,
-        });
+        match resolver.message(&resolver.current_file.package, &m.t) {
+            Ok(message) => messages.push(model::WithLoc {
+                t: message,
+                loc: m.loc,
+            }),
+            Err(error) => errors.push((Some(m.loc), error)),
+        }
     }
 
     for s in &input.services {
-        let service = resolver.service(&s.t)?;
-        services.push(model::WithLoc {
-            t: service,
-            loc: s.loc,
-        })
+        match resolver.service(&s.t) {
+            Ok(service) => services.push(model::WithLoc {
+                t: service,
+                loc: s.loc,
+            }),
+            Err(error) => errors.push((Some(s.loc), error)),
+        }
     }
 
     // Preserve declaration order
@@ -1449,19 +1489,22 @@ pub(crate) fn file_descriptor(
         .map(|model::WithLoc { t, .. }| t)
         .collect();
 
-    output.enum_type = input
-        .enums
-        .iter()
-        .map(|e| resolver.enumeration(&resolver.current_file.package, e))
-        .collect::<Result<_, _>>()?;
+    for e in &input.enums {
+        match resolver.enumeration(&resolver.current_file.package, e) {
+            Ok(e) => output.enum_type.push(e),
+            Err(error) => errors.push((None, error)),
+        }
+    }
 
     output.service = services
         .into_iter()
         .map(|model::WithLoc { t, .. }| t)
         .collect();
 
-    output.options =
-        Some(resolver.file_options(&resolver.current_file.package, &input.options)?).into();
+    match resolver.file_options(&resolver.current_file.package, &input.options) {
+        Ok(options) => output.options = Some(options).into(),
+        Err(error) => errors.push((None, error)),
+    }
 
-    Ok(output)
+    output
 }
@@ -4,6 +4,7 @@ pub(crate) mod convert;
 pub(crate) mod model;
 pub(crate) mod parse_and_typecheck;
 pub(crate) mod parse_dependencies;
+pub(crate) mod tar;
 
 pub use parse_and_typecheck::*;
 pub use parse_dependencies::*;
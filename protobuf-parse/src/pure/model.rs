@@ -325,10 +325,8 @@ pub(crate) struct Method {
     /// Output type
     pub output_type: ProtobufPath,
     /// If this method is client streaming
-    #[allow(dead_code)] // TODO
     pub client_streaming: bool,
     /// If this method is server streaming
-    #[allow(dead_code)] // TODO
     pub server_streaming: bool,
     /// Method options
     pub options: Vec<ProtobufOption>,
@@ -536,4 +534,13 @@ impl FileDescriptor {
             }
         }
     }
+
+    /// Parse a .proto file content skipping statements which cannot be parsed.
+    ///
+    /// Returns what could be parsed and all errors found.
+    pub fn parse_collect_errors<S: AsRef<str>>(file: S) -> (Self, Vec<ParserErrorWithLocation>) {
+        let mut parser = Parser::new_recovering(file.as_ref());
+        let r = parser.next_proto_recovering();
+        (r, parser.take_recovered_errors())
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 use std::fs;
 use std::io;
@@ -14,6 +16,7 @@ use crate::proto_path::ProtoPath;
 use crate::proto_path::ProtoPathBuf;
 use crate::pure::convert;
 use crate::pure::model;
+use crate::pure::tar;
 use crate::FileDescriptorPair;
 
 #[derive(Debug, thiserror::Error)]
@@ -26,6 +29,8 @@ enum ParseAndTypeckError {
     FileMustResideInImportPath(String, String),
     #[error("could not read file `{0}`: {1}")]
     CouldNotReadFile(String, io::Error),
+    #[error("could not read archive `{0}`: {1}")]
+    CouldNotReadArchive(String, tar::TarError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -36,6 +41,33 @@ struct WithFileError {
     error: anyhow::Error,
 }
 
+/// Error found by [`parse_and_typecheck_collect_errors`].
+///
+/// Error is displayed as file and location, the error itself
+/// is available as [`source`](std::error::Error::source).
+#[derive(Debug, thiserror::Error)]
+pub struct ParseError {
+    /// Path of the file containing the error.
+    pub file: String,
+    /// 1-based line and column of the error, if known.
+    ///
+    /// For typecheck errors this is the location of the top-level
+    /// message, extension or service containing the error.
+    pub loc: Option<(u32, u32)>,
+    /// The error.
+    #[source]
+    pub error: anyhow::Error,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.loc {
+            Some((line, col)) => write!(f, "error in `{}` at {}:{}", self.file, line, col),
+            None => write!(f, "error in `{}`", self.file),
+        }
+    }
+}
+
 /// Resolve `.proto` files. `Display` is used for error messages.
 pub trait ProtoPathResolver: fmt::Display {
     /// Resolve a `.proto` file.
@@ -53,6 +85,14 @@ where
     resolver: R,
 }
 
+/// State of [`parse_and_typecheck_collect_errors`].
+#[derive(Default)]
+struct CollectedErrors {
+    /// Files which have errors or import files with errors.
+    failed_files: HashSet<ProtoPathBuf>,
+    errors: Vec<ParseError>,
+}
+
 impl<R> Run<R>
 where
     R: ProtoPathResolver,
@@ -126,9 +166,14 @@ where
             return Ok(());
         }
 
-        let resolved = self.resolver.resolve(protobuf_path)?;
-        if let Some(resolved) = resolved {
-            return self.add_file_content(protobuf_path, &resolved);
+        let resolved = self.resolve(protobuf_path)?;
+        self.add_file_content(protobuf_path, &resolved)
+    }
+
+    /// Find file content with the resolver or among built-in files.
+    fn resolve(&self, protobuf_path: &ProtoPath) -> anyhow::Result<ResolvedProtoFile> {
+        if let Some(resolved) = self.resolver.resolve(protobuf_path)? {
+            return Ok(resolved);
         }
 
         let embedded = match protobuf_path.to_str() {
@@ -148,13 +193,10 @@ where
         };
 
         match embedded {
-            Some(content) => self.add_file_content(
-                protobuf_path,
-                &ResolvedProtoFile {
-                    path: protobuf_path.to_string(),
-                    content: content.as_bytes().to_vec(),
-                },
-            ),
+            Some(content) => Ok(ResolvedProtoFile {
+                path: protobuf_path.to_string(),
+                content: content.as_bytes().to_vec(),
+            }),
             None => Err(ParseAndTypeckError::FileNotFoundInImportPath(
                 protobuf_path.to_string(),
                 format!("{}", self.resolver),
@@ -162,6 +204,117 @@ where
             .into()),
         }
     }
+
+    /// Like `add_file_content`, but errors are collected.
+    ///
+    /// Return `false` if the file or any of its imports has errors.
+    fn add_file_content_collect_errors(
+        &mut self,
+        protobuf_path: &ProtoPath,
+        resolved: &ResolvedProtoFile,
+        collected: &mut CollectedErrors,
+    ) -> bool {
+        let ok = self.add_file_content_collect_errors_impl(protobuf_path, resolved, collected);
+        if !ok {
+            collected
+                .failed_files
+                .insert(protobuf_path.to_proto_path_buf());
+        }
+        ok
+    }
+
+    fn add_file_content_collect_errors_impl(
+        &mut self,
+        protobuf_path: &ProtoPath,
+        resolved: &ResolvedProtoFile,
+        collected: &mut CollectedErrors,
+    ) -> bool {
+        let mut error = |loc, error| {
+            collected.errors.push(ParseError {
+                file: resolved.path.clone(),
+                loc,
+                error,
+            })
+        };
+
+        let content = match str::from_utf8(&resolved.content) {
+            Ok(content) => content,
+            Err(_) => {
+                error(
+                    None,
+                    ParseAndTypeckError::FileContentIsNotUtf8(protobuf_path.to_string()).into(),
+                );
+                return false;
+            }
+        };
+
+        let (parsed, parse_errors) = model::FileDescriptor::parse_collect_errors(content);
+        let mut ok = parse_errors.is_empty();
+        for e in parse_errors {
+            error(Some((e.line, e.col)), e.error);
+        }
+
+        for import in &parsed.imports {
+            if self.parsed_files.get(&import.path).is_some() {
+                continue;
+            }
+            if collected.failed_files.contains(&import.path) {
+                ok = false;
+                continue;
+            }
+            match self.resolve(&import.path) {
+                Ok(import_resolved) => {
+                    ok &= self.add_file_content_collect_errors(
+                        &import.path,
+                        &import_resolved,
+                        collected,
+                    );
+                }
+                Err(e) => {
+                    collected.failed_files.insert(import.path.clone());
+                    collected.errors.push(ParseError {
+                        file: resolved.path.clone(),
+                        loc: None,
+                        error: e,
+                    });
+                    ok = false;
+                }
+            }
+        }
+
+        // Errors in imported files would cause meaningless errors in this file
+        if !ok {
+            return false;
+        }
+
+        let mut this_file_deps = LinkedHashMap::new();
+        self.get_all_deps_already_parsed(&parsed, &mut this_file_deps);
+        let this_file_deps: Vec<_> = this_file_deps.into_iter().map(|(_, v)| v).collect();
+
+        let mut convert_errors = Vec::new();
+        let descriptor = convert::file_descriptor_collect_errors(
+            protobuf_path,
+            &parsed,
+            &this_file_deps,
+            &mut convert_errors,
+        );
+        if !convert_errors.is_empty() {
+            for (loc, e) in convert_errors {
+                collected.errors.push(ParseError {
+                    file: resolved.path.clone(),
+                    loc: loc.map(|loc| (loc.line, loc.col)),
+                    error: e.into(),
+                });
+            }
+            return false;
+        }
+
+        self.parsed_files.insert(
+            protobuf_path.to_proto_path_buf(),
+            FileDescriptorPair { parsed, descriptor },
+        );
+        true
+    }
 }
 
 pub(crate) fn path_to_proto_path(
@@ -194,14 +347,40 @@ pub struct ResolvedProtoFile {
     pub content: Vec<u8>,
 }
 
-fn fs_resolver(includes: &[PathBuf]) -> impl ProtoPathResolver {
+/// Files of an archive passed as include.
+struct IncludeArchive {
+    path: PathBuf,
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl IncludeArchive {
+    fn read(path: &Path) -> anyhow::Result<IncludeArchive> {
+        let content = fs::read(path)
+            .map_err(|e| ParseAndTypeckError::CouldNotReadFile(path.display().to_string(), e))?;
+        let entries = tar::read_tar(&content)
+            .map_err(|e| ParseAndTypeckError::CouldNotReadArchive(path.display().to_string(), e))?;
+        Ok(IncludeArchive {
+            path: path.to_owned(),
+            files: entries.into_iter().map(|e| (e.name, e.content)).collect(),
+        })
+    }
+}
+
+fn fs_resolver(
+    includes: &[PathBuf],
+    archives: &[PathBuf],
+) -> anyhow::Result<impl ProtoPathResolver> {
     struct Impl {
         includes: Vec<PathBuf>,
+        archives: Vec<IncludeArchive>,
     }
 
     impl fmt::Display for Impl {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            write!(f, "{:?}", self.includes)
+            let mut list = f.debug_list();
+            list.entries(&self.includes);
+            list.entries(self.archives.iter().map(|a| &a.path));
+            list.finish()
         }
     }
 
@@ -226,23 +405,49 @@ fn fs_resolver(includes: &[PathBuf]) -> impl ProtoPathResolver {
                     }
                 }
             }
+            for archive in &self.archives {
+                if let Some(content) = archive.files.get(proto_path.to_str()) {
+                    return Ok(Some(ResolvedProtoFile {
+                        path: format!("{}:{}", archive.path.display(), proto_path),
+                        content: content.clone(),
+                    }));
+                }
+            }
             Ok(None)
         }
     }
 
-    Impl {
+    Ok(Impl {
         includes: includes.to_vec(),
-    }
+        archives: archives
+            .iter()
+            .map(|a| IncludeArchive::read(a))
+            .collect::<anyhow::Result<_>>()?,
+    })
 }
 
 /// Parse `.proto` files using pure Rust implementation.
 pub fn parse_and_typecheck(
     includes: &[PathBuf],
     input: &[PathBuf],
+) -> anyhow::Result<ParsedAndTypechecked> {
+    parse_and_typecheck_with_archives(includes, &[], input)
+}
+
+/// Parse `.proto` files using pure Rust implementation,
+/// additionally resolving imports against files in uncompressed tar archives.
+///
+/// Include directories are searched before archives.
+/// Paths in archives are relative to the archive root,
+/// and input files must still be located in include directories.
+pub fn parse_and_typecheck_with_archives(
+    includes: &[PathBuf],
+    archives: &[PathBuf],
+    input: &[PathBuf],
 ) -> anyhow::Result<ParsedAndTypechecked> {
     let mut run = Run {
         parsed_files: LinkedHashMap::new(),
-        resolver: fs_resolver(includes),
+        resolver: fs_resolver(includes, archives)?,
     };
 
     let relative_paths = input
@@ -274,6 +479,89 @@ pub fn parse_and_typecheck(
     })
 }
 
+/// Parse `.proto` files using pure Rust implementation, collecting as many errors as possible.
+///
+/// Unlike [`parse_and_typecheck`], this function does not stop at the first error,
+/// which is useful for editors and linters. Descriptors are returned only if there are no errors.
+///
+/// Recoverable errors are:
+/// * syntax errors: a statement which cannot be parsed (a field, an option,
+///   a nested message etc.) is skipped up to the next `;` or the end of `{ ... }` block,
+///   and parsing continues with the next statement
+/// * typecheck errors (like unresolved type names): reported once per top-level
+///   message, extension or service, enum or file options
+/// * errors reading or resolving a file: the file is skipped
+///
+/// Lexer errors (like an invalid character or an unterminated string literal)
+/// and unexpected end of file stop parsing of the file.
+/// Files with syntax errors or importing files with errors are not typechecked,
+/// to avoid reporting errors caused by other errors.
+pub fn parse_and_typecheck_collect_errors(
+    includes: &[PathBuf],
+    input: &[PathBuf],
+) -> (Option<ParsedAndTypechecked>, Vec<ParseError>) {
+    let mut collected = CollectedErrors::default();
+    let mut run = Run {
+        parsed_files: LinkedHashMap::new(),
+        resolver: fs_resolver(includes, &[]).expect("no archives to read"),
+    };
+
+    let mut relative_paths = Vec::new();
+    for path in input {
+        let mut error = |error| {
+            collected.errors.push(ParseError {
+                file: path.display().to_string(),
+                loc: None,
+                error,
+            })
+        };
+        let proto_path = match path_to_proto_path(path, includes) {
+            Ok(proto_path) => proto_path,
+            Err(e) => {
+                error(e);
+                continue;
+            }
+        };
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(e) => {
+                error(ParseAndTypeckError::CouldNotReadFile(path.display().to_string(), e).into());
+                continue;
+            }
+        };
+        if run.parsed_files.get(&proto_path).is_none()
+            && !collected.failed_files.contains(&proto_path)
+        {
+            run.add_file_content_collect_errors(
+                &proto_path,
+                &ResolvedProtoFile {
+                    path: path.display().to_string(),
+                    content,
+                },
+                &mut collected,
+            );
+        }
+        relative_paths.push(proto_path);
+    }
+
+    if !collected.errors.is_empty() {
+        return (None, collected.errors);
+    }
+
+    let file_descriptors = run
+        .parsed_files
+        .into_iter()
+        .map(|(_, v)| v.descriptor)
+        .collect();
+    (
+        Some(ParsedAndTypechecked {
+            relative_paths,
+            file_descriptors,
+        }),
+        Vec::new(),
+    )
+}
+
 #[doc(hidden)]
 pub fn parse_and_typecheck_custom(
     input: &[ProtoPathBuf],
@@ -298,10 +586,12 @@ pub fn parse_and_typecheck_custom(
 #[cfg(test)]
 mod test {
     use std::fmt;
+    use std::fs;
 
     use crate::proto_path::ProtoPath;
     use crate::pure::parse_and_typecheck::ProtoPathResolver;
     use crate::pure::parse_and_typecheck::ResolvedProtoFile;
+    use crate::pure::tar::test::make_tar;
     use crate::ProtoPathBuf;
 
     #[test]
@@ -335,4 +625,154 @@ mod test {
         assert_eq!(1, resolved.len());
         assert_eq!("Foo", resolved[0].message_type[0].get_name());
     }
+
+    #[test]
+    fn parse_and_typecheck_non_ascii_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("defaults.proto");
+        fs::write(
+            &file,
+            r#"syntax = "proto2"; message Foo {
+                optional string a = 1 [default = "café"];
+                optional string b = 2 [default = "caf\u00e9 \U0001F600"];
+            }"#,
+        )
+        .unwrap();
+
+        let parsed =
+            super::parse_and_typecheck_with_archives(&[dir.path().to_path_buf()], &[], &[file])
+                .unwrap();
+        let fields = &parsed.file_descriptors[0].message_type[0].field;
+        assert_eq!("café", fields[0].get_default_value());
+        assert_eq!("café \u{1f600}", fields[1].get_default_value());
+    }
+
+    #[test]
+    fn parse_and_typecheck_with_archives() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("deps.tar");
+        fs::write(
+            &archive,
+            make_tar(&[(
+                "deps/apple.proto",
+                "syntax = 'proto3'; package deps; message Apple {}",
+            )]),
+        )
+        .unwrap();
+        let banana = dir.path().join("banana.proto");
+        fs::write(
+            &banana,
+            "syntax = 'proto3'; import 'deps/apple.proto'; message Banana { deps.Apple a = 1; }",
+        )
+        .unwrap();
+
+        let parsed = super::parse_and_typecheck_with_archives(
+            &[dir.path().to_path_buf()],
+            &[archive.clone()],
+            &[banana],
+        )
+        .unwrap();
+        assert_eq!(2, parsed.file_descriptors.len());
+        assert_eq!("deps/apple.proto", parsed.file_descriptors[0].get_name());
+        assert_eq!("banana.proto", parsed.file_descriptors[1].get_name());
+    }
+
+    #[test]
+    fn parse_and_typecheck_with_archives_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("deps.tar");
+        fs::write(
+            &archive,
+            make_tar(&[("bad.proto", "syntax = 'proto3'; message {}")]),
+        )
+        .unwrap();
+
+        let bad = dir.path().join("bad_import.proto");
+        fs::write(&bad, "syntax = 'proto3'; import 'bad.proto';").unwrap();
+        let e = super::parse_and_typecheck_with_archives(
+            &[dir.path().to_path_buf()],
+            &[archive.clone()],
+            &[bad],
+        )
+        .err()
+        .unwrap();
+        // Error is reported with path inside archive
+        assert!(
+            e.to_string()
+                .starts_with(&format!("error in `{}:bad.proto`", archive.display())),
+            "{}",
+            e
+        );
+
+        let missing = dir.path().join("missing_import.proto");
+        fs::write(&missing, "syntax = 'proto3'; import 'missing.proto';").unwrap();
+        let e = super::parse_and_typecheck_with_archives(
+            &[dir.path().to_path_buf()],
+            &[archive.clone()],
+            &[missing],
+        )
+        .err()
+        .unwrap();
+        assert!(e.to_string().contains("deps.tar"), "{}", e);
+    }
+
+    #[test]
+    fn parse_and_typecheck_collect_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+
+        let good = write(
+            "good.proto",
+            "syntax = 'proto3'; message Good { int32 a = 1; }",
+        );
+        let (parsed, errors) =
+            super::parse_and_typecheck_collect_errors(&[dir.path().to_path_buf()], &[good.clone()]);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(1, parsed.unwrap().file_descriptors.len());
+
+        let syntax = write(
+            "syntax.proto",
+            "syntax = 'proto3';\nmessage A { int32 = 1; }\nmessage B { string s = ; }\n",
+        );
+        let types = write(
+            "types.proto",
+            "syntax = 'proto3';\nimport 'good.proto';\n\
+             message C { Good g = 1; Missing m = 2; }\nmessage D { Other o = 1; }\n",
+        );
+        let imports = write(
+            "imports.proto",
+            "syntax = 'proto3'; import 'syntax.proto'; import 'missing.proto'; message E {}",
+        );
+        let (parsed, errors) = super::parse_and_typecheck_collect_errors(
+            &[dir.path().to_path_buf()],
+            &[syntax.clone(), types.clone(), imports.clone()],
+        );
+        assert!(parsed.is_none());
+        assert_eq!(
+            format!("error in `{}` at 2:19", syntax.display()),
+            errors[0].to_string()
+        );
+        assert_eq!(
+            errors[0].error.to_string(),
+            std::error::Error::source(&errors[0]).unwrap().to_string()
+        );
+        let errors: Vec<_> = errors.iter().map(|e| (e.file.clone(), e.loc)).collect();
+        let syntax = syntax.display().to_string();
+        let types = types.display().to_string();
+        let imports = imports.display().to_string();
+        assert_eq!(
+            vec![
+                (syntax.clone(), Some((2, 19))),
+                (syntax.clone(), Some((3, 24))),
+                (types.clone(), Some((3, 1))),
+                (types.clone(), Some((4, 1))),
+                (imports.clone(), None),
+            ],
+            errors
+        );
+    }
 }
@@ -0,0 +1,209 @@
+//! Minimal reader of uncompressed tar archives.
+//!
+//! Only regular files are read, other entries (directories, links, etc.) are skipped.
+//! GNU long names are supported, pax extended headers are ignored.
+
+use std::str;
+
+const BLOCK_SIZE: usize = 512;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum TarError {
+    #[error("compressed archives are not supported, archive must be decompressed first")]
+    Compressed,
+    #[error("unexpected end of archive")]
+    UnexpectedEof,
+    #[error("incorrect tar header checksum at offset {0}")]
+    IncorrectChecksum(usize),
+    #[error("incorrect number in tar header at offset {0}")]
+    IncorrectNumber(usize),
+    #[error("file name is not UTF-8 at offset {0}")]
+    NameIsNotUtf8(usize),
+}
+
+/// File stored in tar archive.
+pub(crate) struct TarEntry {
+    /// Path inside archive, without leading `./`.
+    pub name: String,
+    pub content: Vec<u8>,
+}
+
+fn c_str(bytes: &[u8]) -> &[u8] {
+    match bytes.iter().position(|&b| b == 0) {
+        Some(pos) => &bytes[..pos],
+        None => bytes,
+    }
+}
+
+fn parse_name(bytes: &[u8], offset: usize) -> Result<String, TarError> {
+    str::from_utf8(c_str(bytes))
+        .map(|s| s.to_owned())
+        .map_err(|_| TarError::NameIsNotUtf8(offset))
+}
+
+fn parse_octal(bytes: &[u8], offset: usize) -> Result<usize, TarError> {
+    let s = str::from_utf8(c_str(bytes)).map_err(|_| TarError::IncorrectNumber(offset))?;
+    let s = s.trim_matches(|c| c == ' ' || c == '\0');
+    if s.is_empty() {
+        return Ok(0);
+    }
+    usize::from_str_radix(s, 8).map_err(|_| TarError::IncorrectNumber(offset))
+}
+
+fn check_checksum(header: &[u8], offset: usize) -> Result<(), TarError> {
+    let expected = parse_octal(&header[148..156], offset)?;
+    // Checksum is computed with checksum field filled with spaces
+    let actual: usize = header[..148]
+        .iter()
+        .chain(&[b' '; 8])
+        .chain(&header[156..])
+        .map(|&b| b as usize)
+        .sum();
+    if expected != actual {
+        return Err(TarError::IncorrectChecksum(offset));
+    }
+    Ok(())
+}
+
+/// Read all regular files from tar archive.
+pub(crate) fn read_tar(archive: &[u8]) -> Result<Vec<TarEntry>, TarError> {
+    if archive.starts_with(&[0x1f, 0x8b]) || archive.starts_with(b"PK\x03\x04") {
+        return Err(TarError::Compressed);
+    }
+
+    let mut entries = Vec::new();
+    let mut long_name = None;
+    let mut pos = 0;
+    loop {
+        let header = archive
+            .get(pos..pos + BLOCK_SIZE)
+            .ok_or(TarError::UnexpectedEof)?;
+        if header.iter().all(|&b| b == 0) {
+            // End of archive marker
+            break;
+        }
+        check_checksum(header, pos)?;
+
+        let mut name = parse_name(&header[0..100], pos)?;
+        if &header[257..262] == b"ustar" {
+            let prefix = parse_name(&header[345..500], pos)?;
+            if !prefix.is_empty() {
+                name = format!("{}/{}", prefix, name);
+            }
+        }
+        let size = parse_octal(&header[124..136], pos)?;
+        let type_flag = header[156];
+
+        let data_start = pos + BLOCK_SIZE;
+        let data = archive
+            .get(data_start..data_start + size)
+            .ok_or(TarError::UnexpectedEof)?;
+        pos = data_start + (size + BLOCK_SIZE - 1) / BLOCK_SIZE * BLOCK_SIZE;
+
+        match type_flag {
+            // GNU long name of the next entry
+            b'L' => {
+                long_name = Some(parse_name(data, data_start)?);
+                continue;
+            }
+            // Regular file
+            b'0' | 0 => {
+                let name = long_name.take().unwrap_or(name);
+                let name = name.trim_start_matches("./").to_owned();
+                entries.push(TarEntry {
+                    name,
+                    content: data.to_vec(),
+                });
+            }
+            _ => {
+                long_name = None;
+            }
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::read_tar;
+    use super::TarError;
+    use super::BLOCK_SIZE;
+
+    fn header(name: &str, size: usize, type_flag: u8) -> Vec<u8> {
+        let mut header = vec![0; BLOCK_SIZE];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..108].copy_from_slice(b"0000644\0");
+        header[124..136].copy_from_slice(format!("{:011o}\0", size).as_bytes());
+        header[156] = type_flag;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[148..156].copy_from_slice(b"        ");
+        let checksum: usize = header.iter().map(|&b| b as usize).sum();
+        header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+        header
+    }
+
+    fn push_entry(tar: &mut Vec<u8>, name: &str, content: &[u8], type_flag: u8) {
+        tar.extend(header(name, content.len(), type_flag));
+        tar.extend(content);
+        let padding = (BLOCK_SIZE - content.len() % BLOCK_SIZE) % BLOCK_SIZE;
+        tar.extend(vec![0; padding]);
+    }
+
+    /// Build tar archive with given regular files.
+    pub(crate) fn make_tar(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut tar = Vec::new();
+        for (name, content) in files {
+            push_entry(&mut tar, name, content.as_bytes(), b'0');
+        }
+        tar.extend(vec![0; BLOCK_SIZE * 2]);
+        tar
+    }
+
+    #[test]
+    fn files() {
+        let tar = make_tar(&[("./a.proto", "syntax = 'proto3';"), ("b/c.proto", "")]);
+        let entries = read_tar(&tar).unwrap();
+        assert_eq!(2, entries.len());
+        assert_eq!("a.proto", entries[0].name);
+        assert_eq!(b"syntax = 'proto3';", &entries[0].content[..]);
+        assert_eq!("b/c.proto", entries[1].name);
+        assert_eq!(b"", &entries[1].content[..]);
+    }
+
+    #[test]
+    fn directories_and_long_names() {
+        let long_name = format!("{}/x.proto", "d".repeat(150));
+        let mut tar = Vec::new();
+        push_entry(&mut tar, "d/", b"", b'5');
+        push_entry(&mut tar, "././@LongLink", long_name.as_bytes(), b'L');
+        push_entry(&mut tar, "truncated", b"message X {}", b'0');
+        tar.extend(vec![0; BLOCK_SIZE * 2]);
+
+        let entries = read_tar(&tar).unwrap();
+        assert_eq!(1, entries.len());
+        assert_eq!(long_name, entries[0].name);
+        assert_eq!(b"message X {}", &entries[0].content[..]);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            read_tar(&[0x1f, 0x8b, 0, 0]),
+            Err(TarError::Compressed)
+        ));
+
+        let mut tar = make_tar(&[("a.proto", "")]);
+        tar[0] = b'b';
+        assert!(matches!(
+            read_tar(&tar),
+            Err(TarError::IncorrectChecksum(0))
+        ));
+
+        let tar = make_tar(&[("a.proto", "message A {}")]);
+        assert!(matches!(
+            read_tar(&tar[..BLOCK_SIZE + 5]),
+            Err(TarError::UnexpectedEof)
+        ));
+    }
+}
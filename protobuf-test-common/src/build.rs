@@ -133,6 +133,9 @@ pub fn gen_mod_rs_in_dir(dir: &str) {
         if mod_name.contains("carllerche") {
             writeln!(mod_rs, r#"#[cfg(feature = "with-bytes")]"#).expect("write carllerche");
         }
+        if mod_name.contains("smallvec") {
+            writeln!(mod_rs, r#"#[cfg(feature = "with-smallvec")]"#).expect("write smallvec");
+        }
         writeln!(mod_rs, "mod {};", mod_name).expect("write");
     }
 
@@ -89,11 +89,109 @@ fn generate_include_generated() {
         .run_from_script();
 }
 
+fn generate_cargo_out_dir() {
+    Codegen::new()
+        .protoc()
+        .cargo_out_dir("cargo_protos")
+        .input("src/cargo_out_dir/first.proto")
+        .input("src/cargo_out_dir/second.proto")
+        .include("src/cargo_out_dir")
+        .run_from_script();
+}
+
+fn generate_split_impls() {
+    Codegen::new()
+        .protoc()
+        .cargo_out_dir("split_impls_protos")
+        .input("src/split_impls/split_impls.proto")
+        .includes(&["src/split_impls", "../proto"])
+        .customize(Customize {
+            split_impls: Some(true),
+            ..Default::default()
+        })
+        .run_from_script();
+}
+
+/// Enum is too large to be checked in, so `.proto` file is generated.
+fn generate_huge_enum() {
+    let dir = format!("{}/huge_enum", env::var("OUT_DIR").unwrap());
+    if Path::new(&dir).exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir(&dir).unwrap();
+
+    let mut proto = String::new();
+    proto.push_str("syntax = \"proto2\";\n\n");
+    proto.push_str("import \"rustproto.proto\";\n\n");
+    proto.push_str("option (rustproto.enum_lookup_threshold_all) = 1000;\n\n");
+    // Numbers are `-17500 + 7 * i`, declared in shuffled order.
+    proto.push_str("enum HugeEnum {\n");
+    for i in 0..5000 {
+        let i = i * 2903 % 5000;
+        proto.push_str(&format!("    VALUE_{} = {};\n", i, -17500 + 7 * i));
+    }
+    proto.push_str("}\n\n");
+    proto.push_str("enum SmallEnum {\n    SMALL_ZERO = 0;\n    SMALL_ONE = 1;\n}\n");
+    let path = format!("{}/huge_enum.proto", dir);
+    fs::write(&path, proto).unwrap();
+
+    Codegen::new()
+        .protoc()
+        .cargo_out_dir("huge_enum_protos")
+        .input(path)
+        .includes(&[dir.as_str(), "../proto"])
+        .run_from_script();
+}
+
+fn generate_implement_trait() {
+    let dir = format!("{}/implement_trait", env::var("OUT_DIR").unwrap());
+    if Path::new(&dir).exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir(&dir).unwrap();
+    Codegen::new()
+        .protoc()
+        .out_dir(dir)
+        .input("src/implement_trait/implement_trait.proto")
+        .customize(
+            Customize {
+                gen_mod_rs: Some(true),
+                ..Default::default()
+            }
+            .implement_trait(
+                "crate::implement_trait::Routable",
+                r#"
+fn route(&self) -> &'static str {
+    "$full_name"
+}
+
+fn name() -> &'static str {
+    "$name"
+}
+
+fn rust_name() -> &'static str {
+    "$rust_name"
+}
+
+fn descriptor_full_name(&self) -> String {
+    $descriptor.full_name().to_owned()
+}
+"#,
+            ),
+        )
+        .include("src/implement_trait")
+        .run_from_script();
+}
+
 fn generate_pb_rs() {
     generate_in_common();
     generate_in_v2_v3();
     generate_interop();
     generate_include_generated();
+    generate_cargo_out_dir();
+    generate_split_impls();
+    generate_huge_enum();
+    generate_implement_trait();
 }
 
 fn main() {
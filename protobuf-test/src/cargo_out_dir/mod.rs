@@ -0,0 +1,13 @@
+// Include single file which declares module `cargo_protos` with generated mods
+include!(concat!(env!("OUT_DIR"), "/cargo_protos.rs"));
+
+use cargo_protos::first::First;
+use cargo_protos::second::Second;
+
+#[test]
+fn test() {
+    let mut second = Second::new();
+    second.first = Some(First::new()).into();
+    second.first.as_mut().unwrap().value = 10;
+    assert_eq!(10, second.first.get_ref().value);
+}
@@ -3,7 +3,10 @@ use protobuf::reflect;
 use protobuf::CodedInputStream;
 use protobuf::CodedOutputStream;
 use protobuf::Message;
+use protobuf::ParseOptions;
 use protobuf::ProtobufEnum;
+use protobuf::ProtobufError;
+use protobuf::WireError;
 use protobuf_test_common::hex::decode_hex;
 use protobuf_test_common::*;
 
@@ -314,3 +317,43 @@ fn test_serialize_too_large_message() {
     let mut s = CodedOutputStream::bytes(&mut bytes);
     test1.write_to_with_cached_sizes(&mut s).unwrap_err();
 }
+
+#[test]
+fn test_integer_overflow() {
+    let options = ParseOptions {
+        reject_integer_overflow: true,
+        ..Default::default()
+    };
+    let assert_overflow = |hex: &str, expected_field_number: u32| {
+        match TestTypesSingular::parse_from_bytes_with_options(&decode_hex(hex), &options) {
+            Err(ProtobufError::WireError(WireError::IntegerOverflow { field_number })) => {
+                assert_eq!(expected_field_number, field_number)
+            }
+            r => panic!("expecting integer overflow: {:?}", r),
+        }
+    };
+
+    // `uint32_field` = 2^32
+    assert_overflow("28 80 80 80 80 10", 5);
+    // `sint32_field` = 2^33
+    assert_overflow("38 80 80 80 80 20", 7);
+    // `int32_field` = 2^40 after valid `int64_field`
+    assert_overflow("20 01 18 80 80 80 80 80 20", 3);
+    // packed `int32_field` in repeated message
+    match TestTypesRepeatedPacked::parse_from_bytes_with_options(
+        &decode_hex("1a 06 01 80 80 80 80 10"),
+        &options,
+    ) {
+        Err(ProtobufError::WireError(WireError::IntegerOverflow { field_number: 3 })) => {}
+        r => panic!("expecting integer overflow: {:?}", r),
+    }
+
+    // negative `int32_field` encoded as 64-bit varint
+    let m = TestTypesSingular::parse_from_bytes(&decode_hex("18 ff ff ff ff ff ff ff ff ff 01"))
+        .unwrap();
+    assert_eq!(-1, m.get_int32_field());
+
+    // out-of-range values are truncated unless rejection is requested
+    let m = TestTypesSingular::parse_from_bytes(&decode_hex("28 81 80 80 80 10")).unwrap();
+    assert_eq!(1, m.get_uint32_field());
+}
@@ -0,0 +1,81 @@
+use protobuf::reflect::ReflectValueBox;
+use protobuf::Message;
+use protobuf_test_common::*;
+
+use super::test_boxed_slice_pb::*;
+
+fn new_message() -> Dataset {
+    let mut m = Dataset::new();
+    m.values = vec![1, -2, 300].into_boxed_slice();
+    m.packed_values = vec![-4, 5].into_boxed_slice();
+    m.names = vec!["aa".to_owned(), "bb".to_owned()].into_boxed_slice();
+    let mut point = Point::new();
+    point.set_x(10);
+    m.points = vec![point].into_boxed_slice();
+    m.kinds = vec![Kind::KIND_POINT.into()].into_boxed_slice();
+    m.set_title("t".to_owned());
+    m
+}
+
+#[test]
+fn test_types() {
+    let m = Dataset::new();
+    let _: &Box<[i32]> = &m.values;
+    let _: &Box<[String]> = &m.names;
+    let _: &Box<[Point]> = &m.points;
+    let _: &[i32] = m.get_values();
+
+    let m = Unboxed::new();
+    let _: &Vec<i32> = &m.values;
+}
+
+#[test]
+fn test_serialize_deserialize() {
+    let m = new_message();
+    test_serialize_deserialize_no_hex(&m);
+    test_serialize_deserialize_length_delimited(&m);
+    test_serialize_deserialize_no_hex(&Dataset::new());
+}
+
+#[test]
+fn test_merge() {
+    let m = new_message();
+    let bytes = m.write_to_bytes().unwrap();
+    let mut merged = m.clone();
+    merged.merge_from_bytes(&bytes).unwrap();
+    assert_eq!(&[1, -2, 300, 1, -2, 300], &merged.values[..]);
+    assert_eq!(&[-4, 5, -4, 5], &merged.packed_values[..]);
+    assert_eq!(4, merged.names.len());
+    assert_eq!(2, merged.points.len());
+}
+
+#[test]
+fn test_accessors() {
+    let mut m = new_message();
+    m.mut_values()[0] = 7;
+    assert_eq!(&[7, -2, 300], m.get_values());
+    let values = m.take_values();
+    assert_eq!(&[7, -2, 300], &values[..]);
+    assert!(m.get_values().is_empty());
+    m.set_values(values);
+    m.clear_names();
+    assert!(m.names.is_empty());
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = new_message();
+    let field = Dataset::descriptor_static()
+        .get_field_by_name("names")
+        .unwrap();
+    let repeated = field.get_repeated(&m);
+    assert_eq!(2, repeated.len());
+    assert_eq!(Some("bb"), repeated.get(1).to_str());
+
+    let mut repeated = field.mut_repeated(&mut m);
+    repeated.push(ReflectValueBox::String("cc".to_owned()));
+    assert_eq!(&["aa", "bb", "cc"], &m.names[..]);
+
+    test_text_format_message(&m);
+    test_json_message(&m);
+}
@@ -0,0 +1,24 @@
+use super::test_bytes_wrapper_pb::*;
+
+fn len<B: AsRef<[u8]>>(b: B) -> usize {
+    b.as_ref().len()
+}
+
+#[test]
+fn test_as_ref() {
+    let mut blob = Blob::new();
+    assert_eq!(b"", blob.as_ref());
+
+    blob.set_data(b"abc".to_vec());
+    assert_eq!(b"abc", blob.as_ref());
+    assert_eq!(3, len(&blob));
+}
+
+#[test]
+fn test_deref() {
+    let mut blob = Blob::new();
+    blob.set_data(b"abc".to_vec());
+    assert_eq!(3, blob.len());
+    assert_eq!(&b"bc"[..], &blob[1..]);
+    assert!(blob.starts_with(b"ab"));
+}
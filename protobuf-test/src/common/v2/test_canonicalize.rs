@@ -0,0 +1,69 @@
+use protobuf::reflect::CanonicalizeOptions;
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+use super::test_canonicalize_pb::*;
+
+fn leaf(value: i32, with_unknown: bool) -> Leaf {
+    let mut leaf = Leaf::new();
+    leaf.set_value(value);
+    if with_unknown {
+        leaf.mut_unknown_fields().add_varint(15, 1);
+    }
+    leaf
+}
+
+fn tree(with_unknown: bool) -> Tree {
+    let mut tree = Tree::new();
+    tree.set_name("t".to_owned());
+    tree.set_leaf(leaf(1, with_unknown));
+    tree.mut_leaves().push(leaf(2, with_unknown));
+    tree.mut_leaves().push(leaf(3, false));
+    tree.mut_leaf_map()
+        .insert("a".to_owned(), leaf(4, with_unknown));
+    tree.mut_int_map().insert("b".to_owned(), 5);
+    tree.set_oneof_leaf(leaf(6, with_unknown));
+    if with_unknown {
+        tree.mut_unknown_fields().add_varint(14, 7);
+    }
+    tree
+}
+
+fn discard_unknown_fields() -> CanonicalizeOptions {
+    CanonicalizeOptions {
+        discard_unknown_fields: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_discard_unknown_fields() {
+    let mut m = tree(true);
+    assert_ne!(
+        tree(false).write_to_bytes().unwrap(),
+        m.write_to_bytes().unwrap()
+    );
+
+    m.canonicalize(&discard_unknown_fields());
+    assert_eq!(tree(false), m);
+    assert_eq!(
+        tree(false).write_to_bytes().unwrap(),
+        m.write_to_bytes().unwrap()
+    );
+}
+
+#[test]
+fn test_keep_unknown_fields() {
+    let mut m = tree(true);
+    m.canonicalize(&CanonicalizeOptions::default());
+    assert_eq!(tree(true), m);
+}
+
+#[test]
+fn test_dyn() {
+    let mut m = tree(true);
+    let m: &mut dyn MessageDyn = &mut m;
+    m.canonicalize_dyn(&discard_unknown_fields());
+    assert!(m.get_unknown_fields_dyn().iter().next().is_none());
+    assert_eq!(&tree(false), m.downcast_ref::<Tree>().unwrap());
+}
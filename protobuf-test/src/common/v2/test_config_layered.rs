@@ -0,0 +1,50 @@
+use protobuf::config::load_layered;
+use protobuf::config::LoadLayeredError;
+
+use super::test_config_layered_pb::*;
+
+fn defaults() -> Config {
+    let mut config = Config::new();
+    config.set_name("default".to_owned());
+    config.mut_server().set_host("localhost".to_owned());
+    config.mut_server().set_port(8080);
+    config.mut_features().push("base".to_owned());
+    config.mut_limits().insert("connections".to_owned(), 10);
+    config.mut_limits().insert("threads".to_owned(), 4);
+    config
+}
+
+#[test]
+fn test_layers() {
+    let environment = "server { host: \"example.com\" } features: \"metrics\"";
+    let local = "name: \"local\" limits { key: \"threads\" value: 16 }";
+
+    let config = load_layered(defaults(), &[environment, local]).unwrap();
+    assert_eq!("local", config.get_name());
+    // Sub-message is merged, not replaced
+    assert_eq!("example.com", config.get_server().get_host());
+    assert_eq!(8080, config.get_server().get_port());
+    assert_eq!(vec!["base", "metrics"], config.get_features());
+    assert_eq!(10, config.get_limits()["connections"]);
+    assert_eq!(16, config.get_limits()["threads"]);
+}
+
+#[test]
+fn test_later_layer_wins() {
+    let config = load_layered(
+        Config::new(),
+        &["server { port: 1 }", "server { port: 2 }", ""],
+    )
+    .unwrap();
+    assert_eq!(2, config.get_server().get_port());
+}
+
+#[test]
+fn test_error_layer() {
+    match load_layered(defaults(), &["name: \"a\"", "unknown: 1"]) {
+        Err(LoadLayeredError::Parse { layer: 1, error }) => {
+            assert_eq!("unknown field `unknown` at 1:1", error.to_string());
+        }
+        r => panic!("expecting parse error: {:?}", r),
+    }
+}
@@ -0,0 +1,105 @@
+use protobuf::Message;
+use protobuf::UnknownValue;
+
+use super::test_content_hash_pb::*;
+
+fn sample() -> ForHash {
+    let mut m = ForHash::new();
+    m.set_i(10);
+    m.set_s("abc".to_owned());
+    m.set_d(1.5);
+    m.set_r(vec![1, 2, 3]);
+    m.mut_nested().set_name("n".to_owned());
+    m.set_color(Color::GREEN);
+    m
+}
+
+#[test]
+fn test_equal_messages() {
+    assert_eq!(sample().content_hash(), sample().content_hash());
+    assert_eq!(ForHash::new().content_hash(), ForHash::new().content_hash());
+}
+
+#[test]
+fn test_different_messages() {
+    let a = sample();
+
+    let mut b = sample();
+    b.set_i(11);
+    assert_ne!(a.content_hash(), b.content_hash());
+
+    let mut b = sample();
+    b.mut_nested().set_name("m".to_owned());
+    assert_ne!(a.content_hash(), b.content_hash());
+
+    let mut b = sample();
+    b.set_r(vec![1, 3, 2]);
+    assert_ne!(a.content_hash(), b.content_hash());
+
+    assert_ne!(a.content_hash(), ForHash::new().content_hash());
+}
+
+#[test]
+fn test_map_order() {
+    let mut a = sample();
+    let mut b = sample();
+    for i in 0..100 {
+        a.mut_m().insert(format!("k{}", i), i);
+        b.mut_m().insert(format!("k{}", 99 - i), 99 - i);
+    }
+    assert_eq!(a, b);
+    assert_eq!(a.content_hash(), b.content_hash());
+
+    b.mut_m().insert("k0".to_owned(), 1000);
+    assert_ne!(a.content_hash(), b.content_hash());
+}
+
+#[test]
+fn test_wire_order_does_not_matter() {
+    let mut a = ForHash::new();
+    a.set_i(10);
+    a.set_s("abc".to_owned());
+    a.mut_nested().set_name("n".to_owned());
+
+    let mut b = ForHash::new();
+    b.merge_from_bytes(&{
+        let mut m = ForHash::new();
+        m.mut_nested().set_name("n".to_owned());
+        m.write_to_bytes().unwrap()
+    })
+    .unwrap();
+    b.merge_from_bytes(&{
+        let mut m = ForHash::new();
+        m.set_s("abc".to_owned());
+        m.set_i(10);
+        m.write_to_bytes().unwrap()
+    })
+    .unwrap();
+
+    assert_eq!(a.content_hash(), b.content_hash());
+}
+
+#[test]
+fn test_float_zero() {
+    let mut a = ForHash::new();
+    a.set_d(0.0);
+    let mut b = ForHash::new();
+    b.set_d(-0.0);
+    assert_eq!(a.content_hash(), b.content_hash());
+}
+
+#[test]
+fn test_unknown_fields_ignored() {
+    let a = sample();
+    let mut b = sample();
+    b.mut_unknown_fields()
+        .add_value(100, UnknownValue::Varint(1));
+    assert_eq!(a.content_hash(), b.content_hash());
+}
+
+#[test]
+fn test_dyn() {
+    let m = sample();
+    let d: &dyn protobuf::MessageDyn = &m;
+    assert_eq!(m.content_hash(), d.content_hash_dyn());
+}
@@ -0,0 +1,91 @@
+use protobuf::DecodeStats;
+use protobuf::Message;
+
+use super::test_decode_stats_pb::*;
+
+#[test]
+fn test_flat() {
+    let mut m = Root::new();
+    m.set_name("root".to_owned());
+    m.set_unpacked(vec![1, 2, 3]);
+    m.set_packed(vec![1, 2, 3]);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let (parsed, stats) = Root::parse_from_bytes_with_stats(&bytes).unwrap();
+    assert_eq!(m, parsed);
+    assert_eq!(
+        DecodeStats {
+            fields: 5,
+            unknown_bytes: 0,
+            max_depth: 0,
+        },
+        stats
+    );
+}
+
+#[test]
+fn test_nested() {
+    let mut m = Root::new();
+    m.mut_middle().mut_leaf().set_value(1);
+    m.mut_middle().mut_leaves().push(Leaf::new());
+    let mut leaf = Leaf::new();
+    leaf.set_value(2);
+    m.mut_middle().mut_leaves().push(leaf);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let (parsed, stats) = Root::parse_from_bytes_with_stats(&bytes).unwrap();
+    assert_eq!(m, parsed);
+    // middle, leaf, leaf.value, two leaves, value of the second leaf
+    assert_eq!(6, stats.fields);
+    assert_eq!(0, stats.unknown_bytes);
+    assert_eq!(2, stats.max_depth);
+}
+
+#[test]
+fn test_map() {
+    let mut m = Root::new();
+    let mut leaf = Leaf::new();
+    leaf.set_value(1);
+    m.mut_leaf_map().insert("a".to_owned(), leaf);
+    m.mut_int_map().insert("b".to_owned(), 2);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let (parsed, stats) = Root::parse_from_bytes_with_stats(&bytes).unwrap();
+    assert_eq!(m, parsed);
+    // two map entries and leaf value
+    assert_eq!(3, stats.fields);
+    assert_eq!(1, stats.max_depth);
+}
+
+#[test]
+fn test_unknown_fields() {
+    let mut m = Root::new();
+    m.mut_middle().mut_leaf().set_value(1);
+    let mut bytes = m.write_to_bytes().unwrap();
+    // field 15 varint 7
+    bytes.extend_from_slice(&[15 << 3, 7]);
+    // field 14 length-delimited "abc"
+    bytes.extend_from_slice(&[14 << 3 | 2, 3, b'a', b'b', b'c']);
+
+    let (_, stats) = Root::parse_from_bytes_with_stats(&bytes).unwrap();
+    assert_eq!(5, stats.fields);
+    assert_eq!(7, stats.unknown_bytes);
+
+    // unknown fields in nested message
+    let mut leaf = Leaf::new();
+    leaf.mut_unknown_fields().add_varint(15, 7);
+    m.mut_middle().set_leaf(leaf);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let (_, stats) = Root::parse_from_bytes_with_stats(&bytes).unwrap();
+    assert_eq!(3, stats.fields);
+    assert_eq!(2, stats.unknown_bytes);
+}
+
+#[test]
+fn test_error() {
+    let mut m = Root::new();
+    m.set_name("root".to_owned());
+    let bytes = m.write_to_bytes().unwrap();
+    assert!(Root::parse_from_bytes_with_stats(&bytes[..bytes.len() - 1]).is_err());
+}
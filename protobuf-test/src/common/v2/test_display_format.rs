@@ -0,0 +1,29 @@
+use super::test_display_format_pb::*;
+
+fn user(id: u64, name: &str) -> User {
+    let mut user = User::new();
+    user.set_id(id);
+    user.set_name(name.to_owned());
+    user
+}
+
+#[test]
+fn test_display() {
+    assert_eq!("User 17 (alice)", user(17, "alice").to_string());
+}
+
+#[test]
+fn test_nested_message_and_spec() {
+    let mut event = Event::new();
+    event.set_kind("login".to_owned());
+    event.set_user(user(1, "bob"));
+    assert_eq!("{ login} by User 1 (bob), login", event.to_string());
+}
+
+#[test]
+fn test_oneof() {
+    let mut status = Status::new();
+    assert_eq!("code=0", status.to_string());
+    status.set_code(404);
+    assert_eq!("code=404", status.to_string());
+}
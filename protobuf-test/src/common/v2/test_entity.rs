@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use protobuf::Entity;
+
+use super::test_entity_pb::user::Session;
+use super::test_entity_pb::*;
+
+fn index<M>(messages: &[M]) -> HashMap<&M::PrimaryKey, &M>
+where
+    M: Entity,
+    M::PrimaryKey: Eq + Hash,
+{
+    messages.iter().map(|m| (m.primary_key(), m)).collect()
+}
+
+fn user(login: &str, name: &str) -> User {
+    let mut user = User::new();
+    user.set_login(login.to_owned());
+    user.set_name(name.to_owned());
+    user
+}
+
+#[test]
+fn test_string_key() {
+    assert_eq!("", User::new().primary_key());
+
+    let users = vec![user("alice", "Alice"), user("bob", "Bob")];
+    let index = index(&users);
+    assert_eq!("Bob", index["bob"].get_name());
+    assert_eq!("Alice", index["alice"].get_name());
+}
+
+#[test]
+fn test_integer_key() {
+    assert_eq!(&0, Session::new().primary_key());
+
+    let mut session = Session::new();
+    session.set_id(17);
+    let key: &u64 = session.primary_key();
+    assert_eq!(&17, key);
+}
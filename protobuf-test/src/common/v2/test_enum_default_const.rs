@@ -0,0 +1,21 @@
+use super::test_enum_default_const_pb::*;
+
+#[test]
+fn test_default_const() {
+    assert_eq!(Color::UNSPECIFIED, Color::DEFAULT);
+    assert_eq!(Color::DEFAULT, Color::default());
+}
+
+#[test]
+fn test_default_variant_name() {
+    assert_eq!(Level::LOW, Level::default());
+    assert_ne!(Level::LOW, Level::DEFAULT);
+}
+
+#[test]
+fn test_field_default() {
+    let m = WithEnums::new();
+    assert_eq!(Color::DEFAULT, m.get_color());
+    assert_eq!(Level::LOW, m.get_level());
+    assert_eq!(WithEnums::new(), WithEnums::default());
+}
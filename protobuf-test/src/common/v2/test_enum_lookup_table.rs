@@ -0,0 +1,41 @@
+use protobuf::ProtobufEnum;
+
+use super::test_enum_lookup_table_pb::*;
+
+#[test]
+fn from_i32_all_values() {
+    assert_eq!(20, BigEnum::values().len());
+    for &v in BigEnum::values() {
+        assert_eq!(Some(v), BigEnum::from_i32(v.value()));
+    }
+}
+
+#[test]
+fn from_i32_missing_values() {
+    for n in -40..40 {
+        let expected = n >= -30 && n < 30 && (n + 30) % 3 == 0;
+        assert_eq!(expected, BigEnum::from_i32(n).is_some(), "{}", n);
+    }
+    assert_eq!(None, BigEnum::from_i32(i32::MIN));
+    assert_eq!(None, BigEnum::from_i32(i32::MAX));
+}
+
+#[test]
+fn from_i32_boundaries() {
+    assert_eq!(Some(BigEnum::VALUE_M30), BigEnum::from_i32(-30));
+    assert_eq!(Some(BigEnum::VALUE_0), BigEnum::from_i32(0));
+    assert_eq!(Some(BigEnum::VALUE_27), BigEnum::from_i32(27));
+}
+
+#[test]
+fn small_enum() {
+    assert_eq!(Some(SmallEnum::SMALL_ONE), SmallEnum::from_i32(1));
+    assert_eq!(None, SmallEnum::from_i32(2));
+}
+
+#[test]
+fn lookup_table_only_above_threshold() {
+    // `SmallEnum` below the threshold still uses `match`
+    let generated = include_str!("test_enum_lookup_table_pb.rs");
+    assert_eq!(1, generated.matches("binary_search_by_key").count());
+}
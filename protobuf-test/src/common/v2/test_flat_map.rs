@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use protobuf::reflect::to_flat_map;
+use protobuf::reflect::to_flat_map_with_options;
+use protobuf::reflect::FlatMapOptions;
+use protobuf::reflect::FlatMapRepeated;
+
+use super::test_flat_map_pb::*;
+
+fn sample() -> Metrics {
+    let mut m = Metrics::new();
+    m.set_host("db1".to_owned());
+    m.set_level(Level::LEVEL_HIGH);
+    m.set_raw(b"a\n".to_vec());
+    m.mut_inner().set_value(7);
+    m.mut_inner().mut_deeper().set_value(8);
+    m.set_tags(vec!["x".to_owned(), "y".to_owned()]);
+    m.mut_labels().insert("env".to_owned(), 3);
+    let mut item = Inner::new();
+    item.set_value(9);
+    m.set_items(vec![item]);
+    m
+}
+
+fn map(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(
+        map(&[
+            ("host", "db1"),
+            ("inner.deeper.value", "8"),
+            ("inner.value", "7"),
+            ("level", "LEVEL_HIGH"),
+            ("raw", "a\\n"),
+        ]),
+        to_flat_map(&sample(), ".")
+    );
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!(BTreeMap::new(), to_flat_map(&Metrics::new(), "."));
+}
+
+#[test]
+fn test_separator() {
+    let flat = to_flat_map(&sample(), "_");
+    assert_eq!(Some(&"8".to_owned()), flat.get("inner_deeper_value"));
+}
+
+#[test]
+fn test_index() {
+    let options = FlatMapOptions {
+        repeated: FlatMapRepeated::Index,
+        ..Default::default()
+    };
+    let flat = to_flat_map_with_options(&sample(), ".", &options);
+    assert_eq!(Some(&"x".to_owned()), flat.get("tags.0"));
+    assert_eq!(Some(&"y".to_owned()), flat.get("tags.1"));
+    assert_eq!(Some(&"3".to_owned()), flat.get("labels.env"));
+    assert_eq!(Some(&"9".to_owned()), flat.get("items.0.value"));
+}
+
+#[test]
+fn test_max_depth() {
+    let options = FlatMapOptions {
+        max_depth: Some(1),
+        ..Default::default()
+    };
+    let flat = to_flat_map_with_options(&sample(), ".", &options);
+    assert_eq!(Some(&"7".to_owned()), flat.get("inner.value"));
+    assert_eq!(None, flat.get("inner.deeper.value"));
+
+    let options = FlatMapOptions {
+        max_depth: Some(0),
+        ..Default::default()
+    };
+    let flat = to_flat_map_with_options(&sample(), ".", &options);
+    assert_eq!(None, flat.get("inner.value"));
+    assert_eq!(Some(&"db1".to_owned()), flat.get("host"));
+}
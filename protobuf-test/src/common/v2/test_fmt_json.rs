@@ -366,3 +366,78 @@ fn test_more_than_one() {
         &m,
     );
 }
+
+#[test]
+fn test_oneof_flattened() {
+    let mut m = TestOneofThreeArms::new();
+    m.set_before(1);
+    m.set_after(2);
+    test_json_print_parse_message("{\"before\": 1, \"after\": 2}", &m);
+
+    m.set_first_arm(10);
+    test_json_print_parse_message("{\"before\": 1, \"firstArm\": 10, \"after\": 2}", &m);
+
+    m.set_second_arm("ss".to_owned());
+    test_json_print_parse_message("{\"before\": 1, \"secondArm\": \"ss\", \"after\": 2}", &m);
+
+    m.mut_third_arm().set_value(30);
+    test_json_print_parse_message(
+        "{\"before\": 1, \"thirdArm\": {\"value\": 30}, \"after\": 2}",
+        &m,
+    );
+    assert!(!m.has_first_arm());
+    assert!(!m.has_second_arm());
+}
+
+#[test]
+fn test_oneof_parse_sets_arm() {
+    let mut m = TestOneofThreeArms::new();
+    json::merge_from_str(&mut m, "{\"second_arm\": \"x\"}").unwrap();
+    assert!(m.has_second_arm());
+    assert!(!m.has_first_arm());
+    assert!(!m.has_third_arm());
+    assert_eq!("x", m.get_second_arm());
+}
+
+#[test]
+fn test_oneof_two_members_is_error() {
+    let mut m = TestOneofThreeArms::new();
+    let r = json::merge_from_str(&mut m, "{\"firstArm\": 1, \"secondArm\": \"x\"}");
+    let e = r.unwrap_err().to_string();
+    assert!(e.contains("first_arm") && e.contains("second_arm"), "{}", e);
+
+    let mut m = TestOneofThreeArms::new();
+    let r = json::merge_from_str(
+        &mut m,
+        "{\"firstArm\": 1, \"before\": 3, \"third_arm\": {}}",
+    );
+    assert!(r.is_err());
+}
+
+#[test]
+fn test_field_aliases() {
+    let mut parse_options = json::ParseOptions::default();
+    parse_options
+        .field_aliases
+        .insert("oldBool".to_owned(), "boolSingular".to_owned());
+    parse_options
+        .field_aliases
+        .insert("old_repeated".to_owned(), "bool_repeated".to_owned());
+
+    let mut m = TestTypes::new();
+    json::merge_from_str_with_options(
+        &mut m,
+        "{\"oldBool\": true, \"old_repeated\": [false]}",
+        &parse_options,
+    )
+    .unwrap();
+
+    let mut expected = TestTypes::new();
+    expected.set_bool_singular(true);
+    expected.set_bool_repeated(vec![false]);
+    assert_eq!(expected, m);
+
+    // Aliases are not used by default
+    let mut m = TestTypes::new();
+    assert!(json::merge_from_str(&mut m, "{\"oldBool\": true}").is_err());
+}
@@ -1,3 +1,4 @@
+use protobuf::json;
 use protobuf::well_known_types::*;
 use protobuf_test_common::*;
 
@@ -29,6 +30,125 @@ fn test_timestamp() {
     test_json_print_parse_message("{\"timestamp\": \"1970-01-01T00:00:01.000000001Z\"}", &m);
 }
 
+fn print_with_fraction_digits(
+    m: &TestFmtJsonWellKnownTypes,
+    digits: ::std::option::Option<u8>,
+) -> String {
+    let print_options = json::PrintOptions {
+        timestamp_fraction_digits: digits,
+        ..Default::default()
+    };
+    json::print_to_string_with_options(m, &print_options).unwrap()
+}
+
+#[test]
+fn test_duration_fraction_digits() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+
+    m.mut_duration().seconds = 2;
+    assert_eq!(
+        "{\"duration\": \"2s\"}",
+        print_with_fraction_digits(&m, None)
+    );
+    assert_eq!(
+        "{\"duration\": \"2.000s\"}",
+        print_with_fraction_digits(&m, Some(3))
+    );
+
+    // millisecond precision
+    m.mut_duration().nanos = 250_000_000;
+    assert_eq!(
+        "{\"duration\": \"2.250s\"}",
+        print_with_fraction_digits(&m, None)
+    );
+    assert_eq!(
+        "{\"duration\": \"2.250000000s\"}",
+        print_with_fraction_digits(&m, Some(9))
+    );
+    assert_eq!(
+        "{\"duration\": \"2.2s\"}",
+        print_with_fraction_digits(&m, Some(1))
+    );
+    assert_eq!(
+        "{\"duration\": \"2s\"}",
+        print_with_fraction_digits(&m, Some(0))
+    );
+
+    // microsecond precision
+    m.mut_duration().nanos = 250_001_000;
+    assert_eq!(
+        "{\"duration\": \"2.250001s\"}",
+        print_with_fraction_digits(&m, None)
+    );
+
+    // nanosecond precision
+    m.mut_duration().nanos = 250_000_001;
+    assert_eq!(
+        "{\"duration\": \"2.250000001s\"}",
+        print_with_fraction_digits(&m, None)
+    );
+    assert_eq!(
+        "{\"duration\": \"2.250s\"}",
+        print_with_fraction_digits(&m, Some(3))
+    );
+    // at most 9 digits
+    assert_eq!(
+        "{\"duration\": \"2.250000001s\"}",
+        print_with_fraction_digits(&m, Some(12))
+    );
+
+    m.mut_duration().seconds = -2;
+    m.mut_duration().nanos = -500_000_000;
+    assert_eq!(
+        "{\"duration\": \"-2.500s\"}",
+        print_with_fraction_digits(&m, None)
+    );
+    test_json_parse_message(&print_with_fraction_digits(&m, None), &m);
+}
+
+#[test]
+fn test_timestamp_fraction_digits() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+
+    m.mut_timestamp().seconds = 1535585179;
+    assert_eq!(
+        "{\"timestamp\": \"2018-08-29T23:26:19Z\"}",
+        print_with_fraction_digits(&m, None)
+    );
+    assert_eq!(
+        "{\"timestamp\": \"2018-08-29T23:26:19.000000Z\"}",
+        print_with_fraction_digits(&m, Some(6))
+    );
+
+    // millisecond precision
+    m.mut_timestamp().nanos = 123_000_000;
+    assert_eq!(
+        "{\"timestamp\": \"2018-08-29T23:26:19.123Z\"}",
+        print_with_fraction_digits(&m, None)
+    );
+    assert_eq!(
+        "{\"timestamp\": \"2018-08-29T23:26:19.123000000Z\"}",
+        print_with_fraction_digits(&m, Some(9))
+    );
+    test_json_parse_message(&print_with_fraction_digits(&m, Some(9)), &m);
+
+    // nanosecond precision
+    m.mut_timestamp().nanos = 123_456_789;
+    assert_eq!(
+        "{\"timestamp\": \"2018-08-29T23:26:19.123456789Z\"}",
+        print_with_fraction_digits(&m, None)
+    );
+    assert_eq!(
+        "{\"timestamp\": \"2018-08-29T23:26:19.123Z\"}",
+        print_with_fraction_digits(&m, Some(3))
+    );
+    assert_eq!(
+        "{\"timestamp\": \"2018-08-29T23:26:19Z\"}",
+        print_with_fraction_digits(&m, Some(0))
+    );
+    test_json_parse_message(&print_with_fraction_digits(&m, None), &m);
+}
+
 #[test]
 fn test_null_value() {
     let mut m = TestFmtJsonWellKnownTypes::new();
@@ -68,6 +188,34 @@ fn test_value() {
     test_json_print_parse_message("{\"value\": {}}", &m);
 }
 
+#[test]
+fn test_value_arbitrary_json() {
+    let s = "{\"value\": {\"a\": [null, true, 1.5, \"x\", {\"b\": []}]}}";
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    json::merge_from_str(&mut m, s).expect("parse");
+
+    let a = m.get_value().get_struct_value().fields.get("a").unwrap();
+    let a = &a.get_list_value().values;
+    assert_eq!(5, a.len());
+    assert!(a[0].has_null_value());
+    assert_eq!(true, a[1].get_bool_value());
+    assert_eq!(1.5, a[2].get_number_value());
+    assert_eq!("x", a[3].get_string_value());
+    let b = a[4].get_struct_value().fields.get("b").unwrap();
+    assert!(b.get_list_value().values.is_empty());
+
+    assert_eq!(s, json::print_to_string(&m).expect("print_to_string"));
+}
+
+#[test]
+fn test_value_not_finite() {
+    for n in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let mut m = TestFmtJsonWellKnownTypes::new();
+        m.mut_value().set_number_value(*n);
+        assert!(json::print_to_string(&m).is_err());
+    }
+}
+
 #[test]
 fn test_list_value() {
     let mut m = TestFmtJsonWellKnownTypes::new();
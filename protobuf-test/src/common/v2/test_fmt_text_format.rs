@@ -186,7 +186,88 @@ fn test_reflect() {
 fn test_parse_error() {
     let e = protobuf::text_format::parse_from_str::<TestTypes>("nonexistent: 42").unwrap_err();
     let _error: &dyn std::error::Error = &e;
-    assert_eq!(e.to_string(), "1:1: UnknownField(\"nonexistent\")");
+    assert_eq!(e.to_string(), "unknown field `nonexistent` at 1:1");
+}
+
+#[test]
+fn test_parse_error_out_of_range() {
+    fn error(input: &str) -> String {
+        protobuf::text_format::parse_from_str::<TestTypes>(input)
+            .unwrap_err()
+            .to_string()
+    }
+
+    assert_eq!(
+        "value 99999999999 out of range for int32 field int32_singular at 2:17",
+        error("uint32_singular: 1\nint32_singular: 99999999999")
+    );
+    assert_eq!(
+        "value -1 out of range for uint64 field uint64_singular at 1:18",
+        error("uint64_singular: -1")
+    );
+    assert_eq!(
+        "value 4294967296 out of range for uint32 field double_map_field.key at 1:25",
+        error("double_map_field { key: 4294967296 }")
+    );
+    assert!(protobuf::text_format::parse_from_str::<TestTypes>(
+        "int32_singular: -2147483648 int64_singular: -9223372036854775808"
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_parse_error_invalid_escape() {
+    let e = protobuf::text_format::parse_from_str::<TestTypes>(
+        "int32_singular: 1\nstring_singular: \"ab\\qc\"",
+    )
+    .unwrap_err();
+    assert_eq!(
+        "Invalid escape sequence `\\q` in string literal at 2:21",
+        e.to_string()
+    );
+}
+
+#[test]
+fn test_parse_utf8() {
+    let m: TestTypes =
+        protobuf::text_format::parse_from_str("string_singular: \"caf\u{e9}\"").unwrap();
+    assert_eq!("caf\u{e9}", m.get_string_singular());
+
+    let m: TestTypes =
+        protobuf::text_format::parse_from_str(r#"string_singular: "caf\u00e9 \U0001F600""#)
+            .unwrap();
+    assert_eq!("caf\u{e9} \u{1f600}", m.get_string_singular());
+
+    let m: TestTypes =
+        protobuf::text_format::parse_from_str(r#"bytes_singular: "\u00e9""#).unwrap();
+    assert_eq!(b"\xc3\xa9", m.get_bytes_singular());
+}
+
+#[test]
+fn test_parse_error_invalid_utf8() {
+    let input = "int32_singular: 1\nstring_singular: \"ab\\377\"";
+    let e = protobuf::text_format::parse_from_str::<TestTypes>(input).unwrap_err();
+    assert_eq!(
+        "invalid UTF-8 in string field string_singular: \
+        invalid utf-8 sequence of 1 bytes from index 2 at 2:18",
+        e.to_string()
+    );
+
+    let mut parse_options = protobuf::text_format::ParseOptions::default();
+    parse_options.replace_invalid_utf8 = true;
+    let m: TestTypes =
+        protobuf::text_format::parse_from_str_with_options(input, &parse_options).unwrap();
+    assert_eq!("ab\u{fffd}", m.get_string_singular());
+}
+
+#[test]
+fn test_parse_error_invalid_unicode_escape() {
+    let e = protobuf::text_format::parse_from_str::<TestTypes>(r#"string_singular: "\ud800""#)
+        .unwrap_err();
+    assert_eq!(
+        "Invalid escape sequence `\\ud800` in string literal at 1:19",
+        e.to_string()
+    );
 }
 
 fn t<F: FnMut(&mut TestTypes)>(expected: &str, mut setter: F) {
@@ -283,3 +364,37 @@ fn test_string_escaped() {
         &*format!("{:?}", m)
     );
 }
+
+#[test]
+fn test_field_aliases() {
+    let mut parse_options = protobuf::text_format::ParseOptions::default();
+    parse_options
+        .field_aliases
+        .insert("old_uint32".to_owned(), "uint32_singular".to_owned());
+    parse_options
+        .field_aliases
+        .insert("old_value".to_owned(), "value".to_owned());
+
+    let m: TestTypes = protobuf::text_format::parse_from_str_with_options(
+        "old_uint32: 30 test_message_singular { old_value: 40 }",
+        &parse_options,
+    )
+    .unwrap();
+
+    let mut expected = TestTypes::new();
+    expected.set_uint32_singular(30);
+    expected
+        .test_message_singular
+        .mut_or_default()
+        .set_value(40);
+    assert_eq!(expected, m);
+
+    // Actual field name takes precedence and still works
+    let m: TestTypes =
+        protobuf::text_format::parse_from_str_with_options("uint32_singular: 30", &parse_options)
+            .unwrap();
+    assert_eq!(30, m.get_uint32_singular());
+
+    // Aliases are not used by default
+    assert!(protobuf::text_format::parse_from_str::<TestTypes>("old_uint32: 30").is_err());
+}
@@ -0,0 +1,40 @@
+use std::collections::HashSet;
+
+use super::test_impl_hash_pb::key::Nested;
+use super::test_impl_hash_pb::*;
+
+fn assert_hash_impl<T: ::std::hash::Hash + Eq>() {}
+
+fn key(name: &str) -> Key {
+    let mut k = Key::new();
+    k.set_name(name.to_owned());
+    k.set_id(17);
+    k.set_color(Color::VERDE);
+    k.mut_path().extend(&[1, 2, 3]);
+    k.set_digest(vec![0xde, 0xad]);
+    k.mut_nested().set_flag(true);
+    k.set_version_name("v1".to_owned());
+    k
+}
+
+#[test]
+fn test_impl_hash() {
+    assert_hash_impl::<Key>();
+    assert_hash_impl::<Nested>();
+    assert_hash_impl::<key::Version>();
+}
+
+#[test]
+fn test_hash_set() {
+    let mut set = HashSet::new();
+    assert!(set.insert(key("a")));
+    assert!(set.insert(key("b")));
+    assert!(!set.insert(key("a")));
+    assert!(set.insert(Key::new()));
+    assert_eq!(3, set.len());
+
+    let mut other_version = key("a");
+    other_version.set_version_number(1);
+    assert!(!set.contains(&other_version));
+    assert!(set.contains(&key("b")));
+}
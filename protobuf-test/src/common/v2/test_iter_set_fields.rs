@@ -0,0 +1,70 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::ReflectFieldRef;
+use protobuf::MessageDyn;
+
+use super::test_iter_set_fields_pb::*;
+
+fn set_field_names(m: &dyn MessageDyn) -> Vec<String> {
+    m.iter_set_fields()
+        .map(|(f, _)| f.get_name().to_owned())
+        .collect()
+}
+
+#[test]
+fn test_empty() {
+    assert!(set_field_names(&ForIterSetFields::new()).is_empty());
+}
+
+#[test]
+fn test_set_fields() {
+    let mut m = ForIterSetFields::new();
+    m.set_s("abc".to_owned());
+    m.mut_inner();
+    m.set_r(vec![1, 2]);
+    m.set_second("x".to_owned());
+    assert_eq!(vec!["s", "inner", "r", "second"], set_field_names(&m));
+
+    m.set_first(10);
+    m.set_i(20);
+    assert_eq!(vec!["i", "s", "inner", "r", "first"], set_field_names(&m));
+}
+
+#[test]
+fn test_values() {
+    let mut m = ForIterSetFields::new();
+    m.set_i(20);
+    m.mut_m().insert("k".to_owned(), 1);
+    let m: &dyn MessageDyn = &m;
+    let values: Vec<_> = m.iter_set_fields().collect();
+    assert_eq!(2, values.len());
+    match &values[0].1 {
+        ReflectFieldRef::Optional(Some(v)) => assert_eq!(Some(20), v.to_i32()),
+        _ => panic!("unexpected value"),
+    }
+    match &values[1].1 {
+        ReflectFieldRef::Map(map) => assert_eq!(1, map.len()),
+        _ => panic!("unexpected value"),
+    }
+}
+
+#[test]
+fn test_dynamic() {
+    let file_descriptor = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![protobuf::rustproto::file_descriptor()],
+    );
+    let d = file_descriptor
+        .message_by_package_relative_name("ForIterSetFieldsDynamic")
+        .unwrap();
+    let mut m = d.new_instance();
+    assert!(set_field_names(&*m).is_empty());
+
+    d.get_field_by_name("first")
+        .unwrap()
+        .set_singular_field(&mut *m, 10i32.into());
+    d.get_field_by_name("r")
+        .unwrap()
+        .mut_repeated(&mut *m)
+        .push(1i32.into());
+    assert_eq!(vec!["r", "first"], set_field_names(&*m));
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use super::test_map_conversions_pb::*;
+
+fn item(name: &str, id: i32) -> Item {
+    let mut item = Item::new();
+    item.set_name(name.to_owned());
+    item.set_id(id);
+    item
+}
+
+#[test]
+fn test_map_to_vec() {
+    let mut m = WithMaps::new();
+    m.counts.insert("b".to_owned(), 2);
+    m.counts.insert("a".to_owned(), 1);
+    m.counts.insert("c".to_owned(), 3);
+
+    let mut v = m.counts_to_vec();
+    v.sort();
+    assert_eq!(
+        vec![
+            ("a".to_owned(), 1),
+            ("b".to_owned(), 2),
+            ("c".to_owned(), 3)
+        ],
+        v
+    );
+    assert_eq!(v, m.counts_to_sorted_vec());
+}
+
+#[test]
+fn test_map_from_vec() {
+    let mut m = WithMaps::new();
+    m.items.insert(10, item("old", 10));
+    m.set_items_from_vec(vec![
+        (2, item("x", 2)),
+        (1, item("y", 1)),
+        (2, item("z", 2)),
+    ]);
+    assert_eq!(
+        vec![(1, item("y", 1)), (2, item("z", 2))],
+        m.items_to_sorted_vec()
+    );
+}
+
+#[test]
+fn test_repeated_to_map() {
+    let mut m = WithMaps::new();
+    m.by_name = vec![item("a", 1), item("b", 2), item("a", 3)];
+    m.by_id = vec![item("a", 1), item("b", 2)];
+
+    let mut expected = HashMap::new();
+    expected.insert("a".to_owned(), item("a", 3));
+    expected.insert("b".to_owned(), item("b", 2));
+    assert_eq!(expected, m.by_name_to_map());
+
+    let mut expected = HashMap::new();
+    expected.insert(1, item("a", 1));
+    expected.insert(2, item("b", 2));
+    assert_eq!(expected, m.by_id_to_map());
+}
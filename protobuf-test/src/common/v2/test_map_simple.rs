@@ -1,4 +1,9 @@
 use protobuf::text_format::print_to_string;
+use protobuf::Message;
+use protobuf::ParseOptions;
+use protobuf::ProtobufError;
+use protobuf::WireError;
+use protobuf_test_common::hex::decode_hex;
 use protobuf_test_common::*;
 
 use super::test_map_simple_pb::*;
@@ -31,6 +36,25 @@ fn test_map_negative_i32_value() {
     );
 }
 
+#[test]
+fn test_map_value_integer_overflow() {
+    // `me` entry with key 0 and enum value 2^32
+    let bytes = decode_hex("1a 0f 09 00 00 00 00 00 00 00 00 10 80 80 80 80 10");
+    let options = ParseOptions {
+        reject_integer_overflow: true,
+        ..Default::default()
+    };
+    match TestMap::parse_from_bytes_with_options(&bytes, &options) {
+        Err(ProtobufError::WireError(WireError::IntegerOverflow { field_number })) => {
+            assert_eq!(3, field_number)
+        }
+        r => panic!("expecting integer overflow: {:?}", r),
+    }
+
+    let map = TestMap::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(Some(&TestMapEnum::UNKNOWN.into()), map.get_me().get(&0));
+}
+
 #[test]
 fn test_map_with_object() {
     let mut map = TestMap::new();
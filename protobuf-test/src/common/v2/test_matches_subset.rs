@@ -0,0 +1,107 @@
+use protobuf::Message;
+
+use super::test_matches_subset_pb::*;
+
+fn item(name: &str, count: i32) -> Item {
+    let mut item = Item::new();
+    item.set_name(name.to_owned());
+    item.set_count(count);
+    item
+}
+
+fn response() -> Response {
+    let mut m = Response::new();
+    m.set_code(200);
+    m.set_message("ok".to_owned());
+    m.set_item(item("a", 1));
+    m.set_items(vec![item("b", 2), item("c", 3)]);
+    m.set_ids(vec![1, 2, 3]);
+    m.mut_counts().insert("x".to_owned(), 10);
+    m.mut_counts().insert("y".to_owned(), 20);
+    m
+}
+
+#[test]
+fn test_empty_pattern() {
+    assert!(response().matches_subset(&Response::new()));
+    assert!(Response::new().matches_subset(&Response::new()));
+    assert!(response().matches_subset(&response()));
+}
+
+#[test]
+fn test_singular() {
+    let mut pattern = Response::new();
+    pattern.set_code(200);
+    assert!(response().matches_subset(&pattern));
+
+    pattern.set_code(404);
+    assert!(!response().matches_subset(&pattern));
+
+    let mut pattern = Response::new();
+    pattern.set_code(200);
+    assert!(!Response::new().matches_subset(&pattern));
+}
+
+#[test]
+fn test_nested_message() {
+    let mut pattern = Response::new();
+    pattern.mut_item().set_name("a".to_owned());
+    assert!(response().matches_subset(&pattern));
+
+    pattern.mut_item().set_count(2);
+    assert!(!response().matches_subset(&pattern));
+
+    // Empty message matches any set message, but not unset message.
+    let mut pattern = Response::new();
+    pattern.mut_item();
+    assert!(response().matches_subset(&pattern));
+    assert!(!Response::new().matches_subset(&pattern));
+}
+
+#[test]
+fn test_repeated() {
+    let mut pattern = Response::new();
+    pattern.set_ids(vec![1, 2, 3]);
+    assert!(response().matches_subset(&pattern));
+
+    // Length must be equal.
+    pattern.set_ids(vec![1, 2]);
+    assert!(!response().matches_subset(&pattern));
+
+    // Elements are compared in order.
+    pattern.set_ids(vec![3, 2, 1]);
+    assert!(!response().matches_subset(&pattern));
+
+    // Message elements are compared as subsets.
+    let mut pattern = Response::new();
+    let mut b = Item::new();
+    b.set_name("b".to_owned());
+    pattern.set_items(vec![b, Item::new()]);
+    assert!(response().matches_subset(&pattern));
+
+    pattern.mut_items()[1].set_name("b".to_owned());
+    assert!(!response().matches_subset(&pattern));
+}
+
+#[test]
+fn test_map() {
+    let mut pattern = Response::new();
+    pattern.mut_counts().insert("x".to_owned(), 10);
+    assert!(response().matches_subset(&pattern));
+
+    pattern.mut_counts().insert("x".to_owned(), 11);
+    assert!(!response().matches_subset(&pattern));
+
+    let mut pattern = Response::new();
+    pattern.mut_counts().insert("z".to_owned(), 10);
+    assert!(!response().matches_subset(&pattern));
+}
+
+#[test]
+fn test_dyn() {
+    let mut pattern = Response::new();
+    pattern.set_message("ok".to_owned());
+    let m: &dyn protobuf::MessageDyn = &response();
+    assert!(m.matches_subset_dyn(&pattern));
+    assert!(!m.matches_subset_dyn(&Item::new()));
+}
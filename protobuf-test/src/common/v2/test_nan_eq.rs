@@ -0,0 +1,70 @@
+use super::test_nan_eq_pb::measurement::Nested;
+use super::test_nan_eq_pb::*;
+
+fn assert_eq_impl<T: Eq>() {}
+
+fn nan_measurement() -> Measurement {
+    let mut m = Measurement::new();
+    m.set_value(f64::NAN);
+    m.set_error(f32::NAN);
+    m.set_name("nan".to_owned());
+    m.set_unit(Unit::FOOT);
+    m.mut_samples().push(f32::NAN);
+    m.mut_weights().insert("a".to_owned(), f64::NAN);
+    m.mut_nested().set_x(f32::NAN);
+    m.set_reading_double(f64::NAN);
+    m
+}
+
+#[test]
+fn test_nan_equals_nan() {
+    assert_eq_impl::<Measurement>();
+    assert_eq_impl::<Nested>();
+
+    assert_eq!(nan_measurement(), nan_measurement());
+    let m = nan_measurement();
+    assert_eq!(m, m.clone());
+}
+
+#[test]
+fn test_fields_compared() {
+    let m = nan_measurement();
+
+    let mut other = m.clone();
+    other.set_value(1.0);
+    assert_ne!(m, other);
+
+    let mut other = m.clone();
+    other.set_name("other".to_owned());
+    assert_ne!(m, other);
+
+    let mut other = m.clone();
+    other.mut_samples().push(f32::NAN);
+    assert_ne!(m, other);
+
+    let mut other = m.clone();
+    other.mut_weights().insert("a".to_owned(), 1.0);
+    assert_ne!(m, other);
+
+    let mut other = m.clone();
+    other.mut_nested().set_x(1.0);
+    assert_ne!(m, other);
+
+    let mut other = m.clone();
+    other.set_reading_text("nan".to_owned());
+    assert_ne!(m, other);
+}
+
+#[test]
+fn test_bit_pattern() {
+    let mut a = Measurement::new();
+    a.set_value(1.0);
+    let mut b = Measurement::new();
+    b.set_value(1.0);
+    assert_eq!(a, b);
+
+    // Values are compared by bits, so zeros of different sign are not equal
+    a.set_value(0.0);
+    b.set_value(-0.0);
+    assert_ne!(a, b);
+}
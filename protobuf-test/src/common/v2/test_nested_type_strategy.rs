@@ -0,0 +1,43 @@
+use protobuf::Message;
+use protobuf::ProtobufEnum;
+
+use super::test_nested_type_strategy_pb::*;
+
+#[test]
+fn flattened_names() {
+    let mut leaf = Outer_Inner_Leaf::new();
+    leaf.set_value(10);
+    leaf.set_color(Outer_Inner_Color::COLOR_RED);
+
+    let mut inner = Outer_Inner::new();
+    inner.mut_leaves().push(leaf.clone());
+    inner.set_leaf(leaf.clone());
+
+    let mut outer = Outer::new();
+    outer.set_inner(inner);
+    outer.set_leaf(leaf.clone());
+    outer.set_color(Outer_Inner_Color::COLOR_RED);
+    outer.choice = Some(Outer_Choice::choice_leaf(leaf.clone()));
+
+    let bytes = outer.write_to_bytes().unwrap();
+    let parsed = Outer::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(outer, parsed);
+    assert_eq!(&leaf, parsed.get_choice_leaf());
+
+    let mut other = Other::new();
+    other.set_leaf(leaf);
+    let parsed = Other::parse_from_bytes(&other.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(10, parsed.get_leaf().get_value());
+}
+
+#[test]
+fn reflection_names_are_not_affected() {
+    assert_eq!(
+        "test_nested_type_strategy.Outer.Inner.Leaf",
+        Outer_Inner_Leaf::descriptor_static().full_name()
+    );
+    assert_eq!(
+        "test_nested_type_strategy.Outer.Inner.Color",
+        Outer_Inner_Color::enum_descriptor_static().full_name()
+    );
+}
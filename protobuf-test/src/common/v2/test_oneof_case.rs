@@ -0,0 +1,29 @@
+use super::test_oneof_case_pb::*;
+
+#[test]
+fn test_which() {
+    let mut m = WithOneofs::new();
+    assert_eq!(with_oneofs::RequestCase::NotSet, m.which_request());
+    assert_eq!(with_oneofs::OtherCase::NotSet, m.which_other());
+
+    m.set_name("n".to_owned());
+    assert_eq!(with_oneofs::RequestCase::name, m.which_request());
+    m.mut_payload().set_value(1);
+    assert_eq!(with_oneofs::RequestCase::payload, m.which_request());
+    m.set_id(2);
+    assert_eq!(with_oneofs::RequestCase::id, m.which_request());
+    assert_eq!(with_oneofs::OtherCase::NotSet, m.which_other());
+
+    m.set_flag(false);
+    assert_eq!(with_oneofs::OtherCase::flag, m.which_other());
+    m.clear_id();
+    assert_eq!(with_oneofs::RequestCase::NotSet, m.which_request());
+}
+
+#[test]
+fn test_which_boxed() {
+    let mut m = Recursive::new();
+    m.mut_child().set_leaf(1);
+    assert_eq!(recursive::NodeCase::child, m.which_node());
+    assert_eq!(recursive::NodeCase::leaf, m.get_child().which_node());
+}
@@ -0,0 +1,74 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::Message;
+
+use super::test_oneof_reflect_pb;
+use super::test_oneof_reflect_pb::WithOneofs;
+
+#[test]
+fn test_containing_oneof() {
+    let d = WithOneofs::descriptor_static();
+
+    assert!(d
+        .get_field_by_name("plain")
+        .unwrap()
+        .containing_oneof()
+        .is_none());
+
+    let first = d.get_field_by_name("first").unwrap();
+    let second = d.get_field_by_name("second").unwrap();
+    let only = d.get_field_by_name("only").unwrap();
+    assert_eq!("real", first.containing_oneof().unwrap().get_name());
+    assert!(first.containing_oneof() == second.containing_oneof());
+    assert_eq!("single", only.containing_oneof().unwrap().get_name());
+    assert!(first.containing_oneof() != only.containing_oneof());
+}
+
+#[test]
+fn test_is_synthetic() {
+    let d = WithOneofs::descriptor_static();
+
+    let real = d
+        .get_field_by_name("first")
+        .unwrap()
+        .containing_oneof()
+        .unwrap();
+    assert!(!real.is_synthetic());
+    assert_eq!(
+        vec!["first", "second"],
+        real.fields()
+            .map(|f| f.get_name().to_owned())
+            .collect::<Vec<_>>()
+    );
+
+    // Explicitly declared oneof with single field is not synthetic
+    let single = d
+        .get_field_by_name("only")
+        .unwrap()
+        .containing_oneof()
+        .unwrap();
+    assert!(!single.is_synthetic());
+}
+
+#[test]
+fn test_is_synthetic_proto3_optional() {
+    // Make descriptor like `protoc` does for proto3 `optional int32 only = 4;`
+    let mut proto = test_oneof_reflect_pb::file_descriptor().proto().clone();
+    proto.set_syntax("proto3".to_owned());
+    let message = &mut proto.message_type[0];
+    let only = message
+        .field
+        .iter_mut()
+        .find(|f| f.get_name() == "only")
+        .unwrap();
+    only.set_proto3_optional(true);
+
+    let file_descriptor = FileDescriptor::new_dynamic(proto, Vec::new());
+    let d = file_descriptor
+        .message_by_package_relative_name("WithOneofs")
+        .unwrap();
+
+    let only = d.get_field_by_name("only").unwrap();
+    assert!(only.containing_oneof().unwrap().is_synthetic());
+    let first = d.get_field_by_name("first").unwrap();
+    assert!(!first.containing_oneof().unwrap().is_synthetic());
+}
@@ -0,0 +1,87 @@
+use protobuf::Message;
+
+use super::test_parse_prefix_pb::*;
+
+fn routing() -> Routing {
+    let mut m = Routing::new();
+    m.set_destination("backend".to_owned());
+    m.set_priority(3);
+    m.set_payload(vec![1, 2, 3, 4, 5]);
+    m.set_tail(vec![7, 8]);
+    m
+}
+
+#[test]
+fn test_up_to_field_number() {
+    let bytes = routing().write_to_bytes().unwrap();
+
+    let m = Routing::parse_prefix_from_bytes(&bytes, 2, usize::MAX).unwrap();
+    assert_eq!("backend", m.get_destination());
+    assert_eq!(3, m.get_priority());
+    assert!(m.get_payload().is_empty());
+    assert!(m.get_tail().is_empty());
+
+    let m = Routing::parse_prefix_from_bytes(&bytes, 100, usize::MAX).unwrap();
+    assert_eq!(routing(), m);
+}
+
+#[test]
+fn test_max_bytes() {
+    let bytes = routing().write_to_bytes().unwrap();
+
+    let mut destination_only = Routing::new();
+    destination_only.set_destination("backend".to_owned());
+    // tag, length and 7 bytes of `destination`
+    let destination_len = 9;
+
+    let m = Routing::parse_prefix_from_bytes(&bytes, 100, destination_len).unwrap();
+    assert_eq!(destination_only, m);
+
+    // Field which does not fit entirely is not read
+    let m = Routing::parse_prefix_from_bytes(&bytes, 100, destination_len + 1).unwrap();
+    assert_eq!(destination_only, m);
+
+    let m = Routing::parse_prefix_from_bytes(&bytes, 100, 0).unwrap();
+    assert_eq!(Routing::new(), m);
+}
+
+#[test]
+fn test_truncated_input_before_threshold_is_error() {
+    let bytes = routing().write_to_bytes().unwrap();
+    assert!(Routing::parse_prefix_from_bytes(&bytes[..5], 2, usize::MAX).is_err());
+}
+
+#[test]
+fn test_parse_from_bytes_prefix() {
+    let bytes = routing().write_to_bytes().unwrap();
+    let len = bytes.len();
+
+    let (m, consumed) = Routing::parse_from_bytes_prefix(&bytes).unwrap();
+    assert_eq!(routing(), m);
+    assert_eq!(len, consumed);
+
+    for trailer in &[
+        // zero padding
+        &[0, 0, 0, 0][..],
+        // end group tag
+        &[(1 << 3) | 4, 0x11],
+        // unknown wire type
+        &[(1 << 3) | 6],
+        // incomplete varint
+        &[0x80],
+    ] {
+        let mut with_trailer = bytes.clone();
+        with_trailer.extend_from_slice(trailer);
+        let (m, consumed) = Routing::parse_from_bytes_prefix(&with_trailer).unwrap();
+        assert_eq!(routing(), m);
+        assert_eq!(len, consumed);
+    }
+}
+
+#[test]
+fn test_parse_from_bytes_prefix_malformed_field_is_error() {
+    let mut bytes = routing().write_to_bytes().unwrap();
+    // valid tag of length-delimited field with length past the end of input
+    bytes.extend_from_slice(&[(1 << 3) | 2, 100]);
+    assert!(Routing::parse_from_bytes_prefix(&bytes).is_err());
+}
@@ -0,0 +1,59 @@
+use protobuf::Message;
+
+use super::test_parse_selective_pb::*;
+
+fn envelope() -> Envelope {
+    let mut m = Envelope::new();
+    m.set_route("backend".to_owned());
+    m.set_priority(3);
+    m.set_payload(vec![0; 1000]);
+    m.set_tags(vec![1, 2, 3]);
+    m.mut_header().set_trace_id("abc".to_owned());
+    m
+}
+
+fn parse_selective(bytes: &[u8], keep_fields: &[u32]) -> Envelope {
+    let m = Envelope::descriptor_static()
+        .parse_selective(bytes, keep_fields)
+        .unwrap();
+    *m.downcast_box::<Envelope>().unwrap()
+}
+
+#[test]
+fn test_keep_fields() {
+    let bytes = envelope().write_to_bytes().unwrap();
+
+    let m = parse_selective(&bytes, &[1, 4, 5]);
+    let mut expected = Envelope::new();
+    expected.set_route("backend".to_owned());
+    expected.set_tags(vec![1, 2, 3]);
+    expected.mut_header().set_trace_id("abc".to_owned());
+    assert_eq!(expected, m);
+    // Skipped fields are not stored in unknown fields
+    assert_eq!(
+        expected.write_to_bytes().unwrap(),
+        m.write_to_bytes().unwrap()
+    );
+
+    assert_eq!(envelope(), parse_selective(&bytes, &[1, 2, 3, 4, 5]));
+    assert_eq!(Envelope::new(), parse_selective(&bytes, &[]));
+}
+
+#[test]
+fn test_unknown_field_kept() {
+    let mut bytes = envelope().write_to_bytes().unwrap();
+    // field 15 varint 7
+    bytes.extend_from_slice(&[15 << 3, 7]);
+
+    let m = parse_selective(&bytes, &[15]);
+    assert!(m.get_route().is_empty());
+    assert_eq!(vec![15 << 3, 7], m.write_to_bytes().unwrap());
+}
+
+#[test]
+fn test_truncated_skipped_field_is_error() {
+    let bytes = envelope().write_to_bytes().unwrap();
+    assert!(Envelope::descriptor_static()
+        .parse_selective(&bytes[..bytes.len() - 10], &[1])
+        .is_err());
+}
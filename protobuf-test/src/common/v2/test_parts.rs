@@ -0,0 +1,66 @@
+use protobuf::Message;
+
+use super::test_parts_pb::row;
+use super::test_parts_pb::*;
+
+fn row() -> Row {
+    let mut parent = Row::new();
+    parent.set_id(1);
+
+    let mut r = Row::new();
+    r.set_id(2);
+    r.set_name("two".to_owned());
+    r.set_payload(b"payload".to_vec());
+    r.mut_values().extend(&[3, 4]);
+    r.mut_counters().insert("c".to_owned(), 5);
+    r.set_parent(parent);
+    r.set_kind(Kind::KIND_A);
+    r.set_key_name("k".to_owned());
+    r
+}
+
+#[test]
+fn into_parts_into_message() {
+    let r = row();
+    let parts = r.clone().into_parts();
+    assert_eq!(vec![3, 4], parts.values);
+    assert_eq!(Some(&5), parts.counters.get("c"));
+    assert_eq!(1, parts.parent.get_or_default().get_id());
+    assert_eq!(Some(row::Key::key_name("k".to_owned())), parts.key);
+
+    let message = parts.into_message();
+    assert_eq!(r, message);
+    assert_eq!(
+        r.write_to_bytes().unwrap(),
+        message.write_to_bytes().unwrap()
+    );
+}
+
+#[test]
+fn unknown_fields() {
+    let mut r = row();
+    r.mut_unknown_fields().add_varint(100, 17);
+    let bytes = r.write_to_bytes().unwrap();
+
+    let parts = Row::parse_from_bytes(&bytes).unwrap().into_parts();
+    assert_eq!(
+        Some(17),
+        parts
+            .unknown_fields
+            .get(100)
+            .and_then(|v| v.varint.first().cloned())
+    );
+    assert_eq!(bytes, parts.into_message().write_to_bytes().unwrap());
+}
+
+#[test]
+fn default_parts() {
+    assert_eq!(Row::new(), RowParts::default().into_message());
+}
+
+#[test]
+fn nested_message_parts() {
+    let mut nested = row::Nested::new();
+    nested.set_n(10);
+    assert_eq!(nested, nested.clone().into_parts().into_message());
+}
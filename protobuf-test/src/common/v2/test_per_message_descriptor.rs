@@ -0,0 +1,34 @@
+use protobuf::Message;
+
+use super::test_per_message_descriptor_pb::*;
+
+#[test]
+fn test_message_descriptor_proto() {
+    assert_eq!("First", First::message_descriptor_proto().get_name());
+    assert_eq!("Outer", Outer::message_descriptor_proto().get_name());
+    assert_eq!("Inner", outer::Inner::message_descriptor_proto().get_name());
+    assert_eq!(
+        "Deepest",
+        outer::inner::Deepest::message_descriptor_proto().get_name()
+    );
+}
+
+#[test]
+fn test_same_as_reflection() {
+    assert_eq!(
+        First::descriptor_static().get_proto(),
+        First::message_descriptor_proto()
+    );
+    assert_eq!(
+        outer::Inner::descriptor_static().get_proto(),
+        outer::Inner::message_descriptor_proto()
+    );
+}
+
+#[test]
+fn test_static_reference() {
+    let a: &'static _ = Outer::message_descriptor_proto();
+    let b: &'static _ = Outer::message_descriptor_proto();
+    assert!(std::ptr::eq(a, b));
+    assert_eq!(2, a.field.len());
+}
@@ -0,0 +1,59 @@
+use protobuf::FieldPresenceSet;
+
+use super::test_presence_set_pb::*;
+
+fn presence_set(numbers: &[u32]) -> FieldPresenceSet {
+    let mut r = FieldPresenceSet::new();
+    for &n in numbers {
+        r.insert(n);
+    }
+    r
+}
+
+#[test]
+fn test_empty() {
+    assert!(WithPresenceSet::new().fields_present().is_empty());
+}
+
+#[test]
+fn test_fields_present() {
+    let mut m = WithPresenceSet::new();
+    m.set_i(7);
+    m.set_s("s".to_owned());
+    m.mut_m();
+    assert_eq!(presence_set(&[1, 2, 3]), m.fields_present());
+
+    m.mut_r().push(10);
+    m.mut_map().insert("a".to_owned(), 1);
+    m.set_far(17);
+    assert_eq!(presence_set(&[1, 2, 3, 4, 5, 100]), m.fields_present());
+    assert!(m.fields_present().contains(100));
+}
+
+#[test]
+fn test_oneof() {
+    let mut m = WithPresenceSet::new();
+    m.set_one_i(1);
+    assert_eq!(presence_set(&[10]), m.fields_present());
+    m.set_one_s("x".to_owned());
+    assert_eq!(presence_set(&[11]), m.fields_present());
+}
+
+#[test]
+fn test_compare() {
+    let mut a = WithPresenceSet::new();
+    a.set_i(1);
+    a.set_far(2);
+    let mut b = WithPresenceSet::new();
+    b.set_i(3);
+
+    assert_ne!(a.fields_present(), b.fields_present());
+    assert_eq!(
+        presence_set(&[100]),
+        a.fields_present().difference(&b.fields_present())
+    );
+    b.clear_i();
+    b.set_far(4);
+    b.set_i(5);
+    assert_eq!(a.fields_present(), b.fields_present());
+}
@@ -265,3 +265,15 @@ fn test_json_name() {
     // the descriptor itself.
     assert_eq!("subM", field_descriptor.get_proto().get_json_name());
 }
+
+#[test]
+fn test_cached_size_dyn() {
+    let mut m = M::new();
+    m.mut_sub_m().set_n(42);
+    let m: &dyn MessageDyn = &m;
+    assert_eq!(0, m.get_cached_size_dyn());
+    let size = m.compute_size_dyn();
+    assert_ne!(0, size);
+    assert_eq!(size, m.get_cached_size_dyn());
+    assert_eq!(size as usize, m.write_to_bytes_dyn().unwrap().len());
+}
@@ -0,0 +1,36 @@
+use super::test_repeated_index_pb::*;
+
+fn point(x: i32, y: i32) -> Point {
+    let mut p = Point::new();
+    p.set_x(x);
+    p.set_y(y);
+    p
+}
+
+#[test]
+fn test_index() {
+    let mut polyline = Polyline::new();
+    polyline.set_name("zigzag".to_owned());
+    polyline.mut_points().push(point(1, 2));
+    polyline.mut_points().push(point(3, 4));
+
+    assert_eq!(3, polyline[1].get_x());
+    assert_eq!(&point(1, 2), &polyline[0]);
+    // Accessor is still generated
+    assert_eq!(2, polyline.get_points().len());
+}
+
+#[test]
+fn test_index_string() {
+    let mut names = Names::new();
+    names.mut_names().push("a".to_owned());
+    names.mut_names().push("b".to_owned());
+    assert_eq!("b", names[1]);
+}
+
+#[test]
+#[should_panic]
+fn test_index_out_of_bounds() {
+    let polyline = Polyline::new();
+    let _ = &polyline[0];
+}
@@ -0,0 +1,78 @@
+use protobuf::Clear;
+use protobuf::Message;
+use protobuf::UnknownValue;
+
+use super::test_retain_fields_pb::*;
+
+fn filled() -> WithRetainFields {
+    let mut m = WithRetainFields::new();
+    m.set_i(7);
+    m.set_s("secret".to_owned());
+    m.mut_m().set_x(3);
+    m.mut_r().push(10);
+    m.mut_map().insert("a".to_owned(), 1);
+    m.set_one_s("x".to_owned());
+    m.mut_unknown_fields()
+        .add_value(1000, UnknownValue::Varint(1));
+    m
+}
+
+// Cleared unknown fields are not equal to never set unknown fields,
+// so compare serialized messages
+fn assert_same(expected: &WithRetainFields, m: &WithRetainFields) {
+    assert_eq!(
+        expected.write_to_bytes().unwrap(),
+        m.write_to_bytes().unwrap()
+    );
+}
+
+#[test]
+fn test_retain_nothing() {
+    let mut m = filled();
+    m.retain_fields(&[]);
+    assert_same(&WithRetainFields::new(), &m);
+}
+
+#[test]
+fn test_retain_some() {
+    let mut m = filled();
+    m.retain_fields(&[1, 4]);
+
+    let mut expected = WithRetainFields::new();
+    expected.set_i(7);
+    expected.mut_r().push(10);
+    assert_same(&expected, &m);
+    assert_eq!(0, m.get_unknown_fields().iter().count());
+}
+
+#[test]
+fn test_retain_all() {
+    let mut m = filled();
+    m.retain_fields(&[1, 2, 3, 4, 5, 10, 11]);
+
+    let mut expected = filled();
+    expected.mut_unknown_fields().clear();
+    assert_eq!(expected, m);
+}
+
+#[test]
+fn test_oneof() {
+    let mut m = filled();
+    // Other variant of the oneof doesn't keep the set variant
+    m.retain_fields(&[10]);
+    assert!(!m.has_one_s());
+
+    let mut m = filled();
+    m.retain_fields(&[11]);
+    assert_eq!("x", m.get_one_s());
+}
+
+#[test]
+fn test_compute_size_after_retain() {
+    let mut m = filled();
+    m.retain_fields(&[2]);
+    let bytes = m.write_to_bytes().unwrap();
+    let parsed = WithRetainFields::parse_from_bytes(&bytes).unwrap();
+    assert_eq!("secret", parsed.get_s());
+    assert_eq!(0, parsed.get_i());
+}
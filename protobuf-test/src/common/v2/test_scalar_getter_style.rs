@@ -0,0 +1,66 @@
+use protobuf::Message;
+use protobuf_test_common::*;
+
+use super::test_scalar_getter_style_pb::*;
+
+#[test]
+fn test_getters() {
+    let m = ByRef::new();
+    assert_eq!(&0, m.get_i());
+    assert_eq!(&false, m.get_b());
+    assert_eq!(&0.0, m.get_d());
+    assert_eq!(&0, m.get_one_i());
+
+    let mut m = ByRef::new();
+    m.set_i(10);
+    m.set_b(true);
+    m.set_d(1.5);
+    m.set_s("abc".to_owned());
+    m.set_color(Color::RED);
+    m.set_by_value(20);
+    m.set_one_i(30);
+
+    let i: &i32 = m.get_i();
+    assert_eq!(&10, i);
+    assert_eq!(&true, m.get_b());
+    assert_eq!(&1.5, m.get_d());
+    assert_eq!("abc", m.get_s());
+    // enums are always returned by value
+    assert_eq!(Color::RED, m.get_color());
+    let by_value: u64 = m.get_by_value();
+    assert_eq!(20, by_value);
+    assert_eq!(&30, m.get_one_i());
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = ByRef::new();
+    m.set_i(10);
+    m.set_one_i(30);
+
+    let descriptor = ByRef::descriptor_static();
+    assert_eq!(
+        Some(protobuf::reflect::ReflectValueRef::I32(10)),
+        descriptor.get_field_by_name("i").unwrap().get_singular(&m)
+    );
+    assert_eq!(
+        Some(protobuf::reflect::ReflectValueRef::I32(30)),
+        descriptor
+            .get_field_by_name("one_i")
+            .unwrap()
+            .get_singular(&m)
+    );
+}
+
+#[test]
+fn test_json_text_format() {
+    let mut m = ByRef::new();
+    m.set_i(10);
+    m.set_b(true);
+    m.set_d(1.5);
+    m.set_by_value(20);
+    m.set_one_i(30);
+
+    test_json_message(&m);
+    test_text_format_message(&m);
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "with-serde")]
+
+use serde_json;
+
+use super::test_serde_default_pb::*;
+
+fn round_trip(m: &WithDefaults) -> String {
+    let serialized = serde_json::to_string(m).unwrap();
+    let deserialized: WithDefaults = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(*m, deserialized);
+    serialized
+}
+
+#[test]
+fn test_empty_fields_skipped() {
+    assert_eq!("{}", round_trip(&WithDefaults::new()));
+
+    let mut m = WithDefaults::new();
+    m.set_i(10);
+    m.set_ints(vec![1, 2]);
+    assert_eq!(r#"{"i":10,"ints":[1,2]}"#, round_trip(&m));
+}
+
+#[test]
+fn test_all_fields() {
+    let mut m = WithDefaults::new();
+    m.set_i(10);
+    m.set_s("abc".to_owned());
+    m.set_b(true);
+    m.set_d(1.5);
+    m.set_color(Color::GREEN);
+    m.mut_inner().set_x(3);
+    m.set_ints(vec![1, 2]);
+    m.mut_map().insert("k".to_owned(), 7);
+    m.set_pasta("penne".to_owned());
+    assert_eq!(
+        r#"{"i":10,"s":"abc","b":true,"d":1.5,"color":"GREEN","inner":{"x":3},"ints":[1,2],"map":{"k":7},"food":{"pasta":"penne"}}"#,
+        round_trip(&m)
+    );
+}
+
+#[test]
+fn test_missing_fields_are_default() {
+    let deserialized: WithDefaults = serde_json::from_str("{}").unwrap();
+    assert_eq!(WithDefaults::new(), deserialized);
+
+    let deserialized: WithDefaults = serde_json::from_str(r#"{"s":"abc"}"#).unwrap();
+    let mut expected = WithDefaults::new();
+    expected.set_s("abc".to_owned());
+    assert_eq!(expected, deserialized);
+}
@@ -8,3 +8,39 @@ fn test_service() {
     let _ = Request::new();
     let _ = Response::new();
 }
+
+#[test]
+fn test_streaming() {
+    let file_descriptor = file_descriptor();
+    let service = file_descriptor
+        .proto()
+        .service
+        .iter()
+        .find(|s| s.get_name() == "StreamingService")
+        .unwrap();
+
+    let streaming: Vec<_> = service
+        .method
+        .iter()
+        .map(|m| {
+            (
+                m.get_name(),
+                m.get_client_streaming(),
+                m.get_server_streaming(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("Unary", false, false),
+            ("ClientStreaming", true, false),
+            ("ServerStreaming", false, true),
+            ("BidiStreaming", true, true),
+        ],
+        streaming
+    );
+
+    let unary = &service.method[0];
+    assert!(!unary.has_client_streaming());
+    assert!(!unary.has_server_streaming());
+}
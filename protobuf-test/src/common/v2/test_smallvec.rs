@@ -0,0 +1,69 @@
+use protobuf::Message;
+use protobuf_test_common::*;
+use smallvec::SmallVec;
+
+use super::test_smallvec_pb::*;
+
+fn new_message() -> TestSmallVec {
+    let mut m = TestSmallVec::new();
+    m.ints.extend_from_slice(&[1, -2, 300]);
+    m.fixeds.push(7);
+    m.strings.push("aa".to_owned());
+    m.strings.push("bb".to_owned());
+    m.bytes.push(b"cc".to_vec());
+    m.messages.push(SmallVecNested::new());
+    m.enums.push(SmallVecEnum::GREEN.into());
+    m.vec_ints.push(17);
+    m.map.insert("k".to_owned(), 20);
+    m
+}
+
+#[test]
+fn test_types() {
+    let m = TestSmallVec::new();
+    let _: &SmallVec<[i32; 2]> = &m.ints;
+    let _: &SmallVec<[u32; 2]> = &m.fixeds;
+    let _: &SmallVec<[String; 2]> = &m.strings;
+    let _: &Vec<i32> = &m.vec_ints;
+
+    let m = TestSmallVecMessageOption::new();
+    let _: &SmallVec<[u64; 4]> = &m.values;
+}
+
+#[test]
+fn test_serialize_deserialize() {
+    let m = new_message();
+    test_serialize_deserialize_no_hex(&m);
+    test_serialize_deserialize_length_delimited(&m);
+
+    let parsed = TestSmallVec::parse_from_bytes(&m.write_to_bytes().unwrap()).unwrap();
+    // Two elements fit inline, three spill to heap.
+    assert!(!parsed.strings.spilled());
+    assert!(parsed.ints.spilled());
+}
+
+#[test]
+fn test_merge() {
+    let m = new_message();
+    let bytes = m.write_to_bytes().unwrap();
+    let mut merged = m.clone();
+    merged.merge_from_bytes(&bytes).unwrap();
+    assert_eq!(&[1, -2, 300, 1, -2, 300], &merged.ints[..]);
+    assert_eq!(&[7, 7], &merged.fixeds[..]);
+    assert_eq!(&[17, 17], &merged.vec_ints[..]);
+    assert_eq!(4, merged.strings.len());
+}
+
+#[test]
+fn test_reflect() {
+    let m = new_message();
+    let field = TestSmallVec::descriptor_static()
+        .get_field_by_name("strings")
+        .unwrap();
+    let repeated = field.get_repeated(&m);
+    assert_eq!(2, repeated.len());
+    assert_eq!(Some("bb"), repeated.get(1).to_str());
+
+    test_text_format_message(&m);
+    test_json_message(&m);
+}
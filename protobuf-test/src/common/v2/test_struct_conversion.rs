@@ -0,0 +1,196 @@
+use protobuf::reflect::from_struct;
+use protobuf::reflect::to_struct;
+use protobuf::well_known_types::value::Kind;
+use protobuf::well_known_types::ListValue;
+use protobuf::well_known_types::NullValue;
+use protobuf::well_known_types::Struct;
+use protobuf::well_known_types::Value;
+use protobuf::Message;
+
+use super::test_struct_conversion_pb::*;
+
+fn value(kind: Kind) -> Value {
+    let mut value = Value::new();
+    value.kind = Some(kind);
+    value
+}
+
+fn point(x: i32, y: i32) -> Point {
+    let mut p = Point::new();
+    p.set_x(x);
+    p.set_y(y);
+    p
+}
+
+fn sample() -> Shape {
+    let mut m = Shape::new();
+    m.set_name("square".to_owned());
+    m.set_id(1 << 60);
+    m.set_sides(4);
+    m.set_area(2.5);
+    m.set_filled(true);
+    m.set_data(b"ab".to_vec());
+    m.set_color(Color::COLOR_RED);
+    m.set_center(point(1, 2));
+    m.set_points(vec![point(0, 0), point(3, 4)]);
+    m.set_tags(vec!["a".to_owned()]);
+    m.mut_labels().insert("env".to_owned(), 3);
+    m.mut_points_by_id().insert(10, point(5, 6));
+    m.set_extra(value(Kind::bool_value(false)));
+    m.set_attributes(attributes());
+    m.set_history(history());
+    m.set_width(1.5);
+    m
+}
+
+fn attributes() -> Struct {
+    let mut s = Struct::new();
+    s.fields
+        .insert("seconds".to_owned(), value(Kind::number_value(1.0)));
+    s
+}
+
+fn history() -> ListValue {
+    let mut list = ListValue::new();
+    list.values
+        .push(value(Kind::string_value("drawn".to_owned())));
+    list
+}
+
+fn parse(s: &Struct) -> Result<Shape, String> {
+    from_struct(s, &Shape::descriptor_static())
+        .map(|m| *m.downcast_box::<Shape>().unwrap())
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn test_to_struct() {
+    let s = to_struct(&sample());
+    let field = |name: &str| &s.fields[name];
+
+    assert_eq!("square", field("name").get_string_value());
+    assert_eq!((1i64 << 60).to_string(), field("id").get_string_value());
+    assert_eq!(4.0, field("sides").get_number_value());
+    assert_eq!(2.5, field("area").get_number_value());
+    assert_eq!(true, field("filled").get_bool_value());
+    assert_eq!("YWI=", field("data").get_string_value());
+    assert_eq!("COLOR_RED", field("color").get_string_value());
+    assert_eq!(1.5, field("width").get_number_value());
+    assert!(!s.fields.contains_key("radius"));
+
+    let center = field("center").get_struct_value();
+    assert_eq!(1.0, center.fields["x"].get_number_value());
+    assert_eq!(2.0, center.fields["y"].get_number_value());
+
+    let points = field("points").get_list_value();
+    assert_eq!(2, points.values.len());
+    assert_eq!(
+        4.0,
+        points.values[1].get_struct_value().fields["y"].get_number_value()
+    );
+    assert_eq!(
+        "a",
+        field("tags").get_list_value().values[0].get_string_value()
+    );
+
+    assert_eq!(
+        3.0,
+        field("labels").get_struct_value().fields["env"].get_number_value()
+    );
+    let by_id = field("points_by_id").get_struct_value();
+    assert_eq!(
+        5.0,
+        by_id.fields["10"].get_struct_value().fields["x"].get_number_value()
+    );
+
+    // `Value`, `Struct` and `ListValue` fields are emitted as is
+    assert_eq!(&value(Kind::bool_value(false)), field("extra"));
+    assert_eq!(
+        &value(Kind::struct_value(attributes())),
+        field("attributes")
+    );
+    assert_eq!(&value(Kind::list_value(history())), field("history"));
+}
+
+#[test]
+fn test_to_struct_empty() {
+    assert_eq!(Struct::new(), to_struct(&Shape::new()));
+}
+
+#[test]
+fn test_round_trip() {
+    let m = sample();
+    assert_eq!(Ok(m.clone()), parse(&to_struct(&m)));
+    assert_eq!(Ok(Shape::new()), parse(&Struct::new()));
+}
+
+#[test]
+fn test_from_struct_lenient() {
+    let mut s = Struct::new();
+    // 64-bit integers are accepted as numbers, 32-bit as strings
+    s.fields
+        .insert("id".to_owned(), value(Kind::number_value(12.0)));
+    s.fields.insert(
+        "sides".to_owned(),
+        value(Kind::string_value("3".to_owned())),
+    );
+    // JSON name
+    s.fields.insert(
+        "pointsById".to_owned(),
+        value(Kind::struct_value(Struct::new())),
+    );
+    // null leaves field unset
+    s.fields.insert(
+        "name".to_owned(),
+        value(Kind::null_value(NullValue::NULL_VALUE.into())),
+    );
+    // enum by number
+    s.fields
+        .insert("color".to_owned(), value(Kind::number_value(1.0)));
+
+    let m = parse(&s).unwrap();
+    assert_eq!(12, m.get_id());
+    assert_eq!(3, m.get_sides());
+    assert_eq!("", m.get_name());
+    assert_eq!(Color::COLOR_RED, m.get_color());
+}
+
+#[test]
+fn test_from_struct_errors() {
+    let error = |name: &str, v: Value| {
+        let mut s = Struct::new();
+        s.fields.insert(name.to_owned(), v);
+        parse(&s).unwrap_err()
+    };
+
+    assert_eq!(
+        "unknown field name: nope",
+        error("nope", value(Kind::bool_value(true)))
+    );
+    assert_eq!(
+        "field `sides`: incorrect value for type u32",
+        error("sides", value(Kind::number_value(-1.0)))
+    );
+    assert_eq!(
+        "field `sides`: incorrect value for type u32",
+        error("sides", value(Kind::number_value(1.5)))
+    );
+    assert_eq!(
+        "field `color`: unknown enum value: COLOR_BLUE",
+        error("color", value(Kind::string_value("COLOR_BLUE".to_owned())))
+    );
+    assert_eq!(
+        "field `tags`: incorrect value for type String",
+        error("tags", value(Kind::string_value("a".to_owned())))
+    );
+
+    let mut s = Struct::new();
+    s.fields
+        .insert("radius".to_owned(), value(Kind::number_value(1.0)));
+    s.fields
+        .insert("width".to_owned(), value(Kind::number_value(1.0)));
+    assert_eq!(
+        "fields `radius` and `width` are members of the same oneof",
+        parse(&s).unwrap_err()
+    );
+}
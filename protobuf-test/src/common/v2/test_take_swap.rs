@@ -0,0 +1,65 @@
+use super::test_take_swap_pb::*;
+
+#[test]
+fn test_take_copy() {
+    let mut m = TakeSwap::new();
+    m.set_i(7);
+    m.set_e(Color::GREEN);
+    assert_eq!(7, m.take_i());
+    assert_eq!(Color::GREEN, m.take_e());
+    assert_eq!(0, m.get_i());
+    assert_eq!(Color::RED, m.get_e());
+    assert_eq!(TakeSwap::new(), m);
+}
+
+#[test]
+fn test_take_copy_not_set() {
+    let mut m = TakeSwap::new();
+    assert_eq!(0, m.take_i());
+    assert_eq!(Color::RED, m.take_e());
+}
+
+#[test]
+fn test_take_oneof_copy() {
+    let mut m = TakeSwap::new();
+    m.set_one_i(3);
+    assert_eq!(3, m.take_one_i());
+    assert!(!m.has_one_i());
+
+    m.set_one_s("s".to_owned());
+    // Taking other variant leaves the oneof unchanged
+    assert_eq!(0, m.take_one_i());
+    assert_eq!("s", m.get_one_s());
+}
+
+#[test]
+fn test_swap() {
+    let mut m = TakeSwap::new();
+    m.set_s("old".to_owned());
+
+    let mut s = "new".to_owned();
+    m.swap_s(&mut s);
+    assert_eq!("new", m.get_s());
+    assert_eq!("old", s);
+
+    let mut b = vec![1, 2];
+    m.swap_b(&mut b);
+    assert_eq!(&[1, 2], m.get_b());
+    assert!(b.is_empty());
+
+    let mut sub = Sub::new();
+    sub.set_x(5);
+    m.swap_m(&mut sub);
+    assert_eq!(5, m.get_m().get_x());
+    assert_eq!(Sub::new(), sub);
+
+    let mut r = vec![10, 20];
+    m.swap_r(&mut r);
+    assert_eq!(&[10, 20], m.get_r());
+    assert!(r.is_empty());
+
+    let mut one_s = "one".to_owned();
+    m.swap_one_s(&mut one_s);
+    assert_eq!("one", m.get_one_s());
+    assert_eq!("", one_s);
+}
@@ -0,0 +1,30 @@
+use super::test_track_caller_pb::*;
+
+#[test]
+fn test_mut() {
+    let mut m = WithTrackCaller::new();
+    m.mut_string_field().push_str("s");
+    m.mut_repeated_field().push(1);
+    m.mut_oneof_message().set_value(2);
+    assert_eq!("s", m.get_string_field());
+    assert_eq!(&[1], m.get_repeated_field());
+    assert_eq!(2, m.get_oneof_message().get_value());
+
+    m.mut_oneof_string().push_str("o");
+    assert!(!m.has_oneof_message());
+    assert_eq!("o", m.get_oneof_string());
+}
+
+#[test]
+fn test_take() {
+    let mut m = WithTrackCaller::new();
+    m.set_oneof_string("o".to_owned());
+    assert_eq!(Nested::new(), m.take_oneof_message());
+    assert!(m.has_oneof_string());
+    assert_eq!("o", m.take_oneof_string());
+    assert_eq!("", m.take_oneof_string());
+
+    m.set_oneof_int(3);
+    assert_eq!(3, m.take_oneof_int());
+    assert!(!m.has_oneof_int());
+}
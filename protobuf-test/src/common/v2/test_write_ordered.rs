@@ -0,0 +1,101 @@
+use protobuf::reflect::FieldOrder;
+use protobuf::CodedInputStream;
+use protobuf::Message;
+
+use super::test_write_ordered_pb::*;
+
+fn field_numbers(bytes: &[u8]) -> Vec<u32> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let mut numbers = Vec::new();
+    while !is.eof().unwrap() {
+        let (number, wire_type) = is.read_tag_unpack().unwrap();
+        numbers.push(number);
+        is.skip_field(wire_type).unwrap();
+    }
+    numbers
+}
+
+fn ordered() -> Ordered {
+    let mut nested = Ordered::new();
+    nested.set_c("nested".to_owned());
+    nested.set_a(-3);
+
+    let mut m = Ordered::new();
+    m.set_c("c".to_owned());
+    m.set_a(-1);
+    m.mut_values().extend(&[1, 2, 3]);
+    m.mut_counts().insert("b".to_owned(), 2);
+    m.mut_counts().insert("a".to_owned(), -1);
+    m.set_nested(nested);
+    m.mut_kinds().push(Kind::KIND_SPECIAL.into());
+    m
+}
+
+#[test]
+fn test_ascending_field_number() {
+    let m = ordered();
+    let bytes = m
+        .write_to_bytes_ordered(FieldOrder::AscendingFieldNumber)
+        .unwrap();
+    assert_eq!(vec![1, 2, 3, 5, 5, 10, 11], field_numbers(&bytes));
+    assert_eq!(m, Ordered::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_declaration() {
+    let mut m = ordered();
+    m.mut_counts().clear();
+    let bytes = m.write_to_bytes_ordered(FieldOrder::Declaration).unwrap();
+    assert_eq!(vec![3, 1, 10, 2, 11], field_numbers(&bytes));
+    assert_eq!(m.write_to_bytes().unwrap(), bytes);
+}
+
+#[test]
+fn test_map_entries_sorted() {
+    let mut a = Ordered::new();
+    let mut b = Ordered::new();
+    for i in 0..100 {
+        a.mut_counts().insert(format!("k{}", i), i);
+        b.mut_counts().insert(format!("k{}", 99 - i), 99 - i);
+    }
+    let bytes = a
+        .write_to_bytes_ordered(FieldOrder::AscendingFieldNumber)
+        .unwrap();
+    assert_eq!(
+        bytes,
+        b.write_to_bytes_ordered(FieldOrder::AscendingFieldNumber)
+            .unwrap()
+    );
+
+    let mut keys: Vec<String> = a.get_counts().keys().cloned().collect();
+    keys.sort();
+    let mut one_entry = Ordered::new();
+    let mut expected = Vec::new();
+    for key in keys {
+        one_entry.mut_counts().clear();
+        one_entry
+            .mut_counts()
+            .insert(key.clone(), a.get_counts()[&key]);
+        expected.extend(one_entry.write_to_bytes().unwrap());
+    }
+    assert_eq!(expected, bytes);
+}
+
+#[test]
+fn test_unknown_fields() {
+    let mut m = Ordered::new();
+    m.set_a(1);
+    m.mut_values().push(10);
+    m.mut_unknown_fields().add_varint(20, 20);
+    m.mut_unknown_fields().add_varint(4, 4);
+    m.mut_unknown_fields().add_fixed32(7, 7);
+
+    let bytes = m
+        .write_to_bytes_ordered(FieldOrder::AscendingFieldNumber)
+        .unwrap();
+    assert_eq!(vec![1, 4, 7, 10, 20], field_numbers(&bytes));
+    assert_eq!(m, Ordered::parse_from_bytes(&bytes).unwrap());
+
+    let bytes = m.write_to_bytes_ordered(FieldOrder::Declaration).unwrap();
+    assert_eq!(vec![1, 10, 4, 7, 20], field_numbers(&bytes));
+}
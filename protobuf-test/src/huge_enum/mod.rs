@@ -0,0 +1,33 @@
+// `.proto` file with 5000-value enum is generated by `build.rs`
+include!(concat!(env!("OUT_DIR"), "/huge_enum_protos.rs"));
+
+use huge_enum_protos::huge_enum::HugeEnum;
+use huge_enum_protos::huge_enum::SmallEnum;
+use protobuf::ProtobufEnum;
+
+const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/huge_enum_protos/huge_enum.rs"));
+
+#[test]
+fn from_i32_all_values() {
+    assert_eq!(5000, HugeEnum::values().len());
+    for &v in HugeEnum::values() {
+        assert_eq!(Some(v), HugeEnum::from_i32(v.value()));
+    }
+}
+
+#[test]
+fn from_i32_missing_values() {
+    for n in -17510..17510 {
+        let expected = n >= -17500 && n < 17500 && (n + 17500) % 7 == 0;
+        assert_eq!(expected, HugeEnum::from_i32(n).is_some(), "{}", n);
+    }
+    assert_eq!(None, HugeEnum::from_i32(i32::MIN));
+    assert_eq!(None, HugeEnum::from_i32(i32::MAX));
+}
+
+#[test]
+fn lookup_table_only_above_threshold() {
+    assert_eq!(1, GENERATED.matches("binary_search_by_key").count());
+    assert_eq!(Some(SmallEnum::SMALL_ONE), SmallEnum::from_i32(1));
+    assert_eq!(None, SmallEnum::from_i32(2));
+}
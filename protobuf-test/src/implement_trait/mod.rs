@@ -0,0 +1,31 @@
+// Messages generated with `Customize::implement_trait`
+include!(concat!(env!("OUT_DIR"), "/implement_trait/mod.rs"));
+
+use implement_trait::outer::Inner;
+use implement_trait::Other;
+use implement_trait::Outer;
+
+pub trait Routable {
+    fn route(&self) -> &'static str;
+    fn name() -> &'static str;
+    fn rust_name() -> &'static str;
+    fn descriptor_full_name(&self) -> String;
+}
+
+fn route<M: Routable>(m: &M) -> &'static str {
+    m.route()
+}
+
+#[test]
+fn test() {
+    assert_eq!("implement_trait.Outer", route(&Outer::new()));
+    assert_eq!("implement_trait.Outer.Inner", route(&Inner::new()));
+    assert_eq!("implement_trait.Other", route(&Other::new()));
+
+    assert_eq!("Inner", Inner::name());
+    assert_eq!("Inner", Inner::rust_name());
+    assert_eq!(
+        "implement_trait.Outer.Inner",
+        Inner::new().descriptor_full_name()
+    );
+}
@@ -6,6 +6,8 @@ extern crate protobuf_test_common;
 
 #[cfg(feature = "with-bytes")]
 extern crate bytes;
+#[cfg(feature = "with-smallvec")]
+extern crate smallvec;
 
 #[cfg(feature = "with-serde")]
 extern crate serde;
@@ -30,3 +32,11 @@ mod google;
 mod interop;
 
 mod include_generated;
+
+mod cargo_out_dir;
+
+mod split_impls;
+
+mod huge_enum;
+
+mod implement_trait;
@@ -0,0 +1,37 @@
+// Types and implementations are generated into separate modules
+include!(concat!(env!("OUT_DIR"), "/split_impls_protos.rs"));
+
+use protobuf::Message;
+use split_impls_protos::split_impls::outer;
+use split_impls_protos::split_impls::Color;
+use split_impls_protos::split_impls::Outer;
+
+#[test]
+fn test() {
+    let mut m = Outer::new();
+    m.set_value(10);
+    m.set_color(Color::GREEN);
+    m.mut_middle()
+        .mut_inner()
+        .set_kind(outer::middle::Kind::LARGE);
+
+    let bytes = m.write_to_bytes().unwrap();
+    let parsed = Outer::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(m, parsed);
+    assert_eq!(10, parsed.get_value());
+    assert_eq!(
+        outer::middle::Kind::LARGE,
+        parsed.get_middle().get_inner().get_kind()
+    );
+    assert_eq!("Outer", Outer::descriptor_static().name());
+}
+
+#[test]
+fn test_types_file_has_no_impls() {
+    let types = include_str!(concat!(
+        env!("OUT_DIR"),
+        "/split_impls_protos/split_impls.rs"
+    ));
+    assert!(!types.contains("impl "));
+    assert!(!types.contains("include!"));
+}
@@ -0,0 +1,41 @@
+use protobuf::descriptor::field_options::CType;
+use protobuf::Message;
+
+use super::test_ext_enum_pb::holder::Nested;
+use super::test_ext_enum_pb::*;
+
+#[test]
+fn enum_options() {
+    let descriptor = WithOptions::descriptor_static();
+    let options = descriptor.get_proto().options.get_or_default();
+    assert_eq!(Some(Level::HIGH.into()), exts::level_option.get(options));
+    assert_eq!(
+        Some(Level::NEGATIVE.into()),
+        exts::negative_option.get(options)
+    );
+    assert_eq!(
+        Some(Nested::NESTED_SEVEN.into()),
+        exts::nested_option.get(options)
+    );
+    assert_eq!(Some(CType::CORD.into()), exts::ctype_option.get(options));
+}
+
+#[test]
+fn enum_in_message_option() {
+    let descriptor = WithOptions::descriptor_static();
+    let options = descriptor.get_proto().options.get_or_default();
+    let config = exts::config_option.get(options).unwrap();
+    assert_eq!(Level::LOW, config.get_level());
+    assert_eq!(Nested::NESTED_SEVEN, config.get_nested());
+}
+
+#[test]
+fn enum_field_option() {
+    let descriptor = WithOptions::descriptor_static();
+    let field = descriptor.get_field_by_name("field").unwrap();
+    let options = field.get_proto().options.get_or_default();
+    assert_eq!(
+        Some(Level::LOW.into()),
+        exts::field_level_option.get(options)
+    );
+}
@@ -0,0 +1,51 @@
+use super::test_option_getters_pb::*;
+
+#[test]
+fn test_unset() {
+    let m = WithOptionGetters::new();
+    assert_eq!(None, m.get_int_field_opt());
+    assert_eq!(None, m.get_string_field_opt());
+    assert_eq!(None, m.get_bytes_field_opt());
+    assert_eq!(None, m.get_enum_field_opt());
+    assert_eq!(None, m.get_message_field_opt());
+    assert_eq!(None, m.get_required_field_opt());
+    assert_eq!(None, m.get_oneof_int_opt());
+    assert_eq!(None, m.get_oneof_string_opt());
+    assert_eq!(None, WithOptionGettersByRef::new().get_int_field_opt());
+}
+
+#[test]
+fn test_set() {
+    let mut m = WithOptionGetters::new();
+    m.set_int_field(0);
+    m.set_string_field("s".to_owned());
+    m.set_bytes_field(vec![1, 2]);
+    m.set_enum_field(Kind::SECOND);
+    m.message_field.mut_or_default().set_value(3);
+    m.set_required_field(false);
+    m.set_oneof_string("o".to_owned());
+
+    assert_eq!(Some(0), m.get_int_field_opt());
+    assert_eq!(Some("s"), m.get_string_field_opt());
+    assert_eq!(Some(&[1, 2][..]), m.get_bytes_field_opt());
+    assert_eq!(Some(Kind::SECOND), m.get_enum_field_opt());
+    assert_eq!(Some(3), m.get_message_field_opt().map(|n| n.get_value()));
+    assert_eq!(Some(false), m.get_required_field_opt());
+    assert_eq!(None, m.get_oneof_int_opt());
+    assert_eq!(Some("o"), m.get_oneof_string_opt());
+
+    m.set_oneof_int(4);
+    assert_eq!(Some(4), m.get_oneof_int_opt());
+    assert_eq!(None, m.get_oneof_string_opt());
+
+    m.clear_int_field();
+    assert_eq!(None, m.get_int_field_opt());
+    assert_eq!(10, m.get_int_field());
+}
+
+#[test]
+fn test_by_ref() {
+    let mut m = WithOptionGettersByRef::new();
+    m.set_int_field(5);
+    assert_eq!(Some(&5), m.get_int_field_opt());
+}
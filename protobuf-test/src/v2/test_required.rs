@@ -1,4 +1,6 @@
 use protobuf::Message;
+use protobuf::ParseOptions;
+use protobuf::ProtobufError;
 
 use super::test_required_pb::*;
 
@@ -9,7 +11,10 @@ fn test_write_missing_required() {
 
 #[test]
 fn test_read_missing_required() {
-    assert!(TestRequired::parse_from_bytes(&[]).is_err());
+    assert_eq!(
+        "not all message fields set: message `TestRequired`, required fields not set: b",
+        TestRequired::parse_from_bytes(&[]).unwrap_err().to_string()
+    );
 }
 
 #[test]
@@ -21,3 +26,51 @@ fn test_is_initialized_is_recursive() {
     m.inner.as_mut().unwrap().set_b(false);
     assert!(m.is_initialized());
 }
+
+fn strict() -> ParseOptions {
+    ParseOptions {
+        require_initialized: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_with_options_lenient_by_default() {
+    let m = TestRequired::parse_from_bytes_with_options(&[], &ParseOptions::default()).unwrap();
+    assert!(!m.is_initialized());
+}
+
+#[test]
+fn test_parse_with_options_lists_missing_fields() {
+    // `outer { inner {} }`, `TestRequired` item with `b` set
+    let bytes = [0x12, 0x02, 0x0a, 0x00, 0x1a, 0x02, 0x28, 0x01];
+    match TestRequiredNested::parse_from_bytes_with_options(&bytes, &strict()) {
+        Err(ProtobufError::MessageNotInitialized {
+            message,
+            missing_fields,
+        }) => {
+            assert_eq!("TestRequiredNested", message);
+            assert_eq!(
+                vec!["name".to_owned(), "outer.inner.b".to_owned()],
+                missing_fields
+            );
+        }
+        r => panic!("expecting missing required fields: {:?}", r),
+    }
+}
+
+#[test]
+fn test_parse_with_options_initialized() {
+    let mut m = TestRequiredNested::new();
+    m.set_name("n".to_owned());
+    let mut inner = TestRequired::new();
+    inner.set_b(true);
+    let mut outer = TestRequiredOuter::new();
+    outer.inner = Some(inner).into();
+    m.outer = Some(outer).into();
+    let bytes = m.write_to_bytes().unwrap();
+    assert_eq!(
+        m,
+        TestRequiredNested::parse_from_bytes_with_options(&bytes, &strict()).unwrap()
+    );
+}
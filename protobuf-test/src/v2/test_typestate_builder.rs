@@ -0,0 +1,49 @@
+use protobuf::Message;
+
+use super::test_typestate_builder_pb::*;
+
+fn address() -> Address {
+    Address::builder().city("Paris".to_owned()).build()
+}
+
+#[test]
+fn test_build() {
+    let person = Person::builder()
+        .name("Alice".to_owned())
+        .id(17)
+        .address(address())
+        .build();
+    assert!(person.is_initialized());
+    assert_eq!("Alice", person.get_name());
+    assert_eq!(17, person.get_id());
+    assert_eq!("Paris", person.get_address().get_city());
+    assert!(!person.has_email());
+}
+
+#[test]
+fn test_any_order() {
+    let a = Person::builder()
+        .address(address())
+        .id(1)
+        .name("Bob".to_owned())
+        .build();
+    let b = Person::builder()
+        .name("Bob".to_owned())
+        .address(address())
+        .id(1)
+        .build();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_optional_fields_after_build() {
+    let mut person = Person::builder()
+        .id(2)
+        .name("Carol".to_owned())
+        .address(address())
+        .build();
+    person.set_email("carol@example.com".to_owned());
+    person.phones.push("123".to_owned());
+    let bytes = person.write_to_bytes().unwrap();
+    assert_eq!(person, Person::parse_from_bytes(&bytes).unwrap());
+}
@@ -0,0 +1,43 @@
+use protobuf::reflect::MessageDescriptor;
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+use super::test_iter_set_fields_zero_pb::*;
+
+fn set_field_names(m: &dyn MessageDyn) -> Vec<String> {
+    m.iter_set_fields()
+        .map(|(f, _)| f.get_name().to_owned())
+        .collect()
+}
+
+#[test]
+fn test_zeros_are_skipped() {
+    let mut m = ForIterSetFieldsZero::new();
+    m.i = 0;
+    m.s = String::new();
+    assert!(set_field_names(&m).is_empty());
+
+    // Active oneof member is set even if it has default value
+    m.set_first(0);
+    assert_eq!(vec!["first"], set_field_names(&m));
+}
+
+#[test]
+fn test_dynamic_zeros_are_skipped() {
+    let d: MessageDescriptor = ForIterSetFieldsZero::descriptor_static();
+    let d = protobuf::reflect::FileDescriptor::new_dynamic(
+        d.file_descriptor_proto().clone(),
+        Vec::new(),
+    )
+    .message_by_package_relative_name("ForIterSetFieldsZero")
+    .unwrap();
+    let mut m = d.new_instance();
+    d.get_field_by_name("i")
+        .unwrap()
+        .set_singular_field(&mut *m, 0i32.into());
+    assert!(set_field_names(&*m).is_empty());
+    d.get_field_by_name("i")
+        .unwrap()
+        .set_singular_field(&mut *m, 1i32.into());
+    assert_eq!(vec!["i"], set_field_names(&*m));
+}
@@ -365,6 +365,26 @@ impl<'ignore> BufReadIter<'ignore> {
         Ok(())
     }
 
+    /// Skip given number of bytes without copying them.
+    pub fn skip_bytes(&mut self, count: usize) -> ProtobufResult<()> {
+        if count as u64 > self.bytes_until_limit() {
+            return Err(ProtobufError::WireError(WireError::TruncatedMessage));
+        }
+
+        let mut remaining = count;
+        while remaining > 0 {
+            let available = self.fill_buf()?.len();
+            if available == 0 {
+                return Err(ProtobufError::WireError(WireError::UnexpectedEof));
+            }
+            let consume = cmp::min(available, remaining);
+            self.consume(consume);
+            remaining -= consume;
+        }
+
+        Ok(())
+    }
+
     fn do_fill_buf(&mut self) -> ProtobufResult<()> {
         debug_assert!(self.pos_within_buf == self.limit_within_buf);
 
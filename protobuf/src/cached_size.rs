@@ -9,6 +9,20 @@ use std::sync::atomic::Ordering;
 /// (Generated code can use `#[derive(Eq)]`).
 ///
 /// This type should rarely be used directly.
+///
+/// # Thread safety
+///
+/// Size is stored in an atomic, so [`Message::compute_size`](crate::Message::compute_size)
+/// works on `&self`, and the same message can be serialized
+/// from multiple threads concurrently without cloning.
+///
+/// Atomic operations use [`Ordering::Relaxed`]. This is sufficient, because
+/// the size written is a function of message content, which cannot change
+/// while message is shared: concurrent `compute_size` calls store equal values,
+/// and each thread serializing the message reads sizes it has computed itself
+/// or equal sizes stored by other threads.
+/// Cached size is not synchronized with message modification: message
+/// modified after `compute_size` must be resized before writing.
 #[derive(Debug, Default)]
 pub struct CachedSize {
     size: AtomicUsize,
@@ -54,3 +68,53 @@ impl Hash for CachedSize {
         // ignore cached size in cache computation
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::Message;
+
+    fn file_descriptor() -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("shared.proto".to_owned());
+        for i in 0..20 {
+            let mut message = DescriptorProto::new();
+            message.set_name(format!("Message{}", i));
+            for j in 0..i {
+                let mut field = FieldDescriptorProto::new();
+                field.set_name(format!("field_{}", "x".repeat(j * 10)));
+                field.set_number(j as i32 + 1);
+                message.field.push(field);
+            }
+            file.message_type.push(message);
+        }
+        file
+    }
+
+    #[test]
+    fn write_shared_message_from_threads() {
+        let file = Arc::new(file_descriptor());
+        let expected = file.write_to_bytes().unwrap();
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let file = file.clone();
+                thread::spawn(move || {
+                    (0..100)
+                        .map(|_| file.write_to_bytes().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for t in threads {
+            for bytes in t.join().unwrap() {
+                assert_eq!(expected, bytes);
+            }
+        }
+    }
+}
@@ -31,6 +31,7 @@ use crate::reflect::types::ProtobufTypeSint64;
 use crate::reflect::types::ProtobufTypeUint32;
 use crate::reflect::types::ProtobufTypeUint64;
 use crate::reflect::ProtobufValue;
+use crate::repeated_target::RepeatedTarget;
 use crate::unknown::UnknownValue;
 use crate::wire_format;
 use crate::zigzag::decode_zig_zag_32;
@@ -47,6 +48,9 @@ pub struct CodedInputStream<'a> {
     source: BufReadIter<'a>,
     recursion_level: u32,
     recursion_limit: u32,
+    /// Field number of the last read tag, for error reporting.
+    last_field_number: u32,
+    reject_integer_overflow: bool,
 }
 
 impl<'a> CodedInputStream<'a> {
@@ -84,6 +88,8 @@ impl<'a> CodedInputStream<'a> {
             source: source,
             recursion_level: 0,
             recursion_limit: DEFAULT_RECURSION_LIMIT,
+            last_field_number: 0,
+            reject_integer_overflow: false,
         }
     }
 
@@ -92,6 +98,15 @@ impl<'a> CodedInputStream<'a> {
         self.recursion_limit = limit;
     }
 
+    /// Fail reading 32-bit varint types with [`WireError::IntegerOverflow`]
+    /// if the value does not fit into the type.
+    ///
+    /// By default such values are truncated to 32 bits
+    /// like in other protobuf implementations.
+    pub fn set_reject_integer_overflow(&mut self, reject: bool) {
+        self.reject_integer_overflow = reject;
+    }
+
     #[inline]
     pub(crate) fn incr_recursion(&mut self) -> ProtobufResult<()> {
         if self.recursion_level >= self.recursion_limit {
@@ -259,9 +274,8 @@ impl<'a> CodedInputStream<'a> {
         Ok(u64::from_le_bytes(bytes))
     }
 
-    /// Read tag
     #[inline]
-    pub fn read_tag(&mut self) -> ProtobufResult<wire_format::Tag> {
+    fn read_tag_untracked(&mut self) -> ProtobufResult<wire_format::Tag> {
         let v = self.read_raw_varint32()?;
         match wire_format::Tag::new(v) {
             Some(tag) => Ok(tag),
@@ -269,6 +283,25 @@ impl<'a> CodedInputStream<'a> {
         }
     }
 
+    /// Read tag
+    #[inline]
+    pub fn read_tag(&mut self) -> ProtobufResult<wire_format::Tag> {
+        let tag = self.read_tag_untracked()?;
+        self.last_field_number = tag.field_number();
+        Ok(tag)
+    }
+
+    /// Read tag of map entry field.
+    ///
+    /// Unlike [`read_tag_unpack`](CodedInputStream::read_tag_unpack),
+    /// it does not change field number reported in errors,
+    /// so errors in keys and values name the map field.
+    pub(crate) fn read_map_entry_tag_unpack(
+        &mut self,
+    ) -> ProtobufResult<(u32, wire_format::WireType)> {
+        self.read_tag_untracked().map(|t| t.unpack())
+    }
+
     /// Read tag, return it is pair (field number, wire type)
     #[inline]
     pub fn read_tag_unpack(&mut self) -> ProtobufResult<(u32, wire_format::WireType)> {
@@ -292,9 +325,29 @@ impl<'a> CodedInputStream<'a> {
         self.read_raw_varint64().map(|v| v as i64)
     }
 
-    /// Read `int32`
+    fn integer_overflow<T>(&self) -> ProtobufResult<T> {
+        Err(ProtobufError::WireError(WireError::IntegerOverflow {
+            field_number: self.last_field_number,
+        }))
+    }
+
+    /// Read `int32`.
+    ///
+    /// Negative values are encoded as sign-extended 64-bit varints,
+    /// but 32-bit varints written by some encoders are accepted too.
+    /// Other values out of `i32` range are truncated, or rejected
+    /// if [`set_reject_integer_overflow`](CodedInputStream::set_reject_integer_overflow) is set.
     pub fn read_int32(&mut self) -> ProtobufResult<i32> {
-        self.read_raw_varint32().map(|v| v as i32)
+        let v = self.read_raw_varint64()?;
+        let signed = v as i64;
+        if !self.reject_integer_overflow
+            || v <= u32::MAX as u64
+            || (signed < 0 && signed >= i32::MIN as i64)
+        {
+            Ok(v as i32)
+        } else {
+            self.integer_overflow()
+        }
     }
 
     /// Read `uint64`
@@ -302,9 +355,17 @@ impl<'a> CodedInputStream<'a> {
         self.read_raw_varint64()
     }
 
-    /// Read `uint32`
+    /// Read `uint32`.
+    ///
+    /// Values out of `u32` range are truncated, or rejected
+    /// if [`set_reject_integer_overflow`](CodedInputStream::set_reject_integer_overflow) is set.
     pub fn read_uint32(&mut self) -> ProtobufResult<u32> {
-        self.read_raw_varint32()
+        let v = self.read_raw_varint64()?;
+        if !self.reject_integer_overflow || v <= u32::MAX as u64 {
+            Ok(v as u32)
+        } else {
+            self.integer_overflow()
+        }
     }
 
     /// Read `sint64`
@@ -360,7 +421,7 @@ impl<'a> CodedInputStream<'a> {
 
     fn read_repeated_packed_fixed_into<T: ProtobufTypeFixed>(
         &mut self,
-        target: &mut Vec<T::ProtobufValue>,
+        target: &mut impl RepeatedTarget<T::ProtobufValue>,
     ) -> ProtobufResult<()> {
         let len_bytes = self.read_raw_varint64()?;
 
@@ -384,7 +445,7 @@ impl<'a> CodedInputStream<'a> {
 
     fn read_repeated_packed_into<T: ProtobufType>(
         &mut self,
-        target: &mut Vec<T::ProtobufValue>,
+        target: &mut impl RepeatedTarget<T::ProtobufValue>,
     ) -> ProtobufResult<()> {
         let len_bytes = self.read_raw_varint64()?;
 
@@ -409,30 +470,39 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `double`
     pub fn read_repeated_packed_double_into(
         &mut self,
-        target: &mut Vec<f64>,
+        target: &mut impl RepeatedTarget<f64>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_fixed_into::<ProtobufTypeDouble>(target)
     }
 
     /// Read repeated packed `float`
-    pub fn read_repeated_packed_float_into(&mut self, target: &mut Vec<f32>) -> ProtobufResult<()> {
+    pub fn read_repeated_packed_float_into(
+        &mut self,
+        target: &mut impl RepeatedTarget<f32>,
+    ) -> ProtobufResult<()> {
         self.read_repeated_packed_fixed_into::<ProtobufTypeFloat>(target)
     }
 
     /// Read repeated packed `int64`
-    pub fn read_repeated_packed_int64_into(&mut self, target: &mut Vec<i64>) -> ProtobufResult<()> {
+    pub fn read_repeated_packed_int64_into(
+        &mut self,
+        target: &mut impl RepeatedTarget<i64>,
+    ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeInt64>(target)
     }
 
     /// Read repeated packed `int32`
-    pub fn read_repeated_packed_int32_into(&mut self, target: &mut Vec<i32>) -> ProtobufResult<()> {
+    pub fn read_repeated_packed_int32_into(
+        &mut self,
+        target: &mut impl RepeatedTarget<i32>,
+    ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeInt32>(target)
     }
 
     /// Read repeated packed `uint64`
     pub fn read_repeated_packed_uint64_into(
         &mut self,
-        target: &mut Vec<u64>,
+        target: &mut impl RepeatedTarget<u64>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeUint64>(target)
     }
@@ -440,7 +510,7 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `uint32`
     pub fn read_repeated_packed_uint32_into(
         &mut self,
-        target: &mut Vec<u32>,
+        target: &mut impl RepeatedTarget<u32>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeUint32>(target)
     }
@@ -448,7 +518,7 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `sint64`
     pub fn read_repeated_packed_sint64_into(
         &mut self,
-        target: &mut Vec<i64>,
+        target: &mut impl RepeatedTarget<i64>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeSint64>(target)
     }
@@ -456,7 +526,7 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `sint32`
     pub fn read_repeated_packed_sint32_into(
         &mut self,
-        target: &mut Vec<i32>,
+        target: &mut impl RepeatedTarget<i32>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeSint32>(target)
     }
@@ -464,7 +534,7 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `fixed64`
     pub fn read_repeated_packed_fixed64_into(
         &mut self,
-        target: &mut Vec<u64>,
+        target: &mut impl RepeatedTarget<u64>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_fixed_into::<ProtobufTypeFixed64>(target)
     }
@@ -472,7 +542,7 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `fixed32`
     pub fn read_repeated_packed_fixed32_into(
         &mut self,
-        target: &mut Vec<u32>,
+        target: &mut impl RepeatedTarget<u32>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_fixed_into::<ProtobufTypeFixed32>(target)
     }
@@ -480,7 +550,7 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `sfixed64`
     pub fn read_repeated_packed_sfixed64_into(
         &mut self,
-        target: &mut Vec<i64>,
+        target: &mut impl RepeatedTarget<i64>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_fixed_into::<ProtobufTypeSfixed64>(target)
     }
@@ -488,20 +558,23 @@ impl<'a> CodedInputStream<'a> {
     /// Read repeated packed `sfixed32`
     pub fn read_repeated_packed_sfixed32_into(
         &mut self,
-        target: &mut Vec<i32>,
+        target: &mut impl RepeatedTarget<i32>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_fixed_into::<ProtobufTypeSfixed32>(target)
     }
 
     /// Read repeated packed `bool`
-    pub fn read_repeated_packed_bool_into(&mut self, target: &mut Vec<bool>) -> ProtobufResult<()> {
+    pub fn read_repeated_packed_bool_into(
+        &mut self,
+        target: &mut impl RepeatedTarget<bool>,
+    ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeBool>(target)
     }
 
     /// Read repeated packed `enum` into `ProtobufEnum`
     pub fn read_repeated_packed_enum_into<E: ProtobufEnum + ProtobufValue>(
         &mut self,
-        target: &mut Vec<E>,
+        target: &mut impl RepeatedTarget<E>,
     ) -> ProtobufResult<()> {
         self.read_repeated_packed_into::<ProtobufTypeEnum<E>>(target)
     }
@@ -530,7 +603,13 @@ impl<'a> CodedInputStream<'a> {
 
     /// Skip field
     pub fn skip_field(&mut self, wire_type: wire_format::WireType) -> ProtobufResult<()> {
-        self.read_unknown(wire_type).map(|_| ())
+        match wire_type {
+            wire_format::WireTypeLengthDelimited => {
+                let len = self.read_raw_varint32()?;
+                self.skip_raw_bytes(len)
+            }
+            _ => self.read_unknown(wire_type).map(|_| ()),
+        }
     }
 
     /// Read raw bytes into the supplied vector.  The vector will be resized as needed and
@@ -548,8 +627,7 @@ impl<'a> CodedInputStream<'a> {
 
     /// Skip exact number of bytes
     pub fn skip_raw_bytes(&mut self, count: u32) -> ProtobufResult<()> {
-        // TODO: make it more efficient
-        self.read_raw_bytes(count).map(|_| ())
+        self.source.skip_bytes(count as usize)
     }
 
     /// Read `bytes` field, length delimited
@@ -648,6 +726,7 @@ mod test {
     use super::READ_RAW_BYTES_MAX_ALLOC;
     use crate::error::ProtobufError;
     use crate::error::ProtobufResult;
+    use crate::error::WireError;
     use crate::hex::decode_hex;
 
     fn test_read_partial<F>(hex: &str, mut callback: F)
@@ -737,6 +816,59 @@ mod test {
         });
     }
 
+    fn assert_integer_overflow<V: Debug>(result: ProtobufResult<V>, expected_field_number: u32) {
+        match result {
+            Err(ProtobufError::WireError(WireError::IntegerOverflow { field_number })) => {
+                assert_eq!(expected_field_number, field_number)
+            }
+            r => panic!("expecting integer overflow: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_input_stream_read_int32_overflow() {
+        test_read_v("96 01", 150, |reader| reader.read_int32());
+        // sign-extended to 64 bits
+        test_read_v("ff ff ff ff ff ff ff ff ff 01", -1, |reader| {
+            reader.read_int32()
+        });
+        test_read_v("80 80 80 80 f8 ff ff ff ff 01", i32::MIN, |reader| {
+            reader.read_int32()
+        });
+        // 32-bit encoding of negative values
+        test_read_v("ff ff ff ff 0f", -1, |reader| reader.read_int32());
+
+        // 2^32 + 1
+        test_read_v("81 80 80 80 10", 1, |reader| reader.read_int32());
+        test_read("81 80 80 80 10", |is| {
+            is.set_reject_integer_overflow(true);
+            assert_integer_overflow(is.read_int32(), 0);
+        });
+        // i32::MIN - 1 sign-extended
+        test_read("ff ff ff ff f7 ff ff ff ff 01", |is| {
+            is.set_reject_integer_overflow(true);
+            assert_integer_overflow(is.read_int32(), 0);
+        });
+    }
+
+    #[test]
+    fn test_input_stream_read_uint32_overflow() {
+        test_read_v("ff ff ff ff 0f", u32::MAX, |reader| reader.read_uint32());
+        test_read_v("80 80 80 80 10", 0, |reader| reader.read_uint32());
+        // tag of field 3, then 2^32
+        test_read("18 80 80 80 80 10", |is| {
+            is.set_reject_integer_overflow(true);
+            assert_eq!(3, is.read_tag_unpack().unwrap().0);
+            assert_integer_overflow(is.read_uint32(), 3);
+        });
+        // 10-byte varint
+        test_read("28 ff ff ff ff ff ff ff ff ff 01", |is| {
+            is.set_reject_integer_overflow(true);
+            assert_eq!(5, is.read_tag_unpack().unwrap().0);
+            assert_integer_overflow(is.read_sint32(), 5);
+        });
+    }
+
     #[test]
     fn test_input_stream_read_raw_varint_unexpected_eof() {
         test_read_partial("96 97", |reader| {
@@ -771,6 +903,31 @@ mod test {
         test_read_v("40 d5 ab 68 b3 07 3d 46", 23e29, |is| is.read_double());
     }
 
+    #[test]
+    fn test_input_stream_read_fixed() {
+        // Wire format is little-endian regardless of host byte order
+        test_read_v("f1 e2 d3 c4", 0xc4d3e2f1, |is| is.read_fixed32());
+        test_read_v("fe ff ff ff", -2, |is| is.read_sfixed32());
+        test_read_v("f1 e2 d3 c4 b5 a6 07 f8", 0xf807a6b5c4d3e2f1, |is| {
+            is.read_fixed64()
+        });
+        test_read_v("fe ff ff ff ff ff ff ff", -2, |is| is.read_sfixed64());
+    }
+
+    #[test]
+    fn test_input_stream_read_repeated_packed_fixed() {
+        test_read("08 f1 e2 d3 c4 01 00 00 00", |is| {
+            let mut v = Vec::new();
+            is.read_repeated_packed_fixed32_into(&mut v).unwrap();
+            assert_eq!(vec![0xc4d3e2f1, 1], v);
+        });
+        test_read("08 00 00 00 00 00 00 f0 3f", |is| {
+            let mut v = Vec::new();
+            is.read_repeated_packed_double_into(&mut v).unwrap();
+            assert_eq!(vec![1.0], v);
+        });
+    }
+
     #[test]
     fn test_input_stream_skip_raw_bytes() {
         test_read("", |reader| {
@@ -62,6 +62,8 @@ pub struct CodedOutputStream<'a> {
     buffer: &'a mut [u8],
     // within buffer
     position: usize,
+    // number of bytes written before the start of the buffer
+    pos_of_buffer_start: u64,
 }
 
 impl<'a> CodedOutputStream<'a> {
@@ -82,17 +84,22 @@ impl<'a> CodedOutputStream<'a> {
             target: OutputTarget::Write(writer, buffer_storage),
             buffer: buffer,
             position: 0,
+            pos_of_buffer_start: 0,
         }
     }
 
     /// `CodedOutputStream` which writes directly to bytes.
     ///
-    /// Attempt to write more than bytes capacity results in error.
+    /// Attempt to write more than bytes capacity results in error
+    /// (not panic), so this can be used to serialize into fixed-size
+    /// preallocated buffers. Number of bytes written can be obtained
+    /// with [`total_bytes_written`](Self::total_bytes_written).
     pub fn bytes(bytes: &'a mut [u8]) -> CodedOutputStream<'a> {
         CodedOutputStream {
             target: OutputTarget::Bytes,
             buffer: bytes,
             position: 0,
+            pos_of_buffer_start: 0,
         }
     }
 
@@ -102,9 +109,17 @@ impl<'a> CodedOutputStream<'a> {
             target: OutputTarget::Vec(vec),
             buffer: &mut [],
             position: 0,
+            pos_of_buffer_start: 0,
         }
     }
 
+    /// Total number of bytes written to this stream.
+    ///
+    /// This number includes bytes not yet flushed to the underlying target.
+    pub fn total_bytes_written(&self) -> u64 {
+        self.pos_of_buffer_start + self.position as u64
+    }
+
     /// Check if EOF is reached.
     ///
     /// # Panics
@@ -125,6 +140,7 @@ impl<'a> CodedOutputStream<'a> {
         match self.target {
             OutputTarget::Write(ref mut write, _) => {
                 write.write_all(&self.buffer[..self.position])?;
+                self.pos_of_buffer_start += self.position as u64;
                 self.position = 0;
             }
             OutputTarget::Vec(ref mut vec) => unsafe {
@@ -133,6 +149,7 @@ impl<'a> CodedOutputStream<'a> {
                 vec.set_len(vec_len + self.position);
                 vec.reserve(1);
                 self.buffer = remove_lifetime_mut(remaining_capacity_as_slice_mut(vec));
+                self.pos_of_buffer_start += self.position as u64;
                 self.position = 0;
             },
             OutputTarget::Bytes => {
@@ -204,6 +221,7 @@ impl<'a> CodedOutputStream<'a> {
                 }
             }
         }
+        self.pos_of_buffer_start += bytes.len() as u64;
         Ok(())
     }
 
@@ -586,7 +604,9 @@ mod test {
             {
                 let mut os = CodedOutputStream::new(&mut v as &mut dyn Write);
                 gen(&mut os).unwrap();
+                assert_eq!(expected_bytes.len() as u64, os.total_bytes_written());
                 os.flush().unwrap();
+                assert_eq!(expected_bytes.len() as u64, os.total_bytes_written());
             }
             assert_eq!(encode_hex(&expected_bytes), encode_hex(&v));
         }
@@ -599,6 +619,7 @@ mod test {
                 let mut os = CodedOutputStream::bytes(&mut r);
                 gen(&mut os).unwrap();
                 os.check_eof();
+                assert_eq!(expected_bytes.len() as u64, os.total_bytes_written());
             }
             assert_eq!(encode_hex(&expected_bytes), encode_hex(&r));
         }
@@ -610,7 +631,9 @@ mod test {
             {
                 let mut os = CodedOutputStream::vec(&mut r);
                 gen(&mut os).unwrap();
+                assert_eq!(expected_bytes.len() as u64, os.total_bytes_written());
                 os.flush().unwrap();
+                assert_eq!(expected_bytes.len() as u64, os.total_bytes_written());
             }
 
             r.drain(..7);
@@ -697,6 +720,17 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_output_stream_write_fixed_no_tag() {
+        // Wire format is little-endian regardless of host byte order
+        test_write("f1 e2 d3 c4", |os| os.write_fixed32_no_tag(0xc4d3e2f1));
+        test_write("fe ff ff ff", |os| os.write_sfixed32_no_tag(-2));
+        test_write("f1 e2 d3 c4 b5 a6 07 f8", |os| {
+            os.write_fixed64_no_tag(0xf807a6b5c4d3e2f1)
+        });
+        test_write("fe ff ff ff ff ff ff ff", |os| os.write_sfixed64_no_tag(-2));
+    }
+
     #[test]
     fn test_output_stream_io_write() {
         let expected = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
@@ -736,4 +770,18 @@ mod test {
             assert_eq!(expected, *v);
         }
     }
+
+    #[test]
+    fn test_output_stream_bytes_overflow() {
+        let mut bytes = [0u8; 3];
+        let mut os = CodedOutputStream::bytes(&mut bytes);
+        os.write_raw_varint32(150).unwrap();
+        assert_eq!(2, os.total_bytes_written());
+        assert!(os.write_raw_bytes(&[1, 2]).is_err());
+        assert!(os.write_raw_varint64(0xffffffffffffffff).is_err());
+        os.write_raw_byte(0xa1).unwrap();
+        assert!(os.write_raw_byte(0xa1).is_err());
+        assert_eq!(3, os.total_bytes_written());
+        os.check_eof();
+    }
 }
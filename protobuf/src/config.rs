@@ -0,0 +1,79 @@
+//! Configuration assembled from several text format layers.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::text_format;
+use crate::text_format::ParseError;
+use crate::Message;
+use crate::ProtobufError;
+
+/// Error returned by [`load_layered`].
+#[derive(Debug)]
+pub enum LoadLayeredError {
+    /// Override with given index in `overrides` failed to parse.
+    Parse {
+        /// Index of the override.
+        layer: usize,
+        /// Parse error.
+        error: ParseError,
+    },
+    /// Required fields are not set after all overrides are applied.
+    NotInitialized(ProtobufError),
+}
+
+impl fmt::Display for LoadLayeredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadLayeredError::Parse { layer, error } => write!(f, "override {}: {}", layer, error),
+            LoadLayeredError::NotInitialized(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for LoadLayeredError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadLayeredError::Parse { error, .. } => Some(error),
+            LoadLayeredError::NotInitialized(e) => Some(e),
+        }
+    }
+}
+
+/// Apply text format overrides to a base message in order.
+///
+/// Each override is merged with [`text_format::merge_from_str`]:
+/// scalar fields set in an override replace previous values,
+/// repeated fields are appended to, map entries are replaced by key,
+/// and message fields are merged recursively.
+///
+/// Required fields are checked after all overrides are applied,
+/// so they can be specified in any layer.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::well_known_types::Duration;
+///
+/// let mut defaults = Duration::new();
+/// defaults.seconds = 10;
+/// defaults.nanos = 500;
+///
+/// let environment = "seconds: 20";
+/// let local = "nanos: 0";
+///
+/// let config = protobuf::config::load_layered(defaults, &[environment, local]).unwrap();
+/// assert_eq!(20, config.seconds);
+/// assert_eq!(0, config.nanos);
+/// ```
+pub fn load_layered<M: Message>(base: M, overrides: &[&str]) -> Result<M, LoadLayeredError> {
+    let mut message = base;
+    for (layer, text) in overrides.iter().enumerate() {
+        text_format::merge_from_str(&mut message, text)
+            .map_err(|error| LoadLayeredError::Parse { layer, error })?;
+    }
+    message
+        .check_initialized()
+        .map_err(LoadLayeredError::NotInitialized)?;
+    Ok(message)
+}
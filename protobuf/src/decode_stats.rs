@@ -0,0 +1,120 @@
+use crate::coded_input_stream::CodedInputStream;
+use crate::error::ProtobufResult;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::rt;
+use crate::wire_format;
+
+/// Statistics of decoded binary message, collected by
+/// [`Message::parse_from_bytes_with_stats`](crate::Message::parse_from_bytes_with_stats).
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::Message;
+/// use protobuf::well_known_types::Duration;
+///
+/// // `seconds` field and unknown field `15`
+/// let (duration, stats) = Duration::parse_from_bytes_with_stats(&[8, 10, 15 << 3, 7]).unwrap();
+/// assert_eq!(10, duration.seconds);
+/// assert_eq!(2, stats.fields);
+/// assert_eq!(2, stats.unknown_bytes);
+/// assert_eq!(0, stats.max_depth);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeStats {
+    /// Number of fields read, including fields of nested messages and unknown fields.
+    ///
+    /// Each element of non-packed repeated field is counted as a field,
+    /// packed repeated field is counted once, map entry is counted as a field.
+    pub fields: u64,
+    /// Number of bytes of unknown fields (including tags)
+    /// in the message and its nested messages.
+    pub unknown_bytes: u64,
+    /// Maximum nesting depth of messages, `0` if there are no nested messages.
+    pub max_depth: u32,
+}
+
+/// Scan serialized message and add its statistics to `stats`.
+pub(crate) fn collect(
+    bytes: &[u8],
+    descriptor: &MessageDescriptor,
+    depth: u32,
+    stats: &mut DecodeStats,
+) -> ProtobufResult<()> {
+    stats.max_depth = stats.max_depth.max(depth);
+    let mut is = CodedInputStream::from_bytes(bytes);
+    while !is.eof()? {
+        let start = is.pos();
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        stats.fields += 1;
+
+        let field = descriptor.get_field_by_number(field_number);
+        let message = match (&field, wire_type) {
+            (Some(field), wire_format::WireTypeLengthDelimited) => {
+                match field.runtime_field_type() {
+                    RuntimeFieldType::Singular(RuntimeTypeBox::Message(m))
+                    | RuntimeFieldType::Repeated(RuntimeTypeBox::Message(m)) => Some((m, false)),
+                    RuntimeFieldType::Map(_, RuntimeTypeBox::Message(m)) => Some((m, true)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        match message {
+            Some((m, map_entry)) => {
+                let len = is.read_raw_varint32()?;
+                let pos = is.pos() as usize;
+                is.skip_raw_bytes(len)?;
+                let nested = &bytes[pos..pos + len as usize];
+                if map_entry {
+                    collect_map_entry_value(nested, &m, depth + 1, stats)?;
+                } else {
+                    collect(nested, &m, depth + 1, stats)?;
+                }
+            }
+            None => {
+                match wire_type {
+                    wire_format::WireTypeStartGroup => rt::skip_group(&mut is)?,
+                    _ => is.skip_field(wire_type)?,
+                }
+                if field.is_none() {
+                    stats.unknown_bytes += is.pos() - start;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Map entry is counted as a single field, only message values are scanned.
+fn collect_map_entry_value(
+    bytes: &[u8],
+    value_descriptor: &MessageDescriptor,
+    depth: u32,
+    stats: &mut DecodeStats,
+) -> ProtobufResult<()> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    while !is.eof()? {
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        if field_number == 2 && wire_type == wire_format::WireTypeLengthDelimited {
+            let len = is.read_raw_varint32()?;
+            let pos = is.pos() as usize;
+            is.skip_raw_bytes(len)?;
+            collect(
+                &bytes[pos..pos + len as usize],
+                value_descriptor,
+                depth,
+                stats,
+            )?;
+        } else {
+            match wire_type {
+                wire_format::WireTypeStartGroup => rt::skip_group(&mut is)?,
+                _ => is.skip_field(wire_type)?,
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use std::cmp;
+use std::io::BufRead;
+use std::io::Read;
+use std::marker::PhantomData;
+
+use crate::coded_input_stream::CodedInputStream;
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::Message;
+
+/// Generous limit which still protects from allocating gigabytes.
+const DEFAULT_MAX_MESSAGE_SIZE: u64 = 64 << 20;
+
+/// Reader of a stream of length-delimited messages,
+/// as written by [`Message::write_length_delimited_to_writer`].
+///
+/// Messages are returned by iterator, iteration stops at end of stream
+/// or after the first error.
+///
+/// Messages larger than [`max_message_size`](DelimitedReader::set_max_message_size)
+/// are rejected before reading, so a corrupted length prefix does not make reader
+/// allocate or wait for huge amount of data.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::DelimitedReader;
+/// use protobuf::Message;
+/// use protobuf::well_known_types::Duration;
+///
+/// let mut bytes = Vec::new();
+/// for seconds in 1..=3 {
+///     let mut duration = Duration::new();
+///     duration.seconds = seconds;
+///     duration.write_length_delimited_to_vec(&mut bytes).unwrap();
+/// }
+///
+/// let mut progress = Vec::new();
+/// let mut reader = DelimitedReader::<Duration>::from_bytes(&bytes);
+/// reader.on_message(|bytes_consumed| progress.push(bytes_consumed));
+/// let seconds: Vec<i64> = reader.map(|d| d.unwrap().seconds).collect();
+/// assert_eq!(vec![1, 2, 3], seconds);
+/// assert_eq!(vec![3, 6, 9], progress);
+/// ```
+pub struct DelimitedReader<'a, M: Message> {
+    is: CodedInputStream<'a>,
+    max_message_size: u64,
+    on_message: Option<Box<dyn FnMut(u64) + 'a>>,
+    failed: bool,
+    _marker: PhantomData<M>,
+}
+
+impl<'a, M: Message> DelimitedReader<'a, M> {
+    /// Read messages from a `Read`.
+    pub fn new(read: &'a mut dyn Read) -> DelimitedReader<'a, M> {
+        DelimitedReader::from_coded_input_stream(CodedInputStream::new(read))
+    }
+
+    /// Read messages from a `BufRead`.
+    pub fn from_buffered_reader(buf_read: &'a mut dyn BufRead) -> DelimitedReader<'a, M> {
+        DelimitedReader::from_coded_input_stream(CodedInputStream::from_buffered_reader(buf_read))
+    }
+
+    /// Read messages from a byte slice.
+    pub fn from_bytes(bytes: &'a [u8]) -> DelimitedReader<'a, M> {
+        DelimitedReader::from_coded_input_stream(CodedInputStream::from_bytes(bytes))
+    }
+
+    fn from_coded_input_stream(is: CodedInputStream<'a>) -> DelimitedReader<'a, M> {
+        DelimitedReader {
+            is,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            on_message: None,
+            failed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set a callback invoked after each successfully read message
+    /// with the total number of bytes consumed so far, e. g. to report progress.
+    pub fn on_message(&mut self, callback: impl FnMut(u64) + 'a) {
+        self.on_message = Some(Box::new(callback));
+    }
+
+    /// Set maximum size of a single message, 64 MiB by default.
+    ///
+    /// Reading a message with larger length prefix fails with
+    /// [`WireError::MessageTooLarge`].
+    pub fn set_max_message_size(&mut self, max_message_size: u64) {
+        self.max_message_size = max_message_size;
+    }
+
+    /// Total number of bytes consumed from the stream so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.is.pos()
+    }
+
+    /// Read next message, return `None` at end of stream.
+    ///
+    /// When a message is malformed or not initialized, an error is returned
+    /// and the reader is positioned after that message, so the following
+    /// messages can still be read.
+    pub fn read_next(&mut self) -> ProtobufResult<Option<M>> {
+        if self.is.eof()? {
+            return Ok(None);
+        }
+        let pos = self.is.pos();
+        let size = self.is.read_raw_varint64()?;
+        if size > self.max_message_size {
+            return Err(ProtobufError::WireError(WireError::MessageTooLarge {
+                size,
+                max_size: self.max_message_size,
+                pos,
+            }));
+        }
+        let mut message = M::new();
+        let old_limit = self.is.push_limit(size)?;
+        let end = self.is.pos() + size;
+        let merged = message.merge_from(&mut self.is);
+        // restores the limit also when merge failed inside a nested message
+        self.is.pop_limit(old_limit);
+        if let Err(e) = merged {
+            // skip the rest of the message, ignoring errors like truncated input,
+            // which is reported by the next read
+            let _ = self.skip_to(end);
+            return Err(e);
+        }
+        message.check_initialized()?;
+        if let Some(on_message) = &mut self.on_message {
+            on_message(self.is.pos());
+        }
+        Ok(Some(message))
+    }
+
+    fn skip_to(&mut self, pos: u64) -> ProtobufResult<()> {
+        while self.is.pos() < pos {
+            let count = cmp::min(pos - self.is.pos(), u32::MAX as u64);
+            self.is.skip_raw_bytes(count as u32)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, M: Message> Iterator for DelimitedReader<'a, M> {
+    type Item = ProtobufResult<M>;
+
+    fn next(&mut self) -> Option<ProtobufResult<M>> {
+        if self.failed {
+            return None;
+        }
+        match self.read_next() {
+            Ok(message) => message.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DelimitedReader;
+    use crate::error::ProtobufError;
+    use crate::error::WireError;
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::Struct;
+    use crate::Message;
+
+    fn durations(count: i64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for seconds in 0..count {
+            let mut duration = Duration::new();
+            duration.seconds = seconds * 1000;
+            duration.write_length_delimited_to_vec(&mut bytes).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn read_from_reader() {
+        let bytes = durations(100);
+        let mut read = &bytes[..];
+        let mut count = 0;
+        let mut last_consumed = 0;
+        let mut reader = DelimitedReader::<Duration>::new(&mut read);
+        reader.on_message(|bytes_consumed| {
+            assert!(bytes_consumed > last_consumed);
+            last_consumed = bytes_consumed;
+            count += 1;
+        });
+        for (i, duration) in (&mut reader).enumerate() {
+            assert_eq!(i as i64 * 1000, duration.unwrap().seconds);
+        }
+        assert_eq!(bytes.len() as u64, reader.bytes_consumed());
+        drop(reader);
+        assert_eq!(100, count);
+        assert_eq!(bytes.len() as u64, last_consumed);
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = durations(3);
+        let mut reader = DelimitedReader::<Duration>::from_bytes(&bytes[..bytes.len() - 1]);
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn malformed_message() {
+        let mut bytes = durations(1);
+        // field 1 with incorrect wire type
+        bytes.extend_from_slice(&[2, 0x0d, 0x00]);
+        // truncated field 1 inside message
+        bytes.extend_from_slice(&[2, 0x08, 0x80]);
+        bytes.extend(durations(2));
+
+        let mut reader = DelimitedReader::<Duration>::from_bytes(&bytes);
+        assert_eq!(0, reader.read_next().unwrap().unwrap().seconds);
+        assert!(reader.read_next().is_err());
+        assert_eq!(4, reader.bytes_consumed());
+        assert!(reader.read_next().is_err());
+        assert_eq!(7, reader.bytes_consumed());
+        assert_eq!(0, reader.read_next().unwrap().unwrap().seconds);
+        assert_eq!(1000, reader.read_next().unwrap().unwrap().seconds);
+        assert!(reader.read_next().unwrap().is_none());
+    }
+
+    #[test]
+    fn malformed_nested_message() {
+        // `Struct.fields` map entry with incorrect wire type inside, then empty `Struct`
+        let bytes = [4, 0x0a, 0x02, 0x0f, 0x00, 0];
+        let mut reader = DelimitedReader::<Struct>::from_bytes(&bytes);
+        assert!(reader.read_next().is_err());
+        assert_eq!(5, reader.bytes_consumed());
+        assert!(reader.read_next().unwrap().unwrap().fields.is_empty());
+        assert!(reader.read_next().unwrap().is_none());
+    }
+
+    #[test]
+    fn message_too_large() {
+        let mut bytes = durations(2);
+        let bad_frame_pos = bytes.len() as u64;
+        // varint `1 << 40`
+        bytes.extend_from_slice(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x20]);
+        bytes.extend_from_slice(&[0; 10]);
+
+        let mut reader = DelimitedReader::<Duration>::from_bytes(&bytes);
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next() {
+            Some(Err(ProtobufError::WireError(WireError::MessageTooLarge {
+                size,
+                max_size,
+                pos,
+            }))) => {
+                assert_eq!(1 << 40, size);
+                assert_eq!(64 << 20, max_size);
+                assert_eq!(bad_frame_pos, pos);
+            }
+            r => panic!("expecting message too large error: {:?}", r),
+        }
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn max_message_size() {
+        let mut duration = Duration::new();
+        duration.seconds = 1;
+        let mut bytes = duration.write_length_delimited_to_bytes().unwrap();
+        duration.seconds = 1 << 40;
+        bytes.extend(duration.write_length_delimited_to_bytes().unwrap());
+
+        let mut reader = DelimitedReader::<Duration>::from_bytes(&bytes);
+        reader.set_max_message_size(4);
+        assert_eq!(1, reader.next().unwrap().unwrap().seconds);
+        let e = reader.next().unwrap().unwrap_err();
+        assert_eq!(
+            "message size 7 at position 3 exceeds maximum message size 4",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let mut reader = DelimitedReader::<Duration>::from_bytes(&[]);
+        assert_eq!(0, reader.bytes_consumed());
+        assert!(reader.read_next().unwrap().is_none());
+    }
+}
@@ -1675,8 +1675,8 @@ impl FieldDescriptorProto {
 
     pub fn get_label(&self) -> field_descriptor_proto::Label {
         match self.label {
-            Some(e) => e.enum_value_or(field_descriptor_proto::Label::LABEL_OPTIONAL),
-            None => field_descriptor_proto::Label::LABEL_OPTIONAL,
+            Some(e) => e.enum_value_or(field_descriptor_proto::Label::DEFAULT),
+            None => field_descriptor_proto::Label::DEFAULT,
         }
     }
 
@@ -1697,8 +1697,8 @@ impl FieldDescriptorProto {
 
     pub fn get_field_type(&self) -> field_descriptor_proto::Type {
         match self.field_type {
-            Some(e) => e.enum_value_or(field_descriptor_proto::Type::TYPE_DOUBLE),
-            None => field_descriptor_proto::Type::TYPE_DOUBLE,
+            Some(e) => e.enum_value_or(field_descriptor_proto::Type::DEFAULT),
+            None => field_descriptor_proto::Type::DEFAULT,
         }
     }
 
@@ -1915,13 +1915,13 @@ impl FieldDescriptorProto {
             "label",
             |m: &FieldDescriptorProto| { &m.label },
             |m: &mut FieldDescriptorProto| { &mut m.label },
-            field_descriptor_proto::Label::LABEL_OPTIONAL,
+            field_descriptor_proto::Label::DEFAULT,
         ));
         fields.push(crate::reflect::rt::v2::make_option_enum_accessor::<_, field_descriptor_proto::Type>(
             "type",
             |m: &FieldDescriptorProto| { &m.field_type },
             |m: &mut FieldDescriptorProto| { &mut m.field_type },
-            field_descriptor_proto::Type::TYPE_DOUBLE,
+            field_descriptor_proto::Type::DEFAULT,
         ));
         fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
             "type_name",
@@ -2291,7 +2291,7 @@ pub mod field_descriptor_proto {
     // Note, `Default` is implemented although default value is not 0
     impl ::std::default::Default for Type {
         fn default() -> Self {
-            Type::TYPE_DOUBLE
+            Type::DEFAULT
         }
     }
 
@@ -2300,6 +2300,9 @@ pub mod field_descriptor_proto {
     }
 
     impl Type {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: Type = Type::TYPE_DOUBLE;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<Type>("FieldDescriptorProto.Type", 0)
         }
@@ -2344,7 +2347,7 @@ pub mod field_descriptor_proto {
     // Note, `Default` is implemented although default value is not 0
     impl ::std::default::Default for Label {
         fn default() -> Self {
-            Label::LABEL_OPTIONAL
+            Label::DEFAULT
         }
     }
 
@@ -2353,6 +2356,9 @@ pub mod field_descriptor_proto {
     }
 
     impl Label {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: Label = Label::LABEL_OPTIONAL;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<Label>("FieldDescriptorProto.Label", 1)
         }
@@ -5039,7 +5045,7 @@ pub mod file_options {
     // Note, `Default` is implemented although default value is not 0
     impl ::std::default::Default for OptimizeMode {
         fn default() -> Self {
-            OptimizeMode::SPEED
+            OptimizeMode::DEFAULT
         }
     }
 
@@ -5048,6 +5054,9 @@ pub mod file_options {
     }
 
     impl OptimizeMode {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: OptimizeMode = OptimizeMode::SPEED;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<OptimizeMode>("FileOptions.OptimizeMode", 2)
         }
@@ -5824,7 +5833,7 @@ pub mod field_options {
 
     impl ::std::default::Default for CType {
         fn default() -> Self {
-            CType::STRING
+            CType::DEFAULT
         }
     }
 
@@ -5833,6 +5842,9 @@ pub mod field_options {
     }
 
     impl CType {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: CType = CType::STRING;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<CType>("FieldOptions.CType", 3)
         }
@@ -5876,7 +5888,7 @@ pub mod field_options {
 
     impl ::std::default::Default for JSType {
         fn default() -> Self {
-            JSType::JS_NORMAL
+            JSType::DEFAULT
         }
     }
 
@@ -5885,6 +5897,9 @@ pub mod field_options {
     }
 
     impl JSType {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: JSType = JSType::JS_NORMAL;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<JSType>("FieldOptions.JSType", 4)
         }
@@ -6855,7 +6870,7 @@ pub mod method_options {
 
     impl ::std::default::Default for IdempotencyLevel {
         fn default() -> Self {
-            IdempotencyLevel::IDEMPOTENCY_UNKNOWN
+            IdempotencyLevel::DEFAULT
         }
     }
 
@@ -6864,6 +6879,9 @@ pub mod method_options {
     }
 
     impl IdempotencyLevel {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: IdempotencyLevel = IdempotencyLevel::IDEMPOTENCY_UNKNOWN;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<IdempotencyLevel>("MethodOptions.IdempotencyLevel", 5)
         }
@@ -0,0 +1,28 @@
+/// Message which has a primary key field.
+///
+/// Implemented by generated code for messages with `rustproto.primary_key_field` option,
+/// so generic storage code can key messages without knowing field names.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use std::hash::Hash;
+/// # use protobuf::Entity;
+/// fn index<M>(messages: &[M]) -> HashMap<&M::PrimaryKey, &M>
+/// where
+///     M: Entity,
+///     M::PrimaryKey: Eq + Hash,
+/// {
+///     messages.iter().map(|m| (m.primary_key(), m)).collect()
+/// }
+/// ```
+pub trait Entity {
+    /// Type of primary key field, e. g. `str` or `u64`.
+    type PrimaryKey: ?Sized;
+
+    /// Value of primary key field.
+    ///
+    /// Default value of the field is returned if the field is not set.
+    fn primary_key(&self) -> &Self::PrimaryKey;
+}
@@ -1,4 +1,6 @@
 use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::marker;
 
 use crate::reflect::runtime_types::RuntimeTypeEnumOrUnknown;
@@ -119,6 +121,13 @@ impl<E: ProtobufEnum> fmt::Debug for ProtobufEnumOrUnknown<E> {
     }
 }
 
+/// Hash of the value number, so enums with `allow_alias` can be hashed too.
+impl<E> Hash for ProtobufEnumOrUnknown<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
 impl<E: ProtobufEnum + ProtobufValue> ProtobufValue for ProtobufEnumOrUnknown<E> {
     type RuntimeType = RuntimeTypeEnumOrUnknown<E>;
 }
@@ -12,18 +12,43 @@ pub type ProtobufResult<T> = Result<T, ProtobufError>;
 /// Users should not depend on specific values.
 #[derive(Debug)]
 pub enum WireError {
+    /// Unexpected end of input.
     UnexpectedEof,
+    /// Wire type does not match the field type.
     UnexpectedWireType(WireType),
+    /// Tag is not valid.
     IncorrectTag(u32),
+    /// Map entry is incomplete.
     // unused since https://github.com/stepancheg/rust-protobuf/issues/318
     IncompleteMap,
+    /// Varint is malformed.
     IncorrectVarint,
+    /// String is not valid UTF-8.
     Utf8Error,
+    /// Enum value is not known.
     InvalidEnumValue(i32),
+    /// Varint value does not fit into 32-bit field type.
+    IntegerOverflow {
+        /// Number of the field being read, `0` if no tag was read yet.
+        field_number: u32,
+    },
+    /// Message nesting is too deep.
     OverRecursionLimit,
+    /// Length prefix of a length-delimited message is greater than allowed.
+    MessageTooLarge {
+        /// Message size from length prefix.
+        size: u64,
+        /// Maximum allowed message size.
+        max_size: u64,
+        /// Stream position of the length prefix.
+        pos: u64,
+    },
+    /// Message is truncated.
     TruncatedMessage,
+    /// Limit overflows `u64`.
     // not really possible
     LimitOverflow,
+    /// New limit is greater than current limit.
     LimitIncrease,
 }
 
@@ -33,11 +58,25 @@ impl fmt::Display for WireError {
             WireError::Utf8Error => write!(f, "invalid UTF-8 sequence"),
             WireError::UnexpectedWireType(..) => write!(f, "unexpected wire type"),
             WireError::InvalidEnumValue(..) => write!(f, "invalid enum value"),
+            WireError::IntegerOverflow { field_number } => write!(
+                f,
+                "varint value is out of range of field {} type",
+                field_number
+            ),
             WireError::IncorrectTag(..) => write!(f, "incorrect tag"),
             WireError::IncorrectVarint => write!(f, "incorrect varint"),
             WireError::IncompleteMap => write!(f, "incomplete map"),
             WireError::UnexpectedEof => write!(f, "unexpected EOF"),
             WireError::OverRecursionLimit => write!(f, "over recursion limit"),
+            WireError::MessageTooLarge {
+                size,
+                max_size,
+                pos,
+            } => write!(
+                f,
+                "message size {} at position {} exceeds maximum message size {}",
+                size, pos, max_size
+            ),
             WireError::TruncatedMessage => write!(f, "truncated message"),
             WireError::LimitOverflow => write!(f, "limit overflow"),
             WireError::LimitIncrease => {
@@ -57,7 +96,12 @@ pub enum ProtobufError {
     /// Protocol contains a string which is not valid UTF-8 string
     Utf8(str::Utf8Error),
     /// Not all required fields of message set.
-    MessageNotInitialized(String),
+    MessageNotInitialized {
+        /// Message name.
+        message: String,
+        /// Paths of required fields which are not set, like `inner.id`.
+        missing_fields: Vec<String>,
+    },
 }
 
 impl fmt::Display for ProtobufError {
@@ -67,7 +111,15 @@ impl fmt::Display for ProtobufError {
             &ProtobufError::IoError(ref e) => write!(f, "IO error: {}", e),
             &ProtobufError::WireError(ref e) => fmt::Display::fmt(e, f),
             &ProtobufError::Utf8(ref e) => write!(f, "{}", e),
-            &ProtobufError::MessageNotInitialized { .. } => write!(f, "not all message fields set"),
+            ProtobufError::MessageNotInitialized {
+                message,
+                missing_fields,
+            } => write!(
+                f,
+                "not all message fields set: message `{}`, required fields not set: {}",
+                message,
+                missing_fields.join(", ")
+            ),
         }
     }
 }
@@ -102,10 +154,9 @@ impl From<ProtobufError> for io::Error {
             ProtobufError::WireError(e) => {
                 io::Error::new(io::ErrorKind::InvalidData, ProtobufError::WireError(e))
             }
-            ProtobufError::MessageNotInitialized(message) => io::Error::new(
-                io::ErrorKind::InvalidInput,
-                ProtobufError::MessageNotInitialized(message),
-            ),
+            e @ ProtobufError::MessageNotInitialized { .. } => {
+                io::Error::new(io::ErrorKind::InvalidInput, e)
+            }
             e => io::Error::new(io::ErrorKind::Other, Box::new(e)),
         }
     }
@@ -0,0 +1,183 @@
+use std::fmt;
+
+/// Set of field numbers of a message which are present.
+///
+/// Returned by generated `fields_present` function which is generated
+/// when `generate_presence_set` codegen option is enabled.
+///
+/// Field number `n` is stored in bit `n % 64` of word `n / 64`,
+/// so memory used by the set is proportional to the largest field number stored.
+/// Sets which contain the same field numbers are equal regardless
+/// of how they were constructed.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::FieldPresenceSet;
+/// let mut a = FieldPresenceSet::new();
+/// a.insert(1);
+/// a.insert(3);
+///
+/// let mut b = FieldPresenceSet::new();
+/// b.insert(3);
+///
+/// assert!(a.contains(1));
+/// assert!(!b.contains(1));
+/// assert_eq!(vec![1], a.difference(&b).iter().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPresenceSet {
+    // Invariant: last word, if any, is not zero.
+    words: Vec<u64>,
+}
+
+impl FieldPresenceSet {
+    /// Create an empty set.
+    pub fn new() -> FieldPresenceSet {
+        FieldPresenceSet::default()
+    }
+
+    fn word_and_mask(number: u32) -> (usize, u64) {
+        ((number / 64) as usize, 1 << (number % 64))
+    }
+
+    fn trim(&mut self) {
+        while let Some(0) = self.words.last() {
+            self.words.pop();
+        }
+    }
+
+    /// Add field number to the set.
+    pub fn insert(&mut self, number: u32) {
+        let (word, mask) = FieldPresenceSet::word_and_mask(number);
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= mask;
+    }
+
+    /// Remove field number from the set.
+    pub fn remove(&mut self, number: u32) {
+        let (word, mask) = FieldPresenceSet::word_and_mask(number);
+        if let Some(w) = self.words.get_mut(word) {
+            *w &= !mask;
+        }
+        self.trim();
+    }
+
+    /// Check if field number is in the set.
+    pub fn contains(&self, number: u32) -> bool {
+        let (word, mask) = FieldPresenceSet::word_and_mask(number);
+        match self.words.get(word) {
+            Some(w) => w & mask != 0,
+            None => false,
+        }
+    }
+
+    /// Check if no fields are present.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Number of fields in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Iterate field numbers in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &w)| {
+            (0..64)
+                .filter(move |b| w & (1 << b) != 0)
+                .map(move |b| i as u32 * 64 + b)
+        })
+    }
+
+    fn zip_with(&self, other: &FieldPresenceSet, f: impl Fn(u64, u64) -> u64) -> FieldPresenceSet {
+        let len = self.words.len().max(other.words.len());
+        let word = |words: &[u64], i: usize| words.get(i).cloned().unwrap_or(0);
+        let mut r = FieldPresenceSet {
+            words: (0..len)
+                .map(|i| f(word(&self.words, i), word(&other.words, i)))
+                .collect(),
+        };
+        r.trim();
+        r
+    }
+
+    /// Fields present in either set.
+    pub fn union(&self, other: &FieldPresenceSet) -> FieldPresenceSet {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// Fields present in both sets.
+    pub fn intersection(&self, other: &FieldPresenceSet) -> FieldPresenceSet {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// Fields present in `self` but not in `other`.
+    pub fn difference(&self, other: &FieldPresenceSet) -> FieldPresenceSet {
+        self.zip_with(other, |a, b| a & !b)
+    }
+}
+
+impl fmt::Debug for FieldPresenceSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FieldPresenceSet;
+
+    #[test]
+    fn insert_remove() {
+        let mut s = FieldPresenceSet::new();
+        assert!(s.is_empty());
+        s.insert(1);
+        s.insert(64);
+        s.insert(200);
+        assert_eq!(3, s.len());
+        assert!(s.contains(1));
+        assert!(s.contains(64));
+        assert!(s.contains(200));
+        assert!(!s.contains(2));
+        assert!(!s.contains(1000));
+        assert_eq!(vec![1, 64, 200], s.iter().collect::<Vec<_>>());
+
+        s.remove(200);
+        s.remove(64);
+        let mut expected = FieldPresenceSet::new();
+        expected.insert(1);
+        assert_eq!(expected, s);
+
+        s.remove(1);
+        assert!(s.is_empty());
+        assert_eq!(FieldPresenceSet::new(), s);
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut a = FieldPresenceSet::new();
+        a.insert(1);
+        a.insert(100);
+        let mut b = FieldPresenceSet::new();
+        b.insert(1);
+        b.insert(2);
+
+        assert_eq!(vec![1, 2, 100], a.union(&b).iter().collect::<Vec<_>>());
+        assert_eq!(vec![1], a.intersection(&b).iter().collect::<Vec<_>>());
+        assert_eq!(vec![100], a.difference(&b).iter().collect::<Vec<_>>());
+        assert!(b.difference(&b).is_empty());
+        assert_eq!(FieldPresenceSet::new(), b.difference(&b));
+    }
+
+    #[test]
+    fn debug() {
+        let mut s = FieldPresenceSet::new();
+        s.insert(3);
+        s.insert(5);
+        assert_eq!("{3, 5}", format!("{:?}", s));
+    }
+}
@@ -1,7 +1,7 @@
 //! JSON printer and parser which tries to follow
 //! [protobuf conventions](https://developers.google.com/protocol-buffers/docs/proto3#json)
 
-mod base64;
+pub(crate) mod base64;
 mod float;
 mod json_name;
 mod parse;
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::f32;
 use std::f64;
 use std::fmt;
@@ -50,6 +51,7 @@ use crate::well_known_types::Value;
 enum ParseErrorWithoutLocInner {
     TokenizerError(TokenizerError),
     UnknownFieldName(String),
+    OneofFieldsConflict(String, String),
     UnknownEnumVariantName(String),
     UnknownEnumVariantNumber(i32),
     FromBase64Error(FromBase64Error),
@@ -77,6 +79,13 @@ impl fmt::Display for ParseErrorWithoutLoc {
             ParseErrorWithoutLocInner::UnknownFieldName(n) => {
                 write!(f, "unknown field name: {}", n)
             }
+            ParseErrorWithoutLocInner::OneofFieldsConflict(a, b) => {
+                write!(
+                    f,
+                    "fields `{}` and `{}` are members of the same oneof",
+                    a, b
+                )
+            }
             ParseErrorWithoutLocInner::UnknownEnumVariantName(n) => {
                 write!(f, "unknown enum variant name: {}", n)
             }
@@ -700,6 +709,8 @@ impl<'a> Parser<'a> {
 
         self.tokenizer.next_symbol_expect_eq('{')?;
         let mut first = true;
+        // Oneof index and name of oneof member field seen in this object
+        let mut oneofs_seen: Vec<(i32, String)> = Vec::new();
         while !self.tokenizer.next_symbol_if_eq('}')? {
             if !first {
                 self.tokenizer.next_symbol_expect_eq(',')?;
@@ -709,8 +720,29 @@ impl<'a> Parser<'a> {
             let field_name = self.read_string()?;
             // Proto3 JSON parsers are required to accept both
             // the converted `lowerCamelCase` name and the proto field name.
-            match descriptor.get_field_by_name_or_json_name(&field_name) {
+            let field = descriptor
+                .get_field_by_name_or_json_name(&field_name)
+                .or_else(|| {
+                    let alias = self.parse_options.field_aliases.get(&field_name)?;
+                    descriptor.get_field_by_name_or_json_name(alias)
+                });
+            match field {
                 Some(field) => {
+                    if field.get_proto().has_oneof_index() {
+                        let oneof_index = field.get_proto().get_oneof_index();
+                        match oneofs_seen.iter().find(|(i, _)| *i == oneof_index) {
+                            Some((_, seen)) if seen != field.get_name() => {
+                                return Err(ParseErrorWithoutLoc(
+                                    ParseErrorWithoutLocInner::OneofFieldsConflict(
+                                        seen.clone(),
+                                        field.get_name().to_owned(),
+                                    ),
+                                ));
+                            }
+                            Some(..) => {}
+                            None => oneofs_seen.push((oneof_index, field.get_name().to_owned())),
+                        }
+                    }
                     self.tokenizer.next_symbol_expect_eq(':')?;
                     self.merge_field(message, &field)?;
                 }
@@ -894,6 +926,12 @@ pub struct ParseOptions {
     /// When `true` fields with unknown names are ignored.
     /// When `false` parser returns an error on unknown field.
     pub ignore_unknown_fields: bool,
+    /// Map from field names found in input to actual field names.
+    ///
+    /// Alias is used only when a message has no field with the name
+    /// (or JSON name) found in input, so aliases apply to all messages,
+    /// and allow reading data written before a field was renamed.
+    pub field_aliases: HashMap<String, String>,
     /// Prevent initializing `ParseOptions` enumerating all field.
     pub _future_options: (),
 }
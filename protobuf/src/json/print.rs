@@ -1,3 +1,4 @@
+use std::cmp;
 use std::f32;
 use std::f64;
 use std::fmt;
@@ -41,6 +42,7 @@ enum PrintErrorInner {
     Fmt(fmt::Error),
     AnyPrintingIsNotImplemented,
     TimestampNegativeNanos,
+    ValueNumberIsNotFinite,
 }
 
 /// Print to JSON error.
@@ -210,16 +212,30 @@ impl<'a> PrintableToJson for ReflectValueRef<'a> {
     }
 }
 
+/// Number of fractional second digits to print for `Duration` or `Timestamp`.
+fn fraction_digits(nanos: u32, print_options: &PrintOptions) -> usize {
+    match print_options.timestamp_fraction_digits {
+        Some(digits) => cmp::min(digits, 9) as usize,
+        None => match (nanos, nanos % 1_000_000, nanos % 1_000) {
+            (0, _, _) => 0,
+            (_, 0, _) => 3,
+            (_, _, 0) => 6,
+            _ => 9,
+        },
+    }
+}
+
 impl PrintableToJson for Duration {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
         let sign = if self.seconds >= 0 { "" } else { "-" };
-        Ok(write!(
-            w.buf,
-            "\"{}{}.{:09}s\"",
-            sign,
-            self.seconds.abs(),
-            self.nanos.abs()
-        )?)
+        let nanos = self.nanos.unsigned_abs();
+        write!(w.buf, "\"{}{}", sign, self.seconds.abs())?;
+        let digits = fraction_digits(nanos, &w.print_options);
+        if digits != 0 {
+            let fraction = nanos / 10u32.pow(9 - digits as u32);
+            write!(w.buf, ".{:0digits$}", fraction, digits = digits)?;
+        }
+        Ok(write!(w.buf, "s\"")?)
     }
 }
 
@@ -229,7 +245,8 @@ impl PrintableToJson for Timestamp {
             return Err(PrintError(PrintErrorInner::TimestampNegativeNanos));
         }
         let tm_utc = TmUtc::from_protobuf_timestamp(self.seconds, self.nanos as u32);
-        w.print_printable(&tm_utc.to_string())
+        let digits = fraction_digits(self.nanos as u32, &w.print_options);
+        w.print_printable(&format!("{:.*}", digits, tm_utc))
     }
 }
 
@@ -255,7 +272,14 @@ impl PrintableToJson for Value {
                 w.print_wk_null_value(&null_value.enum_value_or_default())
             }
             Some(value::Kind::bool_value(b)) => w.print_printable(&b),
-            Some(value::Kind::number_value(n)) => w.print_printable(&n),
+            Some(value::Kind::number_value(n)) => {
+                // NaN and infinity would be printed as strings,
+                // and parsed back as `string_value`
+                if !n.is_finite() {
+                    return Err(PrintError(PrintErrorInner::ValueNumberIsNotFinite));
+                }
+                w.print_printable(&n)
+            }
             Some(value::Kind::string_value(ref s)) => w.print_printable::<String>(&s),
             Some(value::Kind::struct_value(ref s)) => w.print_printable(&s),
             Some(value::Kind::list_value(ref l)) => w.print_printable(&l),
@@ -551,6 +575,16 @@ pub struct PrintOptions {
     pub proto_field_name: bool,
     /// Output field default values.
     pub always_output_default_values: bool,
+    /// Number of fractional second digits in `Timestamp` and `Duration`.
+    ///
+    /// By default 0, 3, 6 or 9 digits are printed, as few as needed to represent
+    /// the value exactly, as specified by protobuf. When set, exactly this
+    /// number of digits (at most 9) is printed, padding with zeros
+    /// or truncating the value.
+    ///
+    /// Previous versions of rust-protobuf always printed 9 digits,
+    /// use `Some(9)` to keep that output.
+    pub timestamp_fraction_digits: Option<u8>,
     /// Prevent initializing `PrintOptions` enumerating all field.
     pub _future_options: (),
 }
@@ -11,18 +11,25 @@
 extern crate bytes;
 #[cfg(feature = "with-serde")]
 extern crate serde;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
 #[macro_use]
 #[cfg(feature = "with-serde")]
 extern crate serde_derive;
 pub use crate::clear::Clear;
 pub use crate::coded_input_stream::CodedInputStream;
 pub use crate::coded_output_stream::CodedOutputStream;
+pub use crate::decode_stats::DecodeStats;
+pub use crate::delimited::DelimitedReader;
+pub use crate::entity::Entity;
 pub use crate::enums::ProtobufEnum;
 pub use crate::enums::ProtobufEnumOrUnknown;
+pub use crate::field_presence_set::FieldPresenceSet;
 pub use crate::message::Message;
 pub use crate::message_dyn::MessageDyn;
 pub use crate::message_field::MessageField;
 pub use crate::oneof::Oneof;
+pub use crate::parse_options::ParseOptions;
 pub use crate::unknown::UnknownFields;
 pub use crate::unknown::UnknownFieldsIter;
 pub use crate::unknown::UnknownValue;
@@ -34,6 +41,7 @@ pub mod wire_format;
 pub use crate::chars::Chars;
 pub use crate::error::ProtobufError;
 pub use crate::error::ProtobufResult;
+pub use crate::error::WireError;
 
 // generated
 pub mod descriptor;
@@ -44,16 +52,23 @@ pub mod rustproto;
 mod clear;
 mod coded_input_stream;
 mod coded_output_stream;
+pub mod config;
+mod decode_stats;
+mod delimited;
+mod entity;
 mod enums;
 mod error;
 pub mod ext;
+mod field_presence_set;
 pub mod json;
 mod lazy_v2;
 mod message;
 mod message_dyn;
 mod message_field;
 mod oneof;
+mod parse_options;
 pub mod reflect;
+mod repeated_target;
 pub mod rt;
 pub mod text_format;
 pub mod well_known_types;
@@ -9,12 +9,18 @@ use crate::clear::Clear;
 use crate::coded_input_stream::CodedInputStream;
 use crate::coded_output_stream::CodedOutputStream;
 use crate::coded_output_stream::WithCodedOutputStream;
-use crate::error::ProtobufError;
+use crate::decode_stats;
+use crate::decode_stats::DecodeStats;
 use crate::error::ProtobufResult;
 use crate::message_dyn::MessageDyn;
+use crate::parse_options::ParseOptions;
 use crate::reflect::reflect_eq::ReflectEqMode;
+use crate::reflect::CanonicalizeOptions;
+use crate::reflect::FieldOrder;
 use crate::reflect::MessageDescriptor;
+use crate::rt;
 use crate::unknown::UnknownFields;
+use crate::wire_format;
 
 /// Trait implemented for all generated structs for protobuf messages.
 ///
@@ -66,6 +72,9 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     fn write_to_with_cached_sizes(&self, os: &mut CodedOutputStream) -> ProtobufResult<()>;
 
     /// Compute and cache size of this message and all nested messages
+    ///
+    /// Sizes are cached in atomics, so it is safe to serialize
+    /// the same message from multiple threads, see [`CachedSize`](crate::rt::CachedSize).
     fn compute_size(&self) -> u32;
 
     /// Get size previously computed by `compute_size`.
@@ -135,6 +144,92 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         Ok(r)
     }
 
+    /// Parse message from byte array and collect [`DecodeStats`] about it:
+    /// number of fields, bytes of unknown fields and nesting depth.
+    ///
+    /// Statistics are collected by a separate scan of the input,
+    /// so regular parse functions are not affected.
+    fn parse_from_bytes_with_stats(bytes: &[u8]) -> ProtobufResult<(Self, DecodeStats)>
+    where
+        Self: Sized,
+    {
+        let r = Self::parse_from_bytes(bytes)?;
+        let mut stats = DecodeStats::default();
+        decode_stats::collect(bytes, &Self::descriptor_static(), 0, &mut stats)?;
+        Ok((r, stats))
+    }
+
+    /// Parse message from byte array with options.
+    ///
+    /// Unlike [`parse_from_bytes`](Message::parse_from_bytes),
+    /// the message is checked for initialization only if
+    /// [`ParseOptions::require_initialized`] is set.
+    fn parse_from_bytes_with_options(bytes: &[u8], options: &ParseOptions) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut r: Self = Message::new();
+        let mut is = CodedInputStream::from_bytes(bytes);
+        is.set_reject_integer_overflow(options.reject_integer_overflow);
+        r.merge_from(&mut is)?;
+        if options.require_initialized {
+            r.check_initialized()?;
+        }
+        Ok(r)
+    }
+
+    /// Parse only leading fields of the message from byte array.
+    ///
+    /// Parsing stops before the first field with number greater than
+    /// `up_to_field_number`, or before the field which would end past `max_bytes`
+    /// from the start of the input; the remainder of the input is not decoded.
+    ///
+    /// This function assumes fields are serialized in ascending field number order
+    /// (which is what rust-protobuf and most other implementations do),
+    /// otherwise low-numbered fields written after the threshold are not read.
+    ///
+    /// Resulting message is not checked for initialization,
+    /// because required fields may be located after the prefix.
+    fn parse_prefix_from_bytes(
+        bytes: &[u8],
+        up_to_field_number: u32,
+        max_bytes: usize,
+    ) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        let len = message_prefix_len(bytes, up_to_field_number, max_bytes)?;
+        let mut r: Self = Message::new();
+        r.merge_from_bytes(&bytes[..len])?;
+        Ok(r)
+    }
+
+    /// Parse message from the start of byte array which may contain trailing data.
+    ///
+    /// Returns parsed message and the number of bytes of the message.
+    ///
+    /// Fields are decoded until one of the following is found:
+    /// * end of input
+    /// * bytes which are not a valid field tag: zero byte (e. g. zero padding),
+    ///   a tag with field number zero or with unknown wire type,
+    ///   or an incomplete varint at the end of input
+    /// * end group tag (useful when the message is embedded in a group)
+    ///
+    /// Bytes of the stop condition are not counted as consumed.
+    /// Trailing data which starts with a valid tag is decoded as a field,
+    /// and an error is returned if that field is malformed.
+    ///
+    /// Unlike [`parse_prefix_from_bytes`](Message::parse_prefix_from_bytes),
+    /// parsed message is checked for initialization.
+    fn parse_from_bytes_prefix(bytes: &[u8]) -> ProtobufResult<(Self, usize)>
+    where
+        Self: Sized,
+    {
+        let len = message_len_until_terminator(bytes)?;
+        let r = Self::parse_from_bytes(&bytes[..len])?;
+        Ok((r, len))
+    }
+
     /// Parse message from `Bytes` object.
     /// Resulting message may share references to the passed bytes object.
     #[cfg(feature = "bytes")]
@@ -149,14 +244,10 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     }
 
     /// Check if all required fields of this object are initialized.
+    ///
+    /// Returned error lists paths of all missing fields.
     fn check_initialized(&self) -> ProtobufResult<()> {
-        if !self.is_initialized() {
-            Err(ProtobufError::MessageNotInitialized(
-                self.descriptor_by_instance().name().to_owned(),
-            ))
-        } else {
-            Ok(())
-        }
+        <dyn MessageDyn>::check_initialized_dyn(self)
     }
 
     /// Write the message to the writer.
@@ -190,6 +281,20 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         Ok(v)
     }
 
+    /// Write the message to bytes vec with fields in specified order, map entries
+    /// sorted by key and unknown fields sorted by field number.
+    ///
+    /// Unlike [`Message::write_to_bytes`], output does not depend on map iteration order,
+    /// so it can be used where canonical encoding is required.
+    /// This function uses reflection, so it is slower than [`Message::write_to_bytes`].
+    ///
+    /// # See also
+    ///
+    /// [`dyn Message::write_to_bytes_ordered()`], `dyn` version of this function.
+    fn write_to_bytes_ordered(&self, order: FieldOrder) -> ProtobufResult<Vec<u8>> {
+        <dyn MessageDyn>::write_to_bytes_ordered(self, order)
+    }
+
     /// Write the message to the writer, prepend the message with message length
     /// encoded as varint.
     fn write_length_delimited_to_writer(&self, w: &mut dyn Write) -> ProtobufResult<()> {
@@ -237,4 +342,116 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     fn reflect_eq(&self, other: &Self, mode: &ReflectEqMode) -> bool {
         <dyn MessageDyn>::reflect_eq_dyn(self, other, mode)
     }
+
+    /// Stable hash of message content, computed using reflection.
+    ///
+    /// Messages which are equal have equal hashes even if their serialized bytes differ:
+    /// fields are hashed in field number order, map entries are hashed regardless of
+    /// iteration order, `0.0` and `-0.0` hash the same, as do all `NaN` values.
+    ///
+    /// Hash does not depend on the process or Rust version, so it can be stored,
+    /// but it may change in future versions of rust-protobuf.
+    ///
+    /// Unknown fields are ignored.
+    fn content_hash(&self) -> u64 {
+        <dyn MessageDyn>::content_hash_dyn(self)
+    }
+
+    /// Check if fields set in `pattern` have the same values in this message,
+    /// other fields of this message are ignored. Useful in test assertions
+    /// against large messages.
+    ///
+    /// * Singular fields set in `pattern` must be set in this message too.
+    ///   Message fields are compared recursively with the same rules,
+    ///   other fields are compared for equality.
+    /// * Non-empty repeated fields in `pattern` must have the same number of elements
+    ///   in this message, elements are compared pairwise in order like singular fields.
+    /// * For non-empty map fields in `pattern`, this message map must contain
+    ///   all keys of `pattern` map with matching values, other entries are ignored.
+    ///
+    /// Empty repeated and map fields, and proto3 fields with default values
+    /// (except `optional` fields) are not set, so they match anything.
+    /// Unknown fields are ignored.
+    ///
+    /// # See also
+    ///
+    /// [`dyn Message::matches_subset_dyn()`], `dyn` version of this function.
+    fn matches_subset(&self, pattern: &Self) -> bool {
+        <dyn MessageDyn>::matches_subset_dyn(self, pattern)
+    }
+
+    /// Normalize message in place, so messages which are equal
+    /// are more likely to be serialized to the same bytes.
+    ///
+    /// This function:
+    /// * recursively normalizes set message fields, elements of repeated message fields
+    ///   and message values of map fields
+    /// * clears unknown fields of the message and nested messages
+    ///   if [`CanonicalizeOptions::discard_unknown_fields`] is set
+    ///
+    /// Other fields are not modified. In particular, proto2 and proto3 `optional` fields
+    /// explicitly set to default values stay set, because they are serialized
+    /// and are not equal to unset fields. Proto3 fields without presence
+    /// which have default values are never serialized, so they need no normalization.
+    ///
+    /// Normalized messages are not guaranteed to be serialized to identical bytes.
+    /// Map fields are stored in `HashMap` which has no key order to normalize,
+    /// so [`Message::write_to_bytes`] writes map entries in unspecified order.
+    /// Use [`Message::write_to_bytes_ordered`] after normalization when canonical bytes
+    /// are needed: it writes map entries sorted by key and unknown fields by field number.
+    ///
+    /// # See also
+    ///
+    /// [`dyn Message::canonicalize_dyn()`], `dyn` version of this function.
+    fn canonicalize(&mut self, options: &CanonicalizeOptions) {
+        <dyn MessageDyn>::canonicalize_dyn(self, options)
+    }
+}
+
+/// Length of the serialized message for `parse_from_bytes_prefix`.
+fn message_len_until_terminator(bytes: &[u8]) -> ProtobufResult<usize> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let mut len = 0;
+    while !is.eof()? {
+        let tag = match is.read_raw_varint32() {
+            Ok(tag) => tag,
+            Err(_) => break,
+        };
+        let wire_type = match wire_format::Tag::new(tag) {
+            Some(tag) => tag.unpack().1,
+            None => break,
+        };
+        match wire_type {
+            wire_format::WireTypeEndGroup => break,
+            wire_format::WireTypeStartGroup => rt::skip_group(&mut is)?,
+            _ => is.skip_field(wire_type)?,
+        }
+        len = is.pos() as usize;
+    }
+    Ok(len)
+}
+
+/// Length of the serialized message prefix for `parse_prefix_from_bytes`.
+fn message_prefix_len(
+    bytes: &[u8],
+    up_to_field_number: u32,
+    max_bytes: usize,
+) -> ProtobufResult<usize> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let mut len = 0;
+    while !is.eof()? {
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        if field_number > up_to_field_number {
+            break;
+        }
+        match wire_type {
+            wire_format::WireTypeStartGroup => rt::skip_group(&mut is)?,
+            _ => is.skip_field(wire_type)?,
+        }
+        if is.pos() > max_bytes as u64 {
+            break;
+        }
+        len = is.pos() as usize;
+    }
+    Ok(len)
 }
@@ -4,8 +4,17 @@ use std::fmt;
 use std::io::Write;
 
 use crate::coded_output_stream::WithCodedOutputStream;
+use crate::reflect::canonicalize;
+use crate::reflect::content_hash;
+use crate::reflect::ordered_write;
+use crate::reflect::required::missing_required_fields;
+use crate::reflect::subset;
+use crate::reflect::CanonicalizeOptions;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FieldOrder;
 use crate::reflect::MessageDescriptor;
 use crate::reflect::ReflectEqMode;
+use crate::reflect::ReflectFieldRef;
 use crate::CodedInputStream;
 use crate::CodedOutputStream;
 use crate::Message;
@@ -27,6 +36,12 @@ pub trait MessageDyn: Any + fmt::Debug + Send + Sync + 'static {
     /// Compute (and cache) the message size.
     fn compute_size_dyn(&self) -> u32;
 
+    /// Get size previously computed by `compute_size_dyn`.
+    ///
+    /// Size is not invalidated when the message is modified,
+    /// and it is zero if the size was never computed.
+    fn get_cached_size_dyn(&self) -> u32;
+
     /// True iff all required fields are initialized.
     /// Always returns `true` for protobuf 3.
     fn is_initialized_dyn(&self) -> bool;
@@ -54,6 +69,10 @@ impl<M: Message> MessageDyn for M {
         self.compute_size()
     }
 
+    fn get_cached_size_dyn(&self) -> u32 {
+        self.get_cached_size()
+    }
+
     fn is_initialized_dyn(&self) -> bool {
         self.is_initialized()
     }
@@ -71,14 +90,42 @@ impl dyn MessageDyn {
     /// Check if all required fields of this object are initialized.
     pub fn check_initialized_dyn(&self) -> ProtobufResult<()> {
         if !self.is_initialized_dyn() {
-            Err(ProtobufError::MessageNotInitialized(
-                self.descriptor_dyn().name().to_owned(),
-            ))
+            Err(ProtobufError::MessageNotInitialized {
+                message: self.descriptor_dyn().name().to_owned(),
+                missing_fields: missing_required_fields(self),
+            })
         } else {
             Ok(())
         }
     }
 
+    /// Iterate over fields which are set, in declaration order.
+    ///
+    /// Singular fields with presence (proto2 fields, proto3 `optional` fields,
+    /// message fields and oneof members) are returned when present,
+    /// other proto3 fields are returned when value is not default.
+    /// So for a oneof only the active member is returned.
+    /// Repeated and map fields are returned when not empty.
+    pub fn iter_set_fields(
+        &self,
+    ) -> impl Iterator<Item = (FieldDescriptor, ReflectFieldRef<'_>)> + '_ {
+        let fields: Vec<FieldDescriptor> = self.descriptor_dyn().fields().collect();
+        fields.into_iter().filter_map(move |field| {
+            let value = field.get_reflect(self);
+            let set = match &value {
+                ReflectFieldRef::Optional(None) => false,
+                ReflectFieldRef::Optional(Some(v)) => field.has_presence() || v.is_non_zero(),
+                ReflectFieldRef::Repeated(r) => !r.is_empty(),
+                ReflectFieldRef::Map(m) => !m.is_empty(),
+            };
+            if set {
+                Some((field, value))
+            } else {
+                None
+            }
+        })
+    }
+
     /// Write the message to the writer.
     pub fn write_to_writer_dyn(&self, w: &mut dyn Write) -> ProtobufResult<()> {
         w.with_coded_output_stream(|os| self.write_to_dyn(os))
@@ -139,6 +186,16 @@ impl dyn MessageDyn {
         Ok(v)
     }
 
+    /// Write the message to bytes vec with fields in specified order.
+    ///
+    /// # See also
+    ///
+    /// [`Message::write_to_bytes_ordered()`], non-`dyn` version of this function.
+    pub fn write_to_bytes_ordered(&self, order: FieldOrder) -> ProtobufResult<Vec<u8>> {
+        self.check_initialized_dyn()?;
+        ordered_write::write_to_bytes_ordered(self, order)
+    }
+
     /// Write the message to the stream prepending the message with message length
     /// encoded as varint.
     pub fn write_length_delimited_to_dyn(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
@@ -228,6 +285,33 @@ impl dyn MessageDyn {
     pub fn reflect_eq_dyn(&self, other: &dyn MessageDyn, mode: &ReflectEqMode) -> bool {
         MessageDescriptor::reflect_eq_maybe_unrelated(self, other, mode)
     }
+
+    /// Stable hash of message content.
+    ///
+    /// # See also
+    ///
+    /// [`Message::content_hash()`], non-`dyn` version of this function.
+    pub fn content_hash_dyn(&self) -> u64 {
+        content_hash::content_hash(self)
+    }
+
+    /// Check if fields set in `pattern` have the same values in this message.
+    ///
+    /// # See also
+    ///
+    /// [`Message::matches_subset()`], non-`dyn` version of this function.
+    pub fn matches_subset_dyn(&self, pattern: &dyn MessageDyn) -> bool {
+        subset::matches_subset(self, pattern)
+    }
+
+    /// Normalize message in place.
+    ///
+    /// # See also
+    ///
+    /// [`Message::canonicalize()`], non-`dyn` version of this function.
+    pub fn canonicalize_dyn(&mut self, options: &CanonicalizeOptions) {
+        canonicalize::canonicalize(self, options)
+    }
 }
 
 impl Clone for Box<dyn MessageDyn> {
@@ -0,0 +1,33 @@
+/// Binary format parse options.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::Message;
+/// use protobuf::ParseOptions;
+/// use protobuf::well_known_types::Duration;
+///
+/// let parse_options = ParseOptions {
+///     require_initialized: true,
+///     ..Default::default()
+/// };
+/// let duration = Duration::parse_from_bytes_with_options(&[8, 10], &parse_options).unwrap();
+/// assert_eq!(10, duration.seconds);
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct ParseOptions {
+    /// Fail parsing with [`ProtobufError::MessageNotInitialized`](crate::ProtobufError::MessageNotInitialized)
+    /// if any `required` field of the message or its nested messages is not set.
+    ///
+    /// Unset by default, so the message is returned as decoded,
+    /// and it can be checked later with [`Message::check_initialized`](crate::Message::check_initialized).
+    pub require_initialized: bool,
+    /// Fail parsing with [`WireError::IntegerOverflow`](crate::WireError::IntegerOverflow)
+    /// if a value of `int32`, `uint32`, `sint32` or enum field does not fit into 32 bits.
+    ///
+    /// Unset by default, so such values are truncated like in other protobuf implementations,
+    /// see [`CodedInputStream::set_reject_integer_overflow`](crate::CodedInputStream::set_reject_integer_overflow).
+    pub reject_integer_overflow: bool,
+    /// Prevent initializing `ParseOptions` enumerating all field.
+    pub _future_options: (),
+}
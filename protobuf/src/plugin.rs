@@ -1189,7 +1189,7 @@ pub mod code_generator_response {
 
     impl ::std::default::Default for Feature {
         fn default() -> Self {
-            Feature::FEATURE_NONE
+            Feature::DEFAULT
         }
     }
 
@@ -1198,6 +1198,9 @@ pub mod code_generator_response {
     }
 
     impl Feature {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: Feature = Feature::FEATURE_NONE;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<Feature>("CodeGeneratorResponse.Feature", 0)
         }
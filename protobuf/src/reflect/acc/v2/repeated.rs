@@ -1,6 +1,9 @@
 use std::fmt;
 use std::marker;
 
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
+
 use crate::message::Message;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::acc::v2::AccessorV2;
@@ -57,6 +60,36 @@ where
     }
 }
 
+impl<M, V> RepeatedFieldGetMut<M, dyn ReflectRepeated> for RepeatedFieldGetMutImpl<M, Box<[V]>>
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+{
+    fn get_field<'a>(&self, m: &'a M) -> &'a dyn ReflectRepeated {
+        (self.get_field)(m) as &dyn ReflectRepeated
+    }
+
+    fn mut_field<'a>(&self, m: &'a mut M) -> &'a mut dyn ReflectRepeated {
+        (self.mut_field)(m) as &mut dyn ReflectRepeated
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<M, A> RepeatedFieldGetMut<M, dyn ReflectRepeated> for RepeatedFieldGetMutImpl<M, SmallVec<A>>
+where
+    M: Message + 'static,
+    A: smallvec::Array + Send + Sync + 'static,
+    A::Item: ProtobufValue,
+{
+    fn get_field<'a>(&self, m: &'a M) -> &'a dyn ReflectRepeated {
+        (self.get_field)(m) as &dyn ReflectRepeated
+    }
+
+    fn mut_field<'a>(&self, m: &'a mut M) -> &'a mut dyn ReflectRepeated {
+        (self.mut_field)(m) as &mut dyn ReflectRepeated
+    }
+}
+
 struct RepeatedFieldAccessorImpl<M, V>
 where
     M: Message,
@@ -111,3 +144,53 @@ where
         }),
     )
 }
+
+/// Make accessor for `Box<[T]>` field
+pub fn make_boxed_slice_simpler_accessor<M, V>(
+    name: &'static str,
+    get_vec: for<'a> fn(&'a M) -> &'a Box<[V]>,
+    mut_vec: for<'a> fn(&'a mut M) -> &'a mut Box<[V]>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Repeated(RepeatedFieldAccessorHolder {
+            accessor: Box::new(RepeatedFieldAccessorImpl::<M, V> {
+                fns: Box::new(RepeatedFieldGetMutImpl::<M, Box<[V]>> {
+                    get_field: get_vec,
+                    mut_field: mut_vec,
+                }),
+                _marker: marker::PhantomData::<V>,
+            }),
+        }),
+    )
+}
+
+/// Make accessor for `SmallVec` field
+#[cfg(feature = "smallvec")]
+pub fn make_smallvec_simpler_accessor<M, A>(
+    name: &'static str,
+    get_vec: for<'a> fn(&'a M) -> &'a SmallVec<A>,
+    mut_vec: for<'a> fn(&'a mut M) -> &'a mut SmallVec<A>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    A: smallvec::Array + Send + Sync + 'static,
+    A::Item: ProtobufValue,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Repeated(RepeatedFieldAccessorHolder {
+            accessor: Box::new(RepeatedFieldAccessorImpl::<M, A::Item> {
+                fns: Box::new(RepeatedFieldGetMutImpl::<M, SmallVec<A>> {
+                    get_field: get_vec,
+                    mut_field: mut_vec,
+                }),
+                _marker: marker::PhantomData::<A::Item>,
+            }),
+        }),
+    )
+}
@@ -0,0 +1,62 @@
+//! Normalization of messages using reflection.
+//!
+//! Map entries are not reordered here, because maps are stored in `HashMap`;
+//! [`Message::write_to_bytes_ordered`](crate::Message::write_to_bytes_ordered) sorts them by key.
+
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::MessageDyn;
+use crate::UnknownFields;
+
+/// Options for [`Message::canonicalize`](crate::Message::canonicalize).
+#[derive(Debug, Clone, Default)]
+pub struct CanonicalizeOptions {
+    /// Clear unknown fields of the message and all nested messages.
+    pub discard_unknown_fields: bool,
+    /// Prevent initializing `CanonicalizeOptions` enumerating all fields.
+    pub _future_options: (),
+}
+
+fn canonicalize_value(v: &mut ReflectValueBox, options: &CanonicalizeOptions) {
+    if let ReflectValueBox::Message(m) = v {
+        canonicalize(&mut **m, options);
+    }
+}
+
+pub(crate) fn canonicalize(m: &mut dyn MessageDyn, options: &CanonicalizeOptions) {
+    if options.discard_unknown_fields {
+        // Not `clear()`, which keeps allocated empty map,
+        // so the message would not be equal to a message without unknown fields.
+        *m.mut_unknown_fields_dyn() = UnknownFields::new();
+    }
+
+    for field in m.descriptor_dyn().fields() {
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(RuntimeTypeBox::Message(_)) if field.has_field(m) => {
+                canonicalize(field.mut_message(m), options);
+            }
+            RuntimeFieldType::Repeated(RuntimeTypeBox::Message(_)) => {
+                let mut repeated = field.mut_repeated(m);
+                for i in 0..repeated.len() {
+                    let mut v = repeated.get(i).to_box();
+                    canonicalize_value(&mut v, options);
+                    repeated.set(i, v);
+                }
+            }
+            RuntimeFieldType::Map(_, RuntimeTypeBox::Message(_)) => {
+                let entries: Vec<(ReflectValueBox, ReflectValueBox)> = (&field.get_map(m))
+                    .into_iter()
+                    .map(|(k, v)| (k.to_box(), v.to_box()))
+                    .collect();
+                let mut map = field.mut_map(m);
+                map.clear();
+                for (k, mut v) in entries {
+                    canonicalize_value(&mut v, options);
+                    map.insert(k, v);
+                }
+            }
+            _ => {}
+        }
+    }
+}
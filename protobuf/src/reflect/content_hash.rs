@@ -0,0 +1,166 @@
+//! Stable hash of message content computed using reflection.
+
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::MessageDyn;
+
+/// 64-bit FNV-1a.
+///
+/// Standard library hashers are not guaranteed to produce the same output
+/// across Rust versions, so simple hasher with fixed algorithm is used.
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Fnv64 {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, v: u8) {
+        self.write(&[v]);
+    }
+
+    fn write_u32(&mut self, v: u32) {
+        self.write(&v.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, v: u64) {
+        self.write(&v.to_le_bytes());
+    }
+
+    fn write_len_prefixed(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        self.write(bytes);
+    }
+}
+
+fn hash_f32(v: f32, h: &mut Fnv64) {
+    // `0.0 == -0.0`, and all `NaN` values are hashed the same
+    let v = if v == 0.0 {
+        0.0
+    } else if v.is_nan() {
+        f32::NAN
+    } else {
+        v
+    };
+    h.write_u32(v.to_bits());
+}
+
+fn hash_f64(v: f64, h: &mut Fnv64) {
+    let v = if v == 0.0 {
+        0.0
+    } else if v.is_nan() {
+        f64::NAN
+    } else {
+        v
+    };
+    h.write_u64(v.to_bits());
+}
+
+fn hash_value(v: &ReflectValueRef, h: &mut Fnv64) {
+    match v {
+        ReflectValueRef::U32(v) => {
+            h.write_u8(0);
+            h.write_u32(*v);
+        }
+        ReflectValueRef::U64(v) => {
+            h.write_u8(1);
+            h.write_u64(*v);
+        }
+        ReflectValueRef::I32(v) => {
+            h.write_u8(2);
+            h.write_u32(*v as u32);
+        }
+        ReflectValueRef::I64(v) => {
+            h.write_u8(3);
+            h.write_u64(*v as u64);
+        }
+        ReflectValueRef::F32(v) => {
+            h.write_u8(4);
+            hash_f32(*v, h);
+        }
+        ReflectValueRef::F64(v) => {
+            h.write_u8(5);
+            hash_f64(*v, h);
+        }
+        ReflectValueRef::Bool(v) => {
+            h.write_u8(6);
+            h.write_u8(*v as u8);
+        }
+        ReflectValueRef::String(v) => {
+            h.write_u8(7);
+            h.write_len_prefixed(v.as_bytes());
+        }
+        ReflectValueRef::Bytes(v) => {
+            h.write_u8(8);
+            h.write_len_prefixed(v);
+        }
+        ReflectValueRef::Enum(_, v) => {
+            h.write_u8(9);
+            h.write_u32(*v as u32);
+        }
+        ReflectValueRef::Message(m) => {
+            h.write_u8(10);
+            h.write_u64(content_hash(&**m));
+        }
+    }
+}
+
+/// Hash of message fields in field number order.
+///
+/// Map entries are hashed individually, and the sorted entry hashes are hashed,
+/// so the result does not depend on map iteration order.
+/// Unknown fields are ignored.
+pub(crate) fn content_hash(m: &dyn MessageDyn) -> u64 {
+    let descriptor = m.descriptor_dyn();
+    let mut fields: Vec<_> = descriptor.fields().collect();
+    fields.sort_by_key(|f| f.get_proto().get_number());
+
+    let mut h = Fnv64::new();
+    for field in fields {
+        match field.get_reflect(m) {
+            ReflectFieldRef::Optional(None) => {}
+            ReflectFieldRef::Optional(Some(v)) => {
+                h.write_u32(field.get_proto().get_number() as u32);
+                hash_value(&v, &mut h);
+            }
+            ReflectFieldRef::Repeated(r) => {
+                if r.is_empty() {
+                    continue;
+                }
+                h.write_u32(field.get_proto().get_number() as u32);
+                h.write_u64(r.len() as u64);
+                for i in 0..r.len() {
+                    hash_value(&r.get(i), &mut h);
+                }
+            }
+            ReflectFieldRef::Map(map) => {
+                if map.is_empty() {
+                    continue;
+                }
+                let mut entries: Vec<u64> = (&map)
+                    .into_iter()
+                    .map(|(k, v)| {
+                        let mut h = Fnv64::new();
+                        hash_value(&k, &mut h);
+                        hash_value(&v, &mut h);
+                        h.0
+                    })
+                    .collect();
+                entries.sort();
+                h.write_u32(field.get_proto().get_number() as u32);
+                h.write_u64(entries.len() as u64);
+                for e in entries {
+                    h.write_u64(e);
+                }
+            }
+        }
+    }
+    h.0
+}
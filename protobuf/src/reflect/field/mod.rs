@@ -146,6 +146,16 @@ impl FieldDescriptor {
         }
     }
 
+    /// Singular field which tracks presence: proto2 field, proto3 `optional` field,
+    /// message field or oneof member.
+    pub(crate) fn has_presence(&self) -> bool {
+        let proto = self.get_proto();
+        self.is_singular()
+            && (self.message_descriptor.file_descriptor_proto().get_syntax() != "proto3"
+                || proto.has_oneof_index()
+                || proto.get_field_type() == field_descriptor_proto::Type::TYPE_MESSAGE)
+    }
+
     /// If this field repeated or map?
     pub fn is_repeated_or_map(&self) -> bool {
         self.get_proto().get_label() == field_descriptor_proto::Label::LABEL_REPEATED
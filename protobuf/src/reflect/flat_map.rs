@@ -0,0 +1,152 @@
+//! Flatten message fields into a key-value map.
+
+use std::collections::BTreeMap;
+
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::text_format::quote_bytes_to;
+use crate::MessageDyn;
+
+/// How [`to_flat_map_with_options`] handles repeated and map fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlatMapRepeated {
+    /// Repeated and map fields are skipped.
+    #[default]
+    Skip,
+    /// Repeated field elements are emitted with index appended to the key
+    /// (`tags.0`, `tags.1`), map values are emitted with map key appended
+    /// to the key (`labels.env`).
+    Index,
+}
+
+/// Options for [`to_flat_map_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct FlatMapOptions {
+    /// Handling of repeated and map fields, skipped by default.
+    pub repeated: FlatMapRepeated,
+    /// How many levels of nested messages to flatten, unlimited if `None`.
+    ///
+    /// With `Some(0)` only fields of the message itself are emitted.
+    pub max_depth: Option<usize>,
+    /// Prevent initializing `FlatMapOptions` enumerating all fields.
+    pub _future_options: (),
+}
+
+struct Flattener<'a> {
+    sep: &'a str,
+    options: &'a FlatMapOptions,
+    map: BTreeMap<String, String>,
+}
+
+impl<'a> Flattener<'a> {
+    fn key(&self, prefix: &str, name: &str) -> String {
+        match prefix {
+            "" => name.to_owned(),
+            prefix => format!("{}{}{}", prefix, self.sep, name),
+        }
+    }
+
+    fn message(&mut self, m: &dyn MessageDyn, prefix: &str, depth: usize) {
+        for field in m.descriptor_dyn().fields() {
+            let key = self.key(prefix, field.get_name());
+            match field.get_reflect(m) {
+                ReflectFieldRef::Optional(None) => {}
+                ReflectFieldRef::Optional(Some(v)) => self.value(&v, key, depth),
+                ReflectFieldRef::Repeated(r) => {
+                    if self.options.repeated == FlatMapRepeated::Index {
+                        for i in 0..r.len() {
+                            let key = self.key(&key, &i.to_string());
+                            self.value(&r.get(i), key, depth);
+                        }
+                    }
+                }
+                ReflectFieldRef::Map(map) => {
+                    if self.options.repeated == FlatMapRepeated::Index {
+                        for (k, v) in &map {
+                            let key = self.key(&key, &scalar_to_string(&k));
+                            self.value(&v, key, depth);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, v: &ReflectValueRef, key: String, depth: usize) {
+        match v {
+            ReflectValueRef::Message(m) => match self.options.max_depth {
+                Some(max) if depth >= max => {}
+                _ => self.message(&**m, &key, depth + 1),
+            },
+            v => {
+                self.map.insert(key, scalar_to_string(v));
+            }
+        }
+    }
+}
+
+fn scalar_to_string(v: &ReflectValueRef) -> String {
+    match v {
+        ReflectValueRef::U32(v) => v.to_string(),
+        ReflectValueRef::U64(v) => v.to_string(),
+        ReflectValueRef::I32(v) => v.to_string(),
+        ReflectValueRef::I64(v) => v.to_string(),
+        ReflectValueRef::F32(v) => v.to_string(),
+        ReflectValueRef::F64(v) => v.to_string(),
+        ReflectValueRef::Bool(v) => v.to_string(),
+        ReflectValueRef::String(v) => (*v).to_owned(),
+        ReflectValueRef::Bytes(v) => {
+            let mut s = String::new();
+            quote_bytes_to(v, &mut s);
+            s
+        }
+        ReflectValueRef::Enum(d, v) => match d.get_value_by_number(*v) {
+            Some(e) => e.get_name().to_owned(),
+            None => v.to_string(),
+        },
+        ReflectValueRef::Message(..) => unreachable!("message is not a scalar"),
+    }
+}
+
+/// Flatten scalar fields of a message and its nested messages into a map.
+///
+/// Keys are field names of nested messages joined with `sep`, like `inner.value`.
+/// Values are rendered as strings: enums by value name, and bytes
+/// using text format escaping without quotes. Only fields which are set are emitted,
+/// in particular proto3 fields with default values are skipped.
+///
+/// Repeated and map fields are skipped, use [`to_flat_map_with_options`]
+/// to include them or to limit nesting depth.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::reflect::to_flat_map;
+/// use protobuf::well_known_types::Duration;
+///
+/// let mut duration = Duration::new();
+/// duration.seconds = 10;
+/// let map = to_flat_map(&duration, ".");
+/// assert_eq!(Some(&"10".to_owned()), map.get("seconds"));
+/// assert_eq!(None, map.get("nanos"));
+/// ```
+pub fn to_flat_map(message: &dyn MessageDyn, sep: &str) -> BTreeMap<String, String> {
+    to_flat_map_with_options(message, sep, &FlatMapOptions::default())
+}
+
+/// Flatten fields of a message into a map with options.
+///
+/// See [`to_flat_map`] for details.
+pub fn to_flat_map_with_options(
+    message: &dyn MessageDyn,
+    sep: &str,
+    options: &FlatMapOptions,
+) -> BTreeMap<String, String> {
+    let mut flattener = Flattener {
+        sep,
+        options,
+        map: BTreeMap::new(),
+    };
+    flattener.message(message, "", 0);
+    flattener.map
+}
@@ -1,7 +1,9 @@
 use std::fmt;
 
+use crate::coded_input_stream::CodedInputStream;
 use crate::descriptor::DescriptorProto;
 use crate::descriptor::FileDescriptorProto;
+use crate::error::ProtobufResult;
 use crate::message::Message;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::dynamic::DynamicMessage;
@@ -16,6 +18,8 @@ use crate::reflect::EnumDescriptor;
 use crate::reflect::FieldDescriptor;
 use crate::reflect::FileDescriptor;
 use crate::reflect::OneofDescriptor;
+use crate::rt;
+use crate::wire_format;
 
 pub(crate) mod dynamic;
 pub(crate) mod generated;
@@ -153,6 +157,38 @@ impl MessageDescriptor {
         }
     }
 
+    /// Parse message keeping only fields with given numbers.
+    ///
+    /// Other fields are skipped without decoding, and they are not stored
+    /// in unknown fields. Required fields are not checked
+    /// because they may be not in `keep_fields`.
+    ///
+    /// # Panics
+    ///
+    /// If this message is a map entry message.
+    pub fn parse_selective(
+        &self,
+        bytes: &[u8],
+        keep_fields: &[u32],
+    ) -> ProtobufResult<Box<dyn MessageDyn>> {
+        let mut is = CodedInputStream::from_bytes(bytes);
+        let mut kept = Vec::new();
+        while !is.eof()? {
+            let start = is.pos() as usize;
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match wire_type {
+                wire_format::WireTypeStartGroup => rt::skip_group(&mut is)?,
+                _ => is.skip_field(wire_type)?,
+            }
+            if keep_fields.contains(&field_number) {
+                kept.extend_from_slice(&bytes[start..is.pos() as usize]);
+            }
+        }
+        let mut message = self.new_instance();
+        message.merge_from_bytes_dyn(&kept)?;
+        Ok(message)
+    }
+
     /// Shared immutable empty message.
     ///
     /// Returns `None` for dynamic message.
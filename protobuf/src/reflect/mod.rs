@@ -6,6 +6,7 @@ mod enums;
 mod field;
 mod file;
 mod find_message_or_enum;
+mod flat_map;
 mod map;
 pub(crate) mod message;
 mod oneof;
@@ -17,12 +18,19 @@ pub(crate) mod value;
 pub mod runtime_types;
 pub mod types;
 
+pub(crate) mod canonicalize;
+pub(crate) mod content_hash;
+pub(crate) mod ordered_write;
 pub(crate) mod reflect_eq;
+pub(crate) mod required;
+pub(crate) mod struct_conversion;
+pub(crate) mod subset;
 
 pub mod rt;
 
 pub(crate) mod name;
 
+pub use self::canonicalize::CanonicalizeOptions;
 #[doc(hidden)]
 pub use self::enums::generated::GeneratedEnumDescriptorData;
 pub use self::enums::EnumDescriptor;
@@ -33,6 +41,10 @@ pub use self::field::RuntimeFieldType;
 #[doc(hidden)]
 pub use self::file::generated::GeneratedFileDescriptor;
 pub use self::file::FileDescriptor;
+pub use self::flat_map::to_flat_map;
+pub use self::flat_map::to_flat_map_with_options;
+pub use self::flat_map::FlatMapOptions;
+pub use self::flat_map::FlatMapRepeated;
 pub use self::map::ReflectMapMut;
 pub use self::map::ReflectMapRef;
 #[doc(hidden)]
@@ -40,11 +52,15 @@ pub use self::message::generated::GeneratedMessageDescriptorData;
 pub use self::message::message_ref::MessageRef;
 pub use self::message::MessageDescriptor;
 pub use self::oneof::OneofDescriptor;
+pub use self::ordered_write::FieldOrder;
 pub use self::reflect_eq::ReflectEq;
 pub use self::reflect_eq::ReflectEqMode;
 pub use self::repeated::ReflectRepeatedMut;
 pub use self::repeated::ReflectRepeatedRef;
 pub use self::runtime_type_box::RuntimeTypeBox;
+pub use self::struct_conversion::from_struct;
+pub use self::struct_conversion::to_struct;
+pub use self::struct_conversion::FromStructError;
 pub use self::value::value_box::ReflectValueBox;
 pub use self::value::value_ref::ReflectValueRef;
 pub use self::value::ProtobufValue;
@@ -26,4 +26,17 @@ impl OneofDescriptor {
             .fields()
             .filter(move |f| f.containing_oneof().as_ref() == Some(self))
     }
+
+    /// Oneof is synthetic, i. e. generated by `protoc` for
+    /// proto3 `optional` field.
+    ///
+    /// Synthetic oneof contains exactly one field, and it is used to track
+    /// presence of that field rather than being a union of fields.
+    pub fn is_synthetic(&self) -> bool {
+        let mut fields = self.fields();
+        match (fields.next(), fields.next()) {
+            (Some(field), None) => field.get_proto().get_proto3_optional(),
+            _ => false,
+        }
+    }
 }
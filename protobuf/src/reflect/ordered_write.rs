@@ -0,0 +1,244 @@
+//! Serialization with fields written in specified order, implemented using reflection.
+
+use std::cmp::Ordering;
+
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::descriptor::DescriptorProto;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::wire_format::WireType;
+use crate::CodedOutputStream;
+use crate::MessageDyn;
+use crate::ProtobufResult;
+
+/// Order of fields written by [`Message::write_to_bytes_ordered`](crate::Message::write_to_bytes_ordered).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOrder {
+    /// Fields are written in ascending field number order,
+    /// unknown fields are interleaved with known fields by field number.
+    AscendingFieldNumber,
+    /// Fields are written in order of declaration in `.proto` file,
+    /// unknown fields are written last in ascending field number order.
+    Declaration,
+}
+
+pub(crate) fn write_to_bytes_ordered(
+    m: &dyn MessageDyn,
+    order: FieldOrder,
+) -> ProtobufResult<Vec<u8>> {
+    let mut v = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut v);
+        write_message(m, order, &mut os)?;
+        os.flush()?;
+    }
+    Ok(v)
+}
+
+fn write_message(
+    m: &dyn MessageDyn,
+    order: FieldOrder,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    let descriptor = m.descriptor_dyn();
+    let mut fields: Vec<_> = descriptor
+        .fields()
+        // groups are not supported by rust-protobuf, so they are not stored in messages
+        .filter(|f| f.get_proto().get_field_type() != Type::TYPE_GROUP)
+        .collect();
+    if order == FieldOrder::AscendingFieldNumber {
+        fields.sort_by_key(|f| f.get_proto().get_number());
+    }
+
+    let mut unknown_fields: Vec<_> = m.get_unknown_fields_dyn().iter().collect();
+    unknown_fields.sort_by_key(|(n, _)| *n);
+    let mut unknown_fields = unknown_fields.into_iter().peekable();
+
+    for field in &fields {
+        let number = field.get_proto().get_number() as u32;
+        if order == FieldOrder::AscendingFieldNumber {
+            while let Some(&(n, values)) = unknown_fields.peek() {
+                if n > number {
+                    break;
+                }
+                for value in values {
+                    os.write_unknown(n, value)?;
+                }
+                unknown_fields.next();
+            }
+        }
+        write_field(m, field, order, os)?;
+    }
+    for (n, values) in unknown_fields {
+        for value in values {
+            os.write_unknown(n, value)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_field(
+    m: &dyn MessageDyn,
+    field: &FieldDescriptor,
+    order: FieldOrder,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    let proto = field.get_proto();
+    let number = proto.get_number() as u32;
+    let t = proto.get_field_type();
+    match field.get_reflect(m) {
+        ReflectFieldRef::Optional(None) => {}
+        ReflectFieldRef::Optional(Some(v)) => write_value(number, t, &v, order, os)?,
+        ReflectFieldRef::Repeated(r) => {
+            if r.is_empty() {
+                return Ok(());
+            }
+            if is_packed(field) {
+                let mut payload = Vec::new();
+                {
+                    let mut payload_os = CodedOutputStream::vec(&mut payload);
+                    for i in 0..r.len() {
+                        write_value_no_tag(t, &r.get(i), &mut payload_os)?;
+                    }
+                    payload_os.flush()?;
+                }
+                os.write_bytes(number, &payload)?;
+            } else {
+                for i in 0..r.len() {
+                    write_value(number, t, &r.get(i), order, os)?;
+                }
+            }
+        }
+        ReflectFieldRef::Map(map) => {
+            let entry = map_entry(field);
+            let key_type = entry.field[0].get_field_type();
+            let value_type = entry.field[1].get_field_type();
+            let mut entries: Vec<_> = (&map).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| compare_map_keys(a, b));
+            for (k, v) in entries {
+                let mut payload = Vec::new();
+                {
+                    let mut entry_os = CodedOutputStream::vec(&mut payload);
+                    write_value(1, key_type, &k, order, &mut entry_os)?;
+                    write_value(2, value_type, &v, order, &mut entry_os)?;
+                    entry_os.flush()?;
+                }
+                os.write_bytes(number, &payload)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_value(
+    number: u32,
+    t: Type,
+    v: &ReflectValueRef,
+    order: FieldOrder,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    match v {
+        ReflectValueRef::Message(m) => {
+            let mut bytes = Vec::new();
+            {
+                let mut message_os = CodedOutputStream::vec(&mut bytes);
+                write_message(&**m, order, &mut message_os)?;
+                message_os.flush()?;
+            }
+            os.write_bytes(number, &bytes)
+        }
+        v => {
+            os.write_tag(number, wire_type(t))?;
+            write_value_no_tag(t, v, os)
+        }
+    }
+}
+
+fn write_value_no_tag(
+    t: Type,
+    v: &ReflectValueRef,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    match (t, v) {
+        (Type::TYPE_INT32, ReflectValueRef::I32(v)) => os.write_int32_no_tag(*v),
+        (Type::TYPE_SINT32, ReflectValueRef::I32(v)) => os.write_sint32_no_tag(*v),
+        (Type::TYPE_SFIXED32, ReflectValueRef::I32(v)) => os.write_sfixed32_no_tag(*v),
+        (Type::TYPE_INT64, ReflectValueRef::I64(v)) => os.write_int64_no_tag(*v),
+        (Type::TYPE_SINT64, ReflectValueRef::I64(v)) => os.write_sint64_no_tag(*v),
+        (Type::TYPE_SFIXED64, ReflectValueRef::I64(v)) => os.write_sfixed64_no_tag(*v),
+        (Type::TYPE_UINT32, ReflectValueRef::U32(v)) => os.write_uint32_no_tag(*v),
+        (Type::TYPE_FIXED32, ReflectValueRef::U32(v)) => os.write_fixed32_no_tag(*v),
+        (Type::TYPE_UINT64, ReflectValueRef::U64(v)) => os.write_uint64_no_tag(*v),
+        (Type::TYPE_FIXED64, ReflectValueRef::U64(v)) => os.write_fixed64_no_tag(*v),
+        (Type::TYPE_FLOAT, ReflectValueRef::F32(v)) => os.write_float_no_tag(*v),
+        (Type::TYPE_DOUBLE, ReflectValueRef::F64(v)) => os.write_double_no_tag(*v),
+        (Type::TYPE_BOOL, ReflectValueRef::Bool(v)) => os.write_bool_no_tag(*v),
+        (Type::TYPE_STRING, ReflectValueRef::String(v)) => os.write_string_no_tag(v),
+        (Type::TYPE_BYTES, ReflectValueRef::Bytes(v)) => os.write_bytes_no_tag(v),
+        (Type::TYPE_ENUM, ReflectValueRef::Enum(_, v)) => os.write_enum_no_tag(*v),
+        (t, v) => panic!("value {:?} does not match field type {:?}", v, t),
+    }
+}
+
+fn wire_type(t: Type) -> WireType {
+    match t {
+        Type::TYPE_INT32
+        | Type::TYPE_SINT32
+        | Type::TYPE_INT64
+        | Type::TYPE_SINT64
+        | Type::TYPE_UINT32
+        | Type::TYPE_UINT64
+        | Type::TYPE_BOOL
+        | Type::TYPE_ENUM => WireType::WireTypeVarint,
+        Type::TYPE_FIXED32 | Type::TYPE_SFIXED32 | Type::TYPE_FLOAT => WireType::WireTypeFixed32,
+        Type::TYPE_FIXED64 | Type::TYPE_SFIXED64 | Type::TYPE_DOUBLE => WireType::WireTypeFixed64,
+        Type::TYPE_STRING | Type::TYPE_BYTES | Type::TYPE_MESSAGE => {
+            WireType::WireTypeLengthDelimited
+        }
+        Type::TYPE_GROUP => WireType::WireTypeStartGroup,
+    }
+}
+
+fn is_packed(field: &FieldDescriptor) -> bool {
+    let proto = field.get_proto();
+    match wire_type(proto.get_field_type()) {
+        WireType::WireTypeLengthDelimited | WireType::WireTypeStartGroup => return false,
+        _ => {}
+    }
+    let options = proto.options.get_or_default();
+    if options.has_packed() {
+        options.get_packed()
+    } else {
+        field
+            .message_descriptor
+            .file_descriptor_proto()
+            .get_syntax()
+            == "proto3"
+    }
+}
+
+/// Map entry message is a nested message of the message containing the map field.
+fn map_entry(field: &FieldDescriptor) -> &DescriptorProto {
+    let type_name = field.get_proto().get_type_name();
+    let entry_name = &type_name[type_name.rfind('.').map_or(0, |p| p + 1)..];
+    field
+        .message_descriptor
+        .get_proto()
+        .nested_type
+        .iter()
+        .find(|m| m.get_name() == entry_name)
+        .expect("map entry message not found")
+}
+
+fn compare_map_keys(a: &ReflectValueRef, b: &ReflectValueRef) -> Ordering {
+    match (a, b) {
+        (ReflectValueRef::U32(a), ReflectValueRef::U32(b)) => a.cmp(b),
+        (ReflectValueRef::U64(a), ReflectValueRef::U64(b)) => a.cmp(b),
+        (ReflectValueRef::I32(a), ReflectValueRef::I32(b)) => a.cmp(b),
+        (ReflectValueRef::I64(a), ReflectValueRef::I64(b)) => a.cmp(b),
+        (ReflectValueRef::Bool(a), ReflectValueRef::Bool(b)) => a.cmp(b),
+        (ReflectValueRef::String(a), ReflectValueRef::String(b)) => a.cmp(b),
+        (a, b) => panic!("incorrect map keys: {:?}, {:?}", a, b),
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt;
 use std::slice;
 
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
+
 use crate::reflect::dynamic::repeated::DynamicRepeated;
 use crate::reflect::reflect_eq::ReflectEq;
 use crate::reflect::reflect_eq::ReflectEqMode;
@@ -53,6 +56,82 @@ impl<V: ProtobufValue> ReflectRepeated for Vec<V> {
     }
 }
 
+impl<V: ProtobufValue> ReflectRepeated for Box<[V]> {
+    fn reflect_iter<'a>(&'a self) -> ReflectRepeatedIter<'a> {
+        ReflectRepeatedIter {
+            imp: Box::new(ReflectRepeatedIterImplSlice::<'a, V> { iter: self.iter() }),
+        }
+    }
+
+    fn len(&self) -> usize {
+        <[V]>::len(self)
+    }
+
+    fn get(&self, index: usize) -> ReflectValueRef<'_> {
+        V::as_ref(&self[index])
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) {
+        let value = value.downcast().expect("wrong type");
+        self[index] = value;
+    }
+
+    // Reallocates the slice
+    fn push(&mut self, value: ReflectValueBox) {
+        let value = value.downcast().expect("wrong type");
+        let mut vec = std::mem::take(self).into_vec();
+        vec.push(value);
+        *self = vec.into_boxed_slice();
+    }
+
+    fn clear(&mut self) {
+        *self = Box::default();
+    }
+
+    fn element_type(&self) -> RuntimeTypeBox {
+        V::runtime_type_box()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> ReflectRepeated for SmallVec<A>
+where
+    A: smallvec::Array + Send + Sync + 'static,
+    A::Item: ProtobufValue,
+{
+    fn reflect_iter<'a>(&'a self) -> ReflectRepeatedIter<'a> {
+        ReflectRepeatedIter {
+            imp: Box::new(ReflectRepeatedIterImplSlice::<'a, A::Item> { iter: self.iter() }),
+        }
+    }
+
+    fn len(&self) -> usize {
+        SmallVec::len(self)
+    }
+
+    fn get(&self, index: usize) -> ReflectValueRef {
+        A::Item::as_ref(&self[index])
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) {
+        let value = value.downcast().expect("wrong type");
+        self[index] = value;
+    }
+
+    fn push(&mut self, value: ReflectValueBox) {
+        let value = value.downcast().expect("wrong type");
+        self.push(value)
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    fn element_type(&self) -> RuntimeTypeBox {
+        A::Item::runtime_type_box()
+    }
+}
+
 // useless
 impl<V: ProtobufValue> ReflectRepeated for [V] {
     fn reflect_iter<'a>(&'a self) -> ReflectRepeatedIter<'a> {
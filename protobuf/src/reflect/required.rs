@@ -0,0 +1,58 @@
+//! Find `required` fields which are not set.
+
+use crate::descriptor::field_descriptor_proto;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::MessageDyn;
+
+fn map_key_to_string(key: &ReflectValueRef) -> String {
+    match key {
+        ReflectValueRef::U32(v) => v.to_string(),
+        ReflectValueRef::U64(v) => v.to_string(),
+        ReflectValueRef::I32(v) => v.to_string(),
+        ReflectValueRef::I64(v) => v.to_string(),
+        ReflectValueRef::Bool(v) => v.to_string(),
+        ReflectValueRef::String(v) => format!("{:?}", v),
+        _ => unreachable!("invalid map key type"),
+    }
+}
+
+fn collect(m: &dyn MessageDyn, prefix: &str, missing: &mut Vec<String>) {
+    for field in m.descriptor_dyn().fields() {
+        let path = format!("{}{}", prefix, field.get_name());
+        match field.get_reflect(m) {
+            ReflectFieldRef::Optional(None) => {
+                if field.get_proto().get_label() == field_descriptor_proto::Label::LABEL_REQUIRED {
+                    missing.push(path);
+                }
+            }
+            ReflectFieldRef::Optional(Some(ReflectValueRef::Message(n))) => {
+                collect(&*n, &format!("{}.", path), missing);
+            }
+            ReflectFieldRef::Optional(Some(_)) => {}
+            ReflectFieldRef::Repeated(r) => {
+                for i in 0..r.len() {
+                    if let ReflectValueRef::Message(n) = r.get(i) {
+                        collect(&*n, &format!("{}[{}].", path, i), missing);
+                    }
+                }
+            }
+            ReflectFieldRef::Map(map) => {
+                for (k, v) in &map {
+                    if let ReflectValueRef::Message(n) = v {
+                        let key = map_key_to_string(&k);
+                        collect(&*n, &format!("{}[{}].", path, key), missing);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Paths of `required` fields not set in the message and its nested messages,
+/// like `inner.id` or `items[1].id`.
+pub(crate) fn missing_required_fields(m: &dyn MessageDyn) -> Vec<String> {
+    let mut missing = Vec::new();
+    collect(m, "", &mut missing);
+    missing
+}
@@ -3,6 +3,9 @@
 #![doc(hidden)]
 
 pub use crate::reflect::acc::v2::map::make_map_simpler_accessor;
+pub use crate::reflect::acc::v2::repeated::make_boxed_slice_simpler_accessor;
+#[cfg(feature = "smallvec")]
+pub use crate::reflect::acc::v2::repeated::make_smallvec_simpler_accessor;
 pub use crate::reflect::acc::v2::repeated::make_vec_simpler_accessor;
 pub use crate::reflect::acc::v2::singular::make_message_field_accessor;
 pub use crate::reflect::acc::v2::singular::make_option_enum_accessor;
@@ -0,0 +1,433 @@
+//! Conversion between messages and `google.protobuf.Struct`.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::enums::ProtobufEnum;
+use crate::json::base64;
+use crate::json::base64::FromBase64Error;
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::well_known_types::value::Kind;
+use crate::well_known_types::ListValue;
+use crate::well_known_types::NullValue;
+use crate::well_known_types::Struct;
+use crate::well_known_types::Value;
+
+#[derive(Debug)]
+enum FromStructErrorInner {
+    UnknownFieldName(String),
+    OneofFieldsConflict(String, String),
+    IncorrectValue(String, String),
+    UnknownEnumValue(String, String),
+    FromBase64Error(FromBase64Error),
+}
+
+/// Error returned by [`from_struct`].
+#[derive(Debug)]
+pub struct FromStructError(FromStructErrorInner);
+
+impl fmt::Display for FromStructError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            FromStructErrorInner::UnknownFieldName(n) => write!(f, "unknown field name: {}", n),
+            FromStructErrorInner::OneofFieldsConflict(a, b) => write!(
+                f,
+                "fields `{}` and `{}` are members of the same oneof",
+                a, b
+            ),
+            FromStructErrorInner::IncorrectValue(n, t) => {
+                write!(f, "field `{}`: incorrect value for type {}", n, t)
+            }
+            FromStructErrorInner::UnknownEnumValue(n, v) => {
+                write!(f, "field `{}`: unknown enum value: {}", n, v)
+            }
+            FromStructErrorInner::FromBase64Error(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FromStructError {}
+
+impl From<FromBase64Error> for FromStructError {
+    fn from(e: FromBase64Error) -> Self {
+        FromStructError(FromStructErrorInner::FromBase64Error(e))
+    }
+}
+
+type FromStructResult<A> = Result<A, FromStructError>;
+
+fn value(kind: Kind) -> Value {
+    let mut value = Value::new();
+    value.kind = Some(kind);
+    value
+}
+
+fn message_to_value(m: &dyn MessageDyn) -> Value {
+    if let Some(v) = m.downcast_ref::<Value>() {
+        return v.clone();
+    }
+    if let Some(list) = m.downcast_ref::<ListValue>() {
+        return value(Kind::list_value(list.clone()));
+    }
+    if let Some(s) = m.downcast_ref::<Struct>() {
+        return value(Kind::struct_value(s.clone()));
+    }
+    value(Kind::struct_value(to_struct(m)))
+}
+
+fn value_to_value(v: &ReflectValueRef) -> Value {
+    let kind = match v {
+        ReflectValueRef::U32(v) => Kind::number_value(*v as f64),
+        ReflectValueRef::I32(v) => Kind::number_value(*v as f64),
+        ReflectValueRef::U64(v) => Kind::string_value(v.to_string()),
+        ReflectValueRef::I64(v) => Kind::string_value(v.to_string()),
+        ReflectValueRef::F32(v) => Kind::number_value(*v as f64),
+        ReflectValueRef::F64(v) => Kind::number_value(*v),
+        ReflectValueRef::Bool(v) => Kind::bool_value(*v),
+        ReflectValueRef::String(v) => Kind::string_value((*v).to_owned()),
+        ReflectValueRef::Bytes(v) => Kind::string_value(base64::encode(v)),
+        ReflectValueRef::Enum(d, _) if d.is::<NullValue>() => {
+            Kind::null_value(NullValue::NULL_VALUE.into())
+        }
+        ReflectValueRef::Enum(d, v) => match d.get_value_by_number(*v) {
+            Some(e) => Kind::string_value(e.get_name().to_owned()),
+            None => Kind::number_value(*v as f64),
+        },
+        ReflectValueRef::Message(m) => return message_to_value(&**m),
+    };
+    value(kind)
+}
+
+fn key_to_string(k: &ReflectValueRef) -> String {
+    match k {
+        ReflectValueRef::U32(v) => v.to_string(),
+        ReflectValueRef::U64(v) => v.to_string(),
+        ReflectValueRef::I32(v) => v.to_string(),
+        ReflectValueRef::I64(v) => v.to_string(),
+        ReflectValueRef::Bool(v) => v.to_string(),
+        ReflectValueRef::String(v) => (*v).to_owned(),
+        k => panic!("{:?} cannot be a map key", k),
+    }
+}
+
+/// Convert a message to `google.protobuf.Struct`.
+///
+/// Keys of the struct are field names. Only fields which are set are emitted,
+/// in particular proto3 fields with default values and empty repeated fields are skipped.
+///
+/// Field values are converted as follows:
+/// * 32-bit integers, `float` and `double` to numbers
+/// * 64-bit integers to decimal strings, because a number cannot represent all of them
+/// * `bool` and `string` to bools and strings
+/// * `bytes` to base64 strings
+/// * enums to value names, or to numbers if the value is unknown
+/// * messages to nested structs, except `Struct`, `Value` and `ListValue`,
+///   which are emitted as is (other well-known types are converted like regular messages)
+/// * repeated fields to lists
+/// * maps to nested structs with keys converted to strings
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::reflect::to_struct;
+/// use protobuf::well_known_types::Duration;
+///
+/// let mut duration = Duration::new();
+/// duration.seconds = 10;
+/// let s = to_struct(&duration);
+/// assert_eq!("10", s.fields["seconds"].get_string_value());
+/// assert!(!s.fields.contains_key("nanos"));
+/// ```
+pub fn to_struct(message: &dyn MessageDyn) -> Struct {
+    if let Some(s) = message.downcast_ref::<Struct>() {
+        return s.clone();
+    }
+
+    let mut s = Struct::new();
+    for field in message.descriptor_dyn().fields() {
+        let v = match field.get_reflect(message) {
+            ReflectFieldRef::Optional(None) => continue,
+            ReflectFieldRef::Optional(Some(v)) => value_to_value(&v),
+            ReflectFieldRef::Repeated(r) if r.is_empty() => continue,
+            ReflectFieldRef::Repeated(r) => {
+                let mut list = ListValue::new();
+                for i in 0..r.len() {
+                    list.values.push(value_to_value(&r.get(i)));
+                }
+                value(Kind::list_value(list))
+            }
+            ReflectFieldRef::Map(map) if map.is_empty() => continue,
+            ReflectFieldRef::Map(map) => {
+                let mut nested = Struct::new();
+                for (k, v) in &map {
+                    nested.fields.insert(key_to_string(&k), value_to_value(&v));
+                }
+                value(Kind::struct_value(nested))
+            }
+        };
+        s.fields.insert(field.get_name().to_owned(), v);
+    }
+    s
+}
+
+fn incorrect_value(field: &FieldDescriptor, t: &RuntimeTypeBox) -> FromStructError {
+    FromStructError(FromStructErrorInner::IncorrectValue(
+        field.get_name().to_owned(),
+        t.to_string(),
+    ))
+}
+
+fn is_null(v: &Value) -> bool {
+    matches!(v.kind, None | Some(Kind::null_value(..)))
+}
+
+fn to_i64(v: &Value) -> Option<i64> {
+    match &v.kind {
+        Some(Kind::number_value(n))
+            if n.fract() == 0.0 && *n >= i64::MIN as f64 && *n < i64::MAX as f64 =>
+        {
+            Some(*n as i64)
+        }
+        Some(Kind::string_value(s)) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_u64(v: &Value) -> Option<u64> {
+    match &v.kind {
+        Some(Kind::number_value(n)) if n.fract() == 0.0 && *n >= 0.0 && *n < u64::MAX as f64 => {
+            Some(*n as u64)
+        }
+        Some(Kind::string_value(s)) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_f64(v: &Value) -> Option<f64> {
+    match &v.kind {
+        Some(Kind::number_value(n)) => Some(*n),
+        Some(Kind::string_value(s)) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn enum_value(
+    v: &Value,
+    field: &FieldDescriptor,
+    t: &RuntimeTypeBox,
+) -> FromStructResult<ReflectValueBox> {
+    let descriptor = match t {
+        RuntimeTypeBox::Enum(e) => e,
+        _ => unreachable!(),
+    };
+    if descriptor.is::<NullValue>() && is_null(v) {
+        return Ok(ReflectValueBox::from(NullValue::NULL_VALUE.descriptor()));
+    }
+    let (found, text) = match &v.kind {
+        Some(Kind::string_value(s)) => (descriptor.get_value_by_name(s), s.clone()),
+        _ => match to_i64(v).and_then(|n| i32::try_from(n).ok()) {
+            Some(n) => (descriptor.get_value_by_number(n), n.to_string()),
+            None => return Err(incorrect_value(field, t)),
+        },
+    };
+    match found {
+        Some(e) => Ok(ReflectValueBox::from(e)),
+        None => Err(FromStructError(FromStructErrorInner::UnknownEnumValue(
+            field.get_name().to_owned(),
+            text,
+        ))),
+    }
+}
+
+/// Convert a value, `None` if the value is null and the type is not nullable.
+fn value_to_reflect(
+    v: &Value,
+    field: &FieldDescriptor,
+    t: &RuntimeTypeBox,
+) -> FromStructResult<Option<ReflectValueBox>> {
+    let nullable = match t {
+        RuntimeTypeBox::Enum(e) => e.is::<NullValue>(),
+        RuntimeTypeBox::Message(m) => *m == Value::descriptor_static(),
+        _ => false,
+    };
+    if !nullable && is_null(v) {
+        return Ok(None);
+    }
+
+    let incorrect = || incorrect_value(field, t);
+    let value = match t {
+        RuntimeTypeBox::I32 => to_i64(v)
+            .and_then(|n| i32::try_from(n).ok())
+            .map(ReflectValueBox::from),
+        RuntimeTypeBox::I64 => to_i64(v).map(ReflectValueBox::from),
+        RuntimeTypeBox::U32 => to_u64(v)
+            .and_then(|n| u32::try_from(n).ok())
+            .map(ReflectValueBox::from),
+        RuntimeTypeBox::U64 => to_u64(v).map(ReflectValueBox::from),
+        RuntimeTypeBox::F32 => to_f64(v).map(|n| ReflectValueBox::from(n as f32)),
+        RuntimeTypeBox::F64 => to_f64(v).map(ReflectValueBox::from),
+        RuntimeTypeBox::Bool => match &v.kind {
+            Some(Kind::bool_value(b)) => Some(ReflectValueBox::from(*b)),
+            _ => None,
+        },
+        RuntimeTypeBox::String => match &v.kind {
+            Some(Kind::string_value(s)) => Some(ReflectValueBox::from(s.clone())),
+            _ => None,
+        },
+        RuntimeTypeBox::VecU8 => match &v.kind {
+            Some(Kind::string_value(s)) => Some(ReflectValueBox::from(base64::decode(s)?)),
+            _ => None,
+        },
+        RuntimeTypeBox::Enum(..) => Some(enum_value(v, field, t)?),
+        RuntimeTypeBox::Message(m) if *m == Value::descriptor_static() => {
+            let m: Box<dyn MessageDyn> = Box::new(v.clone());
+            Some(ReflectValueBox::from(m))
+        }
+        RuntimeTypeBox::Message(m) if *m == ListValue::descriptor_static() => match &v.kind {
+            Some(Kind::list_value(list)) => {
+                let m: Box<dyn MessageDyn> = Box::new(list.clone());
+                Some(ReflectValueBox::from(m))
+            }
+            _ => None,
+        },
+        RuntimeTypeBox::Message(m) if *m == Struct::descriptor_static() => match &v.kind {
+            Some(Kind::struct_value(s)) => {
+                let m: Box<dyn MessageDyn> = Box::new(s.clone());
+                Some(ReflectValueBox::from(m))
+            }
+            _ => None,
+        },
+        RuntimeTypeBox::Message(m) => match &v.kind {
+            Some(Kind::struct_value(s)) => Some(ReflectValueBox::from(from_struct(s, m)?)),
+            _ => None,
+        },
+    };
+    value.map(Some).ok_or_else(incorrect)
+}
+
+fn parse_key(
+    key: &str,
+    field: &FieldDescriptor,
+    t: &RuntimeTypeBox,
+) -> FromStructResult<ReflectValueBox> {
+    let key = match t {
+        RuntimeTypeBox::I32 => key.parse::<i32>().ok().map(ReflectValueBox::I32),
+        RuntimeTypeBox::I64 => key.parse::<i64>().ok().map(ReflectValueBox::I64),
+        RuntimeTypeBox::U32 => key.parse::<u32>().ok().map(ReflectValueBox::U32),
+        RuntimeTypeBox::U64 => key.parse::<u64>().ok().map(ReflectValueBox::U64),
+        RuntimeTypeBox::Bool => key.parse::<bool>().ok().map(ReflectValueBox::Bool),
+        RuntimeTypeBox::String => Some(ReflectValueBox::String(key.to_owned())),
+        t => panic!("{} cannot be a map key", t),
+    };
+    key.ok_or_else(|| incorrect_value(field, t))
+}
+
+fn merge_field(
+    message: &mut dyn MessageDyn,
+    field: &FieldDescriptor,
+    v: &Value,
+) -> FromStructResult<()> {
+    match field.runtime_field_type() {
+        RuntimeFieldType::Singular(t) => {
+            if let Some(v) = value_to_reflect(v, field, &t)? {
+                field.set_singular_field(message, v);
+            }
+        }
+        RuntimeFieldType::Repeated(t) => {
+            let list = match &v.kind {
+                Some(Kind::list_value(list)) => list,
+                _ if is_null(v) => return Ok(()),
+                _ => return Err(incorrect_value(field, &t)),
+            };
+            let mut repeated = field.mut_repeated(message);
+            for item in &list.values {
+                let item = value_to_reflect(item, field, &t)?;
+                repeated.push(item.ok_or_else(|| incorrect_value(field, &t))?);
+            }
+        }
+        RuntimeFieldType::Map(kt, vt) => {
+            let s = match &v.kind {
+                Some(Kind::struct_value(s)) => s,
+                _ if is_null(v) => return Ok(()),
+                _ => return Err(incorrect_value(field, &vt)),
+            };
+            let mut map = field.mut_map(message);
+            for (k, item) in &s.fields {
+                let k = parse_key(k, field, &kt)?;
+                let item = value_to_reflect(item, field, &vt)?;
+                map.insert(k, item.ok_or_else(|| incorrect_value(field, &vt))?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Convert `google.protobuf.Struct` to a message of given type.
+///
+/// This is the inverse of [`to_struct`]. Fields are looked up by
+/// field name or JSON name, null values leave fields unset.
+/// Integers and floating point numbers are accepted both as numbers
+/// and as strings.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::Message;
+/// use protobuf::reflect::from_struct;
+/// use protobuf::reflect::to_struct;
+/// use protobuf::well_known_types::Duration;
+///
+/// let mut duration = Duration::new();
+/// duration.seconds = 10;
+/// let s = to_struct(&duration);
+/// let parsed = from_struct(&s, &Duration::descriptor_static()).unwrap();
+/// assert_eq!(Some(&duration), parsed.downcast_ref::<Duration>());
+/// ```
+pub fn from_struct(
+    s: &Struct,
+    descriptor: &MessageDescriptor,
+) -> FromStructResult<Box<dyn MessageDyn>> {
+    if *descriptor == Struct::descriptor_static() {
+        return Ok(Box::new(s.clone()));
+    }
+
+    let mut message = descriptor.new_instance();
+    // Iterate in key order to make errors deterministic
+    let mut names: Vec<&String> = s.fields.keys().collect();
+    names.sort();
+    // Oneof index and name of oneof member field seen in this struct
+    let mut oneofs_seen: Vec<(i32, String)> = Vec::new();
+    for name in names {
+        let v = &s.fields[name];
+        let field = match descriptor.get_field_by_name_or_json_name(name) {
+            Some(field) => field,
+            None => {
+                return Err(FromStructError(FromStructErrorInner::UnknownFieldName(
+                    name.clone(),
+                )))
+            }
+        };
+        if field.get_proto().has_oneof_index() && !is_null(v) {
+            let oneof_index = field.get_proto().get_oneof_index();
+            match oneofs_seen.iter().find(|(i, _)| *i == oneof_index) {
+                Some((_, seen)) => {
+                    return Err(FromStructError(FromStructErrorInner::OneofFieldsConflict(
+                        seen.clone(),
+                        field.get_name().to_owned(),
+                    )));
+                }
+                None => oneofs_seen.push((oneof_index, field.get_name().to_owned())),
+            }
+        }
+        merge_field(&mut *message, &field, v)?;
+    }
+    Ok(message)
+}
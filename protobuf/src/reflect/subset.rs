@@ -0,0 +1,42 @@
+//! Partial comparison of messages computed using reflection.
+
+use crate::reflect::ReflectEq;
+use crate::reflect::ReflectEqMode;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::MessageDyn;
+
+fn value_matches_subset(value: &ReflectValueRef, pattern: &ReflectValueRef) -> bool {
+    match (value, pattern) {
+        (ReflectValueRef::Message(v), ReflectValueRef::Message(p)) => matches_subset(&**v, &**p),
+        (v, p) => v.reflect_eq(p, &ReflectEqMode::default()),
+    }
+}
+
+/// Check fields set in `pattern` match fields of `m`.
+///
+/// See [`Message::matches_subset`](crate::Message::matches_subset) for details.
+pub(crate) fn matches_subset(m: &dyn MessageDyn, pattern: &dyn MessageDyn) -> bool {
+    if m.descriptor_dyn() != pattern.descriptor_dyn() {
+        return false;
+    }
+
+    pattern
+        .iter_set_fields()
+        .all(|(field, p)| match (field.get_reflect(m), p) {
+            (ReflectFieldRef::Optional(Some(v)), ReflectFieldRef::Optional(Some(p))) => {
+                value_matches_subset(&v, &p)
+            }
+            (ReflectFieldRef::Repeated(r), ReflectFieldRef::Repeated(p)) => {
+                r.len() == p.len()
+                    && (0..p.len()).all(|i| value_matches_subset(&r.get(i), &p.get(i)))
+            }
+            (ReflectFieldRef::Map(map), ReflectFieldRef::Map(p)) => {
+                (&p).into_iter().all(|(k, pv)| match map.get(k) {
+                    Some(v) => value_matches_subset(&v, &pv),
+                    None => false,
+                })
+            }
+            _ => false,
+        })
+}
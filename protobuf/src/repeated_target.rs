@@ -0,0 +1,35 @@
+//! Containers repeated field values are read into.
+
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
+
+/// Container which values of a repeated field are read into.
+///
+/// Implemented for `Vec`, and for `SmallVec` with `with-smallvec` feature.
+pub trait RepeatedTarget<T> {
+    /// Append an element to the back of the container.
+    fn push(&mut self, value: T);
+    /// Reserve capacity for at least `additional` more elements.
+    fn reserve(&mut self, additional: usize);
+}
+
+impl<T> RepeatedTarget<T> for Vec<T> {
+    fn push(&mut self, value: T) {
+        Vec::push(self, value)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> RepeatedTarget<A::Item> for SmallVec<A> {
+    fn push(&mut self, value: A::Item) {
+        SmallVec::push(self, value)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        SmallVec::reserve(self, additional)
+    }
+}
@@ -21,6 +21,7 @@ pub use crate::lazy_v2::LazyV2;
 use crate::message::*;
 use crate::reflect::types::*;
 use crate::reflect::ProtobufValue;
+pub use crate::repeated_target::RepeatedTarget;
 use crate::unknown::UnknownFields;
 use crate::wire_format;
 use crate::wire_format::WireType;
@@ -32,6 +33,19 @@ use crate::zigzag::*;
 use crate::MessageField;
 use crate::ProtobufEnumOrUnknown;
 
+/// State of a required field in generated typestate builder: the field is not set.
+pub enum RequiredFieldUnset {}
+
+/// State of a required field in generated typestate builder: the field is set.
+pub enum RequiredFieldSet {}
+
+/// Check if value is equal to default value of its type.
+///
+/// Used in `skip_serializing_if` serde attributes of generated code.
+pub fn is_default<T: Default + PartialEq>(v: &T) -> bool {
+    *v == T::default()
+}
+
 /// Given `u64` value compute varint encoded length.
 pub fn compute_raw_varint64_size(value: u64) -> u32 {
     if (value & (0xffffffffffffffffu64 << 7)) == 0 {
@@ -299,7 +313,7 @@ pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u32 {
 pub fn read_repeated_int32_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<i32>,
+    target: &mut impl RepeatedTarget<i32>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_int32_into(target),
@@ -315,7 +329,7 @@ pub fn read_repeated_int32_into(
 pub fn read_repeated_int64_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<i64>,
+    target: &mut impl RepeatedTarget<i64>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_int64_into(target),
@@ -331,7 +345,7 @@ pub fn read_repeated_int64_into(
 pub fn read_repeated_uint32_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<u32>,
+    target: &mut impl RepeatedTarget<u32>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_uint32_into(target),
@@ -347,7 +361,7 @@ pub fn read_repeated_uint32_into(
 pub fn read_repeated_uint64_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<u64>,
+    target: &mut impl RepeatedTarget<u64>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_uint64_into(target),
@@ -363,7 +377,7 @@ pub fn read_repeated_uint64_into(
 pub fn read_repeated_sint32_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<i32>,
+    target: &mut impl RepeatedTarget<i32>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sint32_into(target),
@@ -379,7 +393,7 @@ pub fn read_repeated_sint32_into(
 pub fn read_repeated_sint64_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<i64>,
+    target: &mut impl RepeatedTarget<i64>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sint64_into(target),
@@ -395,7 +409,7 @@ pub fn read_repeated_sint64_into(
 pub fn read_repeated_fixed32_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<u32>,
+    target: &mut impl RepeatedTarget<u32>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_fixed32_into(target),
@@ -411,7 +425,7 @@ pub fn read_repeated_fixed32_into(
 pub fn read_repeated_fixed64_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<u64>,
+    target: &mut impl RepeatedTarget<u64>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_fixed64_into(target),
@@ -427,7 +441,7 @@ pub fn read_repeated_fixed64_into(
 pub fn read_repeated_sfixed32_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<i32>,
+    target: &mut impl RepeatedTarget<i32>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sfixed32_into(target),
@@ -443,7 +457,7 @@ pub fn read_repeated_sfixed32_into(
 pub fn read_repeated_sfixed64_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<i64>,
+    target: &mut impl RepeatedTarget<i64>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_sfixed64_into(target),
@@ -459,7 +473,7 @@ pub fn read_repeated_sfixed64_into(
 pub fn read_repeated_double_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<f64>,
+    target: &mut impl RepeatedTarget<f64>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_double_into(target),
@@ -475,7 +489,7 @@ pub fn read_repeated_double_into(
 pub fn read_repeated_float_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<f32>,
+    target: &mut impl RepeatedTarget<f32>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_float_into(target),
@@ -491,7 +505,7 @@ pub fn read_repeated_float_into(
 pub fn read_repeated_bool_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<bool>,
+    target: &mut impl RepeatedTarget<bool>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_bool_into(target),
@@ -508,7 +522,7 @@ pub fn read_repeated_bool_into(
 pub fn read_repeated_enum_into<E: ProtobufEnum + ProtobufValue>(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<E>,
+    target: &mut impl RepeatedTarget<E>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => is.read_repeated_packed_enum_into(target),
@@ -541,7 +555,7 @@ where
 
 fn read_repeated_packed_enum_with_unknown_fields_into<E: ProtobufEnum>(
     is: &mut CodedInputStream,
-    target: &mut Vec<E>,
+    target: &mut impl RepeatedTarget<E>,
     field_number: u32,
     unknown_fields: &mut UnknownFields,
 ) -> ProtobufResult<()> {
@@ -556,7 +570,7 @@ fn read_repeated_packed_enum_with_unknown_fields_into<E: ProtobufEnum>(
 
 fn read_repeated_packed_enum_or_unknown_into<E: ProtobufEnum>(
     is: &mut CodedInputStream,
-    target: &mut Vec<ProtobufEnumOrUnknown<E>>,
+    target: &mut impl RepeatedTarget<ProtobufEnumOrUnknown<E>>,
 ) -> ProtobufResult<()> {
     let len = is.read_raw_varint64()?;
     let old_limit = is.push_limit(len)?;
@@ -576,7 +590,7 @@ fn read_repeated_packed_enum_or_unknown_into<E: ProtobufEnum>(
 pub fn read_repeated_enum_with_unknown_fields_into<E: ProtobufEnum>(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<E>,
+    target: &mut impl RepeatedTarget<E>,
     field_number: u32,
     unknown_fields: &mut UnknownFields,
 ) -> ProtobufResult<()> {
@@ -603,7 +617,7 @@ pub fn read_repeated_enum_with_unknown_fields_into<E: ProtobufEnum>(
 pub fn read_repeated_enum_or_unknown_into<E: ProtobufEnum>(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<ProtobufEnumOrUnknown<E>>,
+    target: &mut impl RepeatedTarget<ProtobufEnumOrUnknown<E>>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => read_repeated_packed_enum_or_unknown_into(is, target),
@@ -659,7 +673,7 @@ pub fn read_proto2_enum_with_unknown_fields_into<E: ProtobufEnum>(
 pub fn read_repeated_string_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<String>,
+    target: &mut impl RepeatedTarget<String>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
@@ -675,7 +689,7 @@ pub fn read_repeated_string_into(
 pub fn read_repeated_carllerche_string_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<Chars>,
+    target: &mut impl RepeatedTarget<Chars>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
@@ -734,7 +748,7 @@ pub fn read_singular_proto3_carllerche_string_into(
 pub fn read_repeated_bytes_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<Vec<u8>>,
+    target: &mut impl RepeatedTarget<Vec<u8>>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
@@ -750,7 +764,7 @@ pub fn read_repeated_bytes_into(
 pub fn read_repeated_carllerche_bytes_into(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<Bytes>,
+    target: &mut impl RepeatedTarget<Bytes>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
@@ -809,7 +823,7 @@ pub fn read_singular_proto3_carllerche_bytes_into(
 pub fn read_repeated_message_into_vec<M: Message + Default>(
     wire_type: WireType,
     is: &mut CodedInputStream,
-    target: &mut Vec<M>,
+    target: &mut impl RepeatedTarget<M>,
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
@@ -850,7 +864,7 @@ where
     }
 }
 
-fn skip_group(is: &mut CodedInputStream) -> ProtobufResult<()> {
+pub(crate) fn skip_group(is: &mut CodedInputStream) -> ProtobufResult<()> {
     loop {
         let (_, wire_type) = is.read_tag_unpack()?;
         if wire_type == wire_format::WireTypeEndGroup {
@@ -973,7 +987,7 @@ where
     let len = is.read_raw_varint32()?;
     let old_limit = is.push_limit(len as u64)?;
     while !is.eof()? {
-        let (field_number, wire_type) = is.read_tag_unpack()?;
+        let (field_number, wire_type) = is.read_map_entry_tag_unpack()?;
         match field_number {
             1 => {
                 if wire_type != K::WIRE_TYPE {
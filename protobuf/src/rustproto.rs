@@ -26,12 +26,20 @@ pub mod exts {
 
     pub const expose_oneof_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17001, phantom: ::std::marker::PhantomData };
 
+    pub const generate_oneof_case_enum_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17002, phantom: ::std::marker::PhantomData };
+
     pub const expose_fields_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
     pub const generate_accessors_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
 
     pub const generate_getter_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17005, phantom: ::std::marker::PhantomData };
 
+    pub const scalar_getter_style_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17006, phantom: ::std::marker::PhantomData };
+
+    pub const generate_option_getters_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17007, phantom: ::std::marker::PhantomData };
+
+    pub const track_caller_accessors_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17008, phantom: ::std::marker::PhantomData };
+
     pub const carllerche_bytes_for_bytes_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
     pub const carllerche_bytes_for_string_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
@@ -40,16 +48,52 @@ pub mod exts {
 
     pub const serde_derive_cfg_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    pub const serde_default_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17032, phantom: ::std::marker::PhantomData };
+
     pub const lite_runtime_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17035, phantom: ::std::marker::PhantomData };
 
+    pub const generate_presence_set_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    pub const generate_map_conversions_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17043, phantom: ::std::marker::PhantomData };
+
+    pub const nan_eq_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17046, phantom: ::std::marker::PhantomData };
+
+    pub const impl_eq_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17047, phantom: ::std::marker::PhantomData };
+
+    pub const impl_hash_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17053, phantom: ::std::marker::PhantomData };
+
+    pub const typestate_builder_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17054, phantom: ::std::marker::PhantomData };
+
+    pub const enum_lookup_threshold_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeUint32> = crate::ext::ExtFieldOptional { field_number: 17055, phantom: ::std::marker::PhantomData };
+
+    pub const generate_parts_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17057, phantom: ::std::marker::PhantomData };
+
+    pub const nested_type_strategy_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17058, phantom: ::std::marker::PhantomData };
+
+    pub const generate_retain_fields_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17049, phantom: ::std::marker::PhantomData };
+
+    pub const per_message_descriptor_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17051, phantom: ::std::marker::PhantomData };
+
+    pub const repeated_type_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17050, phantom: ::std::marker::PhantomData };
+
+    pub const repeated_boxed_slice_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17052, phantom: ::std::marker::PhantomData };
+
     pub const expose_oneof: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17001, phantom: ::std::marker::PhantomData };
 
+    pub const generate_oneof_case_enum: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17002, phantom: ::std::marker::PhantomData };
+
     pub const expose_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
     pub const generate_accessors: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
 
     pub const generate_getter: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17005, phantom: ::std::marker::PhantomData };
 
+    pub const scalar_getter_style: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17006, phantom: ::std::marker::PhantomData };
+
+    pub const generate_option_getters: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17007, phantom: ::std::marker::PhantomData };
+
+    pub const track_caller_accessors: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17008, phantom: ::std::marker::PhantomData };
+
     pub const carllerche_bytes_for_bytes: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
     pub const carllerche_bytes_for_string: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
@@ -58,157 +102,483 @@ pub mod exts {
 
     pub const serde_derive_cfg: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    pub const serde_default: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17032, phantom: ::std::marker::PhantomData };
+
+    pub const generate_presence_set: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    pub const bytes_wrapper: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    pub const repeated_index_impl: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17056, phantom: ::std::marker::PhantomData };
+
+    pub const generate_map_conversions: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17043, phantom: ::std::marker::PhantomData };
+
+    pub const primary_key_field: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17045, phantom: ::std::marker::PhantomData };
+
+    pub const nan_eq: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17046, phantom: ::std::marker::PhantomData };
+
+    pub const impl_eq: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17047, phantom: ::std::marker::PhantomData };
+
+    pub const impl_hash: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17053, phantom: ::std::marker::PhantomData };
+
+    pub const typestate_builder: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17054, phantom: ::std::marker::PhantomData };
+
+    pub const enum_lookup_threshold: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeUint32> = crate::ext::ExtFieldOptional { field_number: 17055, phantom: ::std::marker::PhantomData };
+
+    pub const generate_parts: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17057, phantom: ::std::marker::PhantomData };
+
+    pub const display_format: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17048, phantom: ::std::marker::PhantomData };
+
+    pub const generate_retain_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17049, phantom: ::std::marker::PhantomData };
+
+    pub const per_message_descriptor: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17051, phantom: ::std::marker::PhantomData };
+
+    pub const repeated_type: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17050, phantom: ::std::marker::PhantomData };
+
+    pub const repeated_boxed_slice: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17052, phantom: ::std::marker::PhantomData };
+
     pub const expose_fields_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
     pub const generate_accessors_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
 
     pub const generate_getter_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17005, phantom: ::std::marker::PhantomData };
 
+    pub const scalar_getter_style_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17006, phantom: ::std::marker::PhantomData };
+
+    pub const generate_option_getters_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17007, phantom: ::std::marker::PhantomData };
+
+    pub const track_caller_accessors_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17008, phantom: ::std::marker::PhantomData };
+
     pub const carllerche_bytes_for_bytes_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
     pub const carllerche_bytes_for_string_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
+
+    pub const generate_map_conversions_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17043, phantom: ::std::marker::PhantomData };
+
+    pub const map_key_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17044, phantom: ::std::marker::PhantomData };
+
+    pub const repeated_type_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17050, phantom: ::std::marker::PhantomData };
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x0frustproto.proto\x12\trustproto\x1a\x20google/protobuf/descriptor.p\
     roto:H\n\x10expose_oneof_all\x18\xe9\x84\x01\x20\x01(\x08\x12\x1c.google\
-    .protobuf.FileOptionsR\x0eexposeOneofAll:J\n\x11expose_fields_all\x18\
-    \xeb\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0fexpose\
-    FieldsAll:T\n\x16generate_accessors_all\x18\xec\x84\x01\x20\x01(\x08\x12\
-    \x1c.google.protobuf.FileOptionsR\x14generateAccessorsAll:N\n\x13generat\
-    e_getter_all\x18\xed\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOp\
-    tionsR\x11generateGetterAll:b\n\x1ecarllerche_bytes_for_bytes_all\x18\
-    \xf3\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1acarlle\
-    rcheBytesForBytesAll:d\n\x1fcarllerche_bytes_for_string_all\x18\xf4\x84\
-    \x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1bcarllercheByte\
-    sForStringAll:H\n\x10serde_derive_all\x18\x86\x85\x01\x20\x01(\x08\x12\
-    \x1c.google.protobuf.FileOptionsR\x0eserdeDeriveAll:O\n\x14serde_derive_\
-    cfg_all\x18\x87\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptionsR\
-    \x11serdeDeriveCfgAll:H\n\x10lite_runtime_all\x18\x8b\x85\x01\x20\x01(\
-    \x08\x12\x1c.google.protobuf.FileOptionsR\x0eliteRuntimeAll:D\n\x0cexpos\
-    e_oneof\x18\xe9\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpti\
-    onsR\x0bexposeOneof:F\n\rexpose_fields\x18\xeb\x84\x01\x20\x01(\x08\x12\
-    \x1f.google.protobuf.MessageOptionsR\x0cexposeFields:P\n\x12generate_acc\
-    essors\x18\xec\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptio\
-    nsR\x11generateAccessors:J\n\x0fgenerate_getter\x18\xed\x84\x01\x20\x01(\
-    \x08\x12\x1f.google.protobuf.MessageOptionsR\x0egenerateGetter:^\n\x1aca\
-    rllerche_bytes_for_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.pro\
-    tobuf.MessageOptionsR\x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes\
-    _for_string\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.Message\
-    OptionsR\x18carllercheBytesForString:D\n\x0cserde_derive\x18\x86\x85\x01\
-    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0bserdeDerive:K\n\
-    \x10serde_derive_cfg\x18\x87\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.\
-    MessageOptionsR\x0eserdeDeriveCfg:O\n\x13expose_fields_field\x18\xeb\x84\
+    .protobuf.FileOptionsR\x0eexposeOneofAll:^\n\x1cgenerate_oneof_case_enum\
+    _all\x18\xea\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\
+    \x18generateOneofCaseEnumAll:J\n\x11expose_fields_all\x18\xeb\x84\x01\
+    \x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0fexposeFieldsAll:T\
+    \n\x16generate_accessors_all\x18\xec\x84\x01\x20\x01(\x08\x12\x1c.google\
+    .protobuf.FileOptionsR\x14generateAccessorsAll:N\n\x13generate_getter_al\
+    l\x18\xed\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x11g\
+    enerateGetterAll:U\n\x17scalar_getter_style_all\x18\xee\x84\x01\x20\x01(\
+    \t\x12\x1c.google.protobuf.FileOptionsR\x14scalarGetterStyleAll:]\n\x1bg\
+    enerate_option_getters_all\x18\xef\x84\x01\x20\x01(\x08\x12\x1c.google.p\
+    rotobuf.FileOptionsR\x18generateOptionGettersAll:[\n\x1atrack_caller_acc\
+    essors_all\x18\xf0\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOpti\
+    onsR\x17trackCallerAccessorsAll:b\n\x1ecarllerche_bytes_for_bytes_all\
+    \x18\xf3\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1aca\
+    rllercheBytesForBytesAll:d\n\x1fcarllerche_bytes_for_string_all\x18\xf4\
+    \x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1bcarllerche\
+    BytesForStringAll:H\n\x10serde_derive_all\x18\x86\x85\x01\x20\x01(\x08\
+    \x12\x1c.google.protobuf.FileOptionsR\x0eserdeDeriveAll:O\n\x14serde_der\
+    ive_cfg_all\x18\x87\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptio\
+    nsR\x11serdeDeriveCfgAll:J\n\x11serde_default_all\x18\x88\x85\x01\x20\
+    \x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0fserdeDefaultAll:H\n\
+    \x10lite_runtime_all\x18\x8b\x85\x01\x20\x01(\x08\x12\x1c.google.protobu\
+    f.FileOptionsR\x0eliteRuntimeAll:Y\n\x19generate_presence_set_all\x18\
+    \x90\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x16genera\
+    tePresenceSetAll:_\n\x1cgenerate_map_conversions_all\x18\x93\x85\x01\x20\
+    \x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x19generateMapConversions\
+    All:<\n\nnan_eq_all\x18\x96\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf\
+    .FileOptionsR\x08nanEqAll:>\n\x0bimpl_eq_all\x18\x97\x85\x01\x20\x01(\
+    \x08\x12\x1c.google.protobuf.FileOptionsR\timplEqAll:B\n\rimpl_hash_all\
+    \x18\x9d\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0bim\
+    plHashAll:R\n\x15typestate_builder_all\x18\x9e\x85\x01\x20\x01(\x08\x12\
+    \x1c.google.protobuf.FileOptionsR\x13typestateBuilderAll:Y\n\x19enum_loo\
+    kup_threshold_all\x18\x9f\x85\x01\x20\x01(\r\x12\x1c.google.protobuf.Fil\
+    eOptionsR\x16enumLookupThresholdAll:L\n\x12generate_parts_all\x18\xa1\
+    \x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x10generatePa\
+    rtsAll:W\n\x18nested_type_strategy_all\x18\xa2\x85\x01\x20\x01(\t\x12\
+    \x1c.google.protobuf.FileOptionsR\x15nestedTypeStrategyAll:[\n\x1agenera\
+    te_retain_fields_all\x18\x99\x85\x01\x20\x01(\x08\x12\x1c.google.protobu\
+    f.FileOptionsR\x17generateRetainFieldsAll:[\n\x1aper_message_descriptor_\
+    all\x18\x9b\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\
+    \x17perMessageDescriptorAll:J\n\x11repeated_type_all\x18\x9a\x85\x01\x20\
+    \x01(\t\x12\x1c.google.protobuf.FileOptionsR\x0frepeatedTypeAll:W\n\x18r\
+    epeated_boxed_slice_all\x18\x9c\x85\x01\x20\x01(\x08\x12\x1c.google.prot\
+    obuf.FileOptionsR\x15repeatedBoxedSliceAll:D\n\x0cexpose_oneof\x18\xe9\
+    \x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0bexposeO\
+    neof:Z\n\x18generate_oneof_case_enum\x18\xea\x84\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\x15generateOneofCaseEnum:F\n\rexpos\
+    e_fields\x18\xeb\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpt\
+    ionsR\x0cexposeFields:P\n\x12generate_accessors\x18\xec\x84\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\x11generateAccessors:J\n\
+    \x0fgenerate_getter\x18\xed\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf\
+    .MessageOptionsR\x0egenerateGetter:Q\n\x13scalar_getter_style\x18\xee\
+    \x84\x01\x20\x01(\t\x12\x1f.google.protobuf.MessageOptionsR\x11scalarGet\
+    terStyle:Y\n\x17generate_option_getters\x18\xef\x84\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\x15generateOptionGetters:W\n\x16tra\
+    ck_caller_accessors\x18\xf0\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf\
+    .MessageOptionsR\x14trackCallerAccessors:^\n\x1acarllerche_bytes_for_byt\
+    es\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\
+    \x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes_for_string\x18\xf4\
+    \x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x18carller\
+    cheBytesForString:D\n\x0cserde_derive\x18\x86\x85\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\x0bserdeDerive:K\n\x10serde_derive_\
+    cfg\x18\x87\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.MessageOptionsR\
+    \x0eserdeDeriveCfg:F\n\rserde_default\x18\x88\x85\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\x0cserdeDefault:U\n\x15generate_pre\
+    sence_set\x18\x90\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOp\
+    tionsR\x13generatePresenceSet:F\n\rbytes_wrapper\x18\x91\x85\x01\x20\x01\
+    (\x08\x12\x1f.google.protobuf.MessageOptionsR\x0cbytesWrapper:Q\n\x13rep\
+    eated_index_impl\x18\xa0\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.Me\
+    ssageOptionsR\x11repeatedIndexImpl:[\n\x18generate_map_conversions\x18\
+    \x93\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x16gen\
+    erateMapConversions:M\n\x11primary_key_field\x18\x95\x85\x01\x20\x01(\t\
+    \x12\x1f.google.protobuf.MessageOptionsR\x0fprimaryKeyField:8\n\x06nan_e\
+    q\x18\x96\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\
+    \x05nanEq::\n\x07impl_eq\x18\x97\x85\x01\x20\x01(\x08\x12\x1f.google.pro\
+    tobuf.MessageOptionsR\x06implEq:>\n\timpl_hash\x18\x9d\x85\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\x08implHash:N\n\x11typestat\
+    e_builder\x18\x9e\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOp\
+    tionsR\x10typestateBuilder:U\n\x15enum_lookup_threshold\x18\x9f\x85\x01\
+    \x20\x01(\r\x12\x1f.google.protobuf.MessageOptionsR\x13enumLookupThresho\
+    ld:H\n\x0egenerate_parts\x18\xa1\x85\x01\x20\x01(\x08\x12\x1f.google.pro\
+    tobuf.MessageOptionsR\rgenerateParts:H\n\x0edisplay_format\x18\x98\x85\
+    \x01\x20\x01(\t\x12\x1f.google.protobuf.MessageOptionsR\rdisplayFormat:W\
+    \n\x16generate_retain_fields\x18\x99\x85\x01\x20\x01(\x08\x12\x1f.google\
+    .protobuf.MessageOptionsR\x14generateRetainFields:W\n\x16per_message_des\
+    criptor\x18\x9b\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpti\
+    onsR\x14perMessageDescriptor:F\n\rrepeated_type\x18\x9a\x85\x01\x20\x01(\
+    \t\x12\x1f.google.protobuf.MessageOptionsR\x0crepeatedType:S\n\x14repeat\
+    ed_boxed_slice\x18\x9c\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.Mess\
+    ageOptionsR\x12repeatedBoxedSlice:O\n\x13expose_fields_field\x18\xeb\x84\
     \x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x11exposeFieldsF\
     ield:Y\n\x18generate_accessors_field\x18\xec\x84\x01\x20\x01(\x08\x12\
     \x1d.google.protobuf.FieldOptionsR\x16generateAccessorsField:S\n\x15gene\
     rate_getter_field\x18\xed\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.F\
-    ieldOptionsR\x13generateGetterField:g\n\x20carllerche_bytes_for_bytes_fi\
-    eld\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\
-    \x1ccarllercheBytesForBytesField:i\n!carllerche_bytes_for_string_field\
-    \x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1dc\
-    arllercheBytesForStringFieldJ\x8e\x18\n\x06\x12\x04\0\0A\x01\n\x08\n\x01\
-    \x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\0*\n\xe5\x01\n\x01\x02\
-    \x12\x03\n\0\x122^\x20see\x20https://github.com/gogo/protobuf/blob/maste\
-    r/gogoproto/gogo.proto\n\x20for\x20the\x20original\x20idea\n2{\x20Genera\
-    ted\x20files\x20can\x20be\x20customized\x20using\x20this\x20proto\n\x20o\
-    r\x20using\x20`Customize`\x20struct\x20when\x20codegen\x20is\x20invoked\
-    \x20programmatically.\n\n\t\n\x01\x07\x12\x04\x0c\0!\x01\n7\n\x02\x07\0\
-    \x12\x03\x0e\x04+\x1a,\x20When\x20true,\x20oneof\x20field\x20is\x20gener\
-    ated\x20public\n\n\n\n\x03\x07\0\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\0\
-    \x04\x12\x03\x0e\x04\x0c\n\n\n\x03\x07\0\x05\x12\x03\x0e\r\x11\n\n\n\x03\
-    \x07\0\x01\x12\x03\x0e\x12\"\n\n\n\x03\x07\0\x03\x12\x03\x0e%*\nI\n\x02\
-    \x07\x01\x12\x03\x10\x04,\x1a>\x20When\x20true\x20all\x20fields\x20are\
-    \x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\x07\x01\
-    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x01\x04\x12\x03\x10\x04\x0c\n\n\n\
-    \x03\x07\x01\x05\x12\x03\x10\r\x11\n\n\n\x03\x07\x01\x01\x12\x03\x10\x12\
-    #\n\n\n\x03\x07\x01\x03\x12\x03\x10&+\nP\n\x02\x07\x02\x12\x03\x12\x041\
-    \x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20acces\
-    sors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x02\x02\x12\x03\x0c\x07\
-    \"\n\n\n\x03\x07\x02\x04\x12\x03\x12\x04\x0c\n\n\n\x03\x07\x02\x05\x12\
-    \x03\x12\r\x11\n\n\n\x03\x07\x02\x01\x12\x03\x12\x12(\n\n\n\x03\x07\x02\
-    \x03\x12\x03\x12+0\nL\n\x02\x07\x03\x12\x03\x14\x04.\x1aA\x20When\x20fal\
-    se,\x20`get_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\
-    \x20\"proto2\"`\n\n\n\n\x03\x07\x03\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
-    \x03\x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\x03\x05\x12\x03\x14\r\x11\n\n\
-    \n\x03\x07\x03\x01\x12\x03\x14\x12%\n\n\n\x03\x07\x03\x03\x12\x03\x14(-\
-    \n2\n\x02\x07\x04\x12\x03\x16\x049\x1a'\x20Use\x20`bytes::Bytes`\x20for\
-    \x20`bytes`\x20fields\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\
-    \x03\x07\x04\x04\x12\x03\x16\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\x16\r\
-    \x11\n\n\n\x03\x07\x04\x01\x12\x03\x16\x120\n\n\n\x03\x07\x04\x03\x12\
-    \x03\x1638\n3\n\x02\x07\x05\x12\x03\x18\x04:\x1a(\x20Use\x20`bytes::Byte\
-    s`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x05\x02\x12\x03\x0c\x07\
-    \"\n\n\n\x03\x07\x05\x04\x12\x03\x18\x04\x0c\n\n\n\x03\x07\x05\x05\x12\
-    \x03\x18\r\x11\n\n\n\x03\x07\x05\x01\x12\x03\x18\x121\n\n\n\x03\x07\x05\
-    \x03\x12\x03\x1849\nJ\n\x02\x07\x06\x12\x03\x1b\x04+\x1a?\x20Use\x20`ser\
-    de_derive`\x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\
-    \n\n\x03\x07\x06\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x06\x04\x12\x03\x1b\
-    \x04\x0c\n\n\n\x03\x07\x06\x05\x12\x03\x1b\r\x11\n\n\n\x03\x07\x06\x01\
-    \x12\x03\x1b\x12\"\n\n\n\x03\x07\x06\x03\x12\x03\x1b%*\n3\n\x02\x07\x07\
-    \x12\x03\x1d\x041\x1a(\x20Guard\x20serde\x20annotations\x20with\x20cfg\
-    \x20attr.\n\n\n\n\x03\x07\x07\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x07\
-    \x04\x12\x03\x1d\x04\x0c\n\n\n\x03\x07\x07\x05\x12\x03\x1d\r\x13\n\n\n\
-    \x03\x07\x07\x01\x12\x03\x1d\x14(\n\n\n\x03\x07\x07\x03\x12\x03\x1d+0\nN\
-    \n\x02\x07\x08\x12\x03\x20\x04+\x1aC\x20When\x20true,\x20will\x20only\
-    \x20generate\x20codes\x20that\x20works\x20with\x20lite\x20runtime.\n\n\n\
-    \n\x03\x07\x08\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x08\x04\x12\x03\x20\
-    \x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\x20\r\x11\n\n\n\x03\x07\x08\x01\
-    \x12\x03\x20\x12\"\n\n\n\x03\x07\x08\x03\x12\x03\x20%*\n\t\n\x01\x07\x12\
-    \x04#\04\x01\n7\n\x02\x07\t\x12\x03%\x04'\x1a,\x20When\x20true,\x20oneof\
-    \x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\t\x02\x12\x03#\
-    \x07%\n\n\n\x03\x07\t\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03%\
-    \r\x11\n\n\n\x03\x07\t\x01\x12\x03%\x12\x1e\n\n\n\x03\x07\t\x03\x12\x03%\
-    !&\nI\n\x02\x07\n\x12\x03'\x04(\x1a>\x20When\x20true\x20all\x20fields\
-    \x20are\x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\
-    \x07\n\x02\x12\x03#\x07%\n\n\n\x03\x07\n\x04\x12\x03'\x04\x0c\n\n\n\x03\
-    \x07\n\x05\x12\x03'\r\x11\n\n\n\x03\x07\n\x01\x12\x03'\x12\x1f\n\n\n\x03\
-    \x07\n\x03\x12\x03'\"'\nP\n\x02\x07\x0b\x12\x03)\x04-\x1aE\x20When\x20fa\
-    lse,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\
-    \x20generated\n\n\n\n\x03\x07\x0b\x02\x12\x03#\x07%\n\n\n\x03\x07\x0b\
-    \x04\x12\x03)\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03)\r\x11\n\n\n\x03\x07\
-    \x0b\x01\x12\x03)\x12$\n\n\n\x03\x07\x0b\x03\x12\x03)',\nL\n\x02\x07\x0c\
-    \x12\x03+\x04*\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generate\
-    d\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x0c\x02\
-    \x12\x03#\x07%\n\n\n\x03\x07\x0c\x04\x12\x03+\x04\x0c\n\n\n\x03\x07\x0c\
-    \x05\x12\x03+\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03+\x12!\n\n\n\x03\x07\
-    \x0c\x03\x12\x03+$)\n2\n\x02\x07\r\x12\x03-\x045\x1a'\x20Use\x20`bytes::\
-    Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\r\x02\x12\x03#\x07%\n\
-    \n\n\x03\x07\r\x04\x12\x03-\x04\x0c\n\n\n\x03\x07\r\x05\x12\x03-\r\x11\n\
-    \n\n\x03\x07\r\x01\x12\x03-\x12,\n\n\n\x03\x07\r\x03\x12\x03-/4\n3\n\x02\
-    \x07\x0e\x12\x03/\x046\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\
-    \x20fields\n\n\n\n\x03\x07\x0e\x02\x12\x03#\x07%\n\n\n\x03\x07\x0e\x04\
-    \x12\x03/\x04\x0c\n\n\n\x03\x07\x0e\x05\x12\x03/\r\x11\n\n\n\x03\x07\x0e\
-    \x01\x12\x03/\x12-\n\n\n\x03\x07\x0e\x03\x12\x03/05\nJ\n\x02\x07\x0f\x12\
-    \x031\x04'\x1a?\x20Use\x20`serde_derive`\x20to\x20implement\x20`Serializ\
-    e`\x20and\x20`Deserialize`\n\n\n\n\x03\x07\x0f\x02\x12\x03#\x07%\n\n\n\
-    \x03\x07\x0f\x04\x12\x031\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\x031\r\x11\n\
-    \n\n\x03\x07\x0f\x01\x12\x031\x12\x1e\n\n\n\x03\x07\x0f\x03\x12\x031!&\n\
-    3\n\x02\x07\x10\x12\x033\x04-\x1a(\x20Guard\x20serde\x20annotations\x20w\
-    ith\x20cfg\x20attr.\n\n\n\n\x03\x07\x10\x02\x12\x03#\x07%\n\n\n\x03\x07\
-    \x10\x04\x12\x033\x04\x0c\n\n\n\x03\x07\x10\x05\x12\x033\r\x13\n\n\n\x03\
-    \x07\x10\x01\x12\x033\x14$\n\n\n\x03\x07\x10\x03\x12\x033',\n\t\n\x01\
-    \x07\x12\x046\0A\x01\nI\n\x02\x07\x11\x12\x038\x04.\x1a>\x20When\x20true\
+    ieldOptionsR\x13generateGetterField:Z\n\x19scalar_getter_style_field\x18\
+    \xee\x84\x01\x20\x01(\t\x12\x1d.google.protobuf.FieldOptionsR\x16scalarG\
+    etterStyleField:b\n\x1dgenerate_option_getters_field\x18\xef\x84\x01\x20\
+    \x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1agenerateOptionGetters\
+    Field:`\n\x1ctrack_caller_accessors_field\x18\xf0\x84\x01\x20\x01(\x08\
+    \x12\x1d.google.protobuf.FieldOptionsR\x19trackCallerAccessorsField:g\n\
+    \x20carllerche_bytes_for_bytes_field\x18\xf3\x84\x01\x20\x01(\x08\x12\
+    \x1d.google.protobuf.FieldOptionsR\x1ccarllercheBytesForBytesField:i\n!c\
+    arllerche_bytes_for_string_field\x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.go\
+    ogle.protobuf.FieldOptionsR\x1dcarllercheBytesForStringField:d\n\x1egene\
+    rate_map_conversions_field\x18\x93\x85\x01\x20\x01(\x08\x12\x1d.google.p\
+    rotobuf.FieldOptionsR\x1bgenerateMapConversionsField:C\n\rmap_key_field\
+    \x18\x94\x85\x01\x20\x01(\t\x12\x1d.google.protobuf.FieldOptionsR\x0bmap\
+    KeyField:O\n\x13repeated_type_field\x18\x9a\x85\x01\x20\x01(\t\x12\x1d.g\
+    oogle.protobuf.FieldOptionsR\x11repeatedTypeFieldJ\x9fL\n\x07\x12\x05\0\
+    \0\x9d\x01\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\
+    \x02\0*\n\xe5\x01\n\x01\x02\x12\x03\n\0\x122^\x20see\x20https://github.c\
+    om/gogo/protobuf/blob/master/gogoproto/gogo.proto\n\x20for\x20the\x20ori\
+    ginal\x20idea\n2{\x20Generated\x20files\x20can\x20be\x20customized\x20us\
+    ing\x20this\x20proto\n\x20or\x20using\x20`Customize`\x20struct\x20when\
+    \x20codegen\x20is\x20invoked\x20programmatically.\n\n\t\n\x01\x07\x12\
+    \x04\x0c\0F\x01\n7\n\x02\x07\0\x12\x03\x0e\x04+\x1a,\x20When\x20true,\
+    \x20oneof\x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\0\x02\
+    \x12\x03\x0c\x07\"\n\n\n\x03\x07\0\x04\x12\x03\x0e\x04\x0c\n\n\n\x03\x07\
+    \0\x05\x12\x03\x0e\r\x11\n\n\n\x03\x07\0\x01\x12\x03\x0e\x12\"\n\n\n\x03\
+    \x07\0\x03\x12\x03\x0e%*\nQ\n\x02\x07\x01\x12\x03\x10\x047\x1aF\x20Gener\
+    ate\x20enum\x20of\x20oneof\x20cases\x20without\x20values\x20and\x20`whic\
+    h_xxx`\x20function\n\n\n\n\x03\x07\x01\x02\x12\x03\x0c\x07\"\n\n\n\x03\
+    \x07\x01\x04\x12\x03\x10\x04\x0c\n\n\n\x03\x07\x01\x05\x12\x03\x10\r\x11\
+    \n\n\n\x03\x07\x01\x01\x12\x03\x10\x12.\n\n\n\x03\x07\x01\x03\x12\x03\
+    \x1016\nI\n\x02\x07\x02\x12\x03\x12\x04,\x1a>\x20When\x20true\x20all\x20\
+    fields\x20are\x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\
+    \x03\x07\x02\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x02\x04\x12\x03\x12\x04\
+    \x0c\n\n\n\x03\x07\x02\x05\x12\x03\x12\r\x11\n\n\n\x03\x07\x02\x01\x12\
+    \x03\x12\x12#\n\n\n\x03\x07\x02\x03\x12\x03\x12&+\nP\n\x02\x07\x03\x12\
+    \x03\x14\x041\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20\
+    etc.\x20accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x03\x02\x12\
+    \x03\x0c\x07\"\n\n\n\x03\x07\x03\x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\
+    \x03\x05\x12\x03\x14\r\x11\n\n\n\x03\x07\x03\x01\x12\x03\x14\x12(\n\n\n\
+    \x03\x07\x03\x03\x12\x03\x14+0\nL\n\x02\x07\x04\x12\x03\x16\x04.\x1aA\
+    \x20When\x20false,\x20`get_`\x20is\x20not\x20generated\x20even\x20if\x20\
+    `syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\
+    \n\n\x03\x07\x04\x04\x12\x03\x16\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\
+    \x16\r\x11\n\n\n\x03\x07\x04\x01\x12\x03\x16\x12%\n\n\n\x03\x07\x04\x03\
+    \x12\x03\x16(-\nl\n\x02\x07\x05\x12\x03\x18\x044\x1aa\x20Return\x20numbe\
+    rs\x20and\x20`bool`\x20from\x20`get_`\x20by\x20value\x20(`by_value`,\x20\
+    default)\x20or\x20by\x20reference\x20(`by_ref`)\n\n\n\n\x03\x07\x05\x02\
+    \x12\x03\x0c\x07\"\n\n\n\x03\x07\x05\x04\x12\x03\x18\x04\x0c\n\n\n\x03\
+    \x07\x05\x05\x12\x03\x18\r\x13\n\n\n\x03\x07\x05\x01\x12\x03\x18\x14+\n\
+    \n\n\x03\x07\x05\x03\x12\x03\x18.3\n\\\n\x02\x07\x06\x12\x03\x1a\x046\
+    \x1aQ\x20Generate\x20`get_xxx_opt`\x20returning\x20`None`\x20for\x20unse\
+    t\x20fields\x20with\x20explicit\x20presence\n\n\n\n\x03\x07\x06\x02\x12\
+    \x03\x0c\x07\"\n\n\n\x03\x07\x06\x04\x12\x03\x1a\x04\x0c\n\n\n\x03\x07\
+    \x06\x05\x12\x03\x1a\r\x11\n\n\n\x03\x07\x06\x01\x12\x03\x1a\x12-\n\n\n\
+    \x03\x07\x06\x03\x12\x03\x1a05\nI\n\x02\x07\x07\x12\x03\x1c\x045\x1a>\
+    \x20Add\x20`#[track_caller]`\x20to\x20generated\x20accessors\x20which\
+    \x20may\x20panic\n\n\n\n\x03\x07\x07\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
+    \x07\x04\x12\x03\x1c\x04\x0c\n\n\n\x03\x07\x07\x05\x12\x03\x1c\r\x11\n\n\
+    \n\x03\x07\x07\x01\x12\x03\x1c\x12,\n\n\n\x03\x07\x07\x03\x12\x03\x1c/4\
+    \n2\n\x02\x07\x08\x12\x03\x1e\x049\x1a'\x20Use\x20`bytes::Bytes`\x20for\
+    \x20`bytes`\x20fields\n\n\n\n\x03\x07\x08\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\x08\x04\x12\x03\x1e\x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\x1e\r\
+    \x11\n\n\n\x03\x07\x08\x01\x12\x03\x1e\x120\n\n\n\x03\x07\x08\x03\x12\
+    \x03\x1e38\n3\n\x02\x07\t\x12\x03\x20\x04:\x1a(\x20Use\x20`bytes::Bytes`\
+    \x20for\x20`string`\x20fields\n\n\n\n\x03\x07\t\x02\x12\x03\x0c\x07\"\n\
+    \n\n\x03\x07\t\x04\x12\x03\x20\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03\x20\r\
+    \x11\n\n\n\x03\x07\t\x01\x12\x03\x20\x121\n\n\n\x03\x07\t\x03\x12\x03\
+    \x2049\nJ\n\x02\x07\n\x12\x03#\x04+\x1a?\x20Use\x20`serde_derive`\x20to\
+    \x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\n\n\x03\x07\n\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\n\x04\x12\x03#\x04\x0c\n\n\n\x03\
+    \x07\n\x05\x12\x03#\r\x11\n\n\n\x03\x07\n\x01\x12\x03#\x12\"\n\n\n\x03\
+    \x07\n\x03\x12\x03#%*\n3\n\x02\x07\x0b\x12\x03%\x041\x1a(\x20Guard\x20se\
+    rde\x20annotations\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07\x0b\x02\x12\
+    \x03\x0c\x07\"\n\n\n\x03\x07\x0b\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\x0b\
+    \x05\x12\x03%\r\x13\n\n\n\x03\x07\x0b\x01\x12\x03%\x14(\n\n\n\x03\x07\
+    \x0b\x03\x12\x03%+0\ng\n\x02\x07\x0c\x12\x03'\x04,\x1a\\\x20With\x20`ser\
+    de_derive`,\x20fill\x20missing\x20fields\x20with\x20defaults\x20and\x20s\
+    kip\x20fields\x20with\x20default\x20values\n\n\n\n\x03\x07\x0c\x02\x12\
+    \x03\x0c\x07\"\n\n\n\x03\x07\x0c\x04\x12\x03'\x04\x0c\n\n\n\x03\x07\x0c\
+    \x05\x12\x03'\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03'\x12#\n\n\n\x03\x07\
+    \x0c\x03\x12\x03'&+\nN\n\x02\x07\r\x12\x03*\x04+\x1aC\x20When\x20true,\
+    \x20will\x20only\x20generate\x20codes\x20that\x20works\x20with\x20lite\
+    \x20runtime.\n\n\n\n\x03\x07\r\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\r\x04\
+    \x12\x03*\x04\x0c\n\n\n\x03\x07\r\x05\x12\x03*\r\x11\n\n\n\x03\x07\r\x01\
+    \x12\x03*\x12\"\n\n\n\x03\x07\r\x03\x12\x03*%*\nV\n\x02\x07\x0e\x12\x03-\
+    \x044\x1aK\x20Generate\x20`fields_present`\x20function\x20returning\x20s\
+    et\x20of\x20present\x20field\x20numbers\n\n\n\n\x03\x07\x0e\x02\x12\x03\
+    \x0c\x07\"\n\n\n\x03\x07\x0e\x04\x12\x03-\x04\x0c\n\n\n\x03\x07\x0e\x05\
+    \x12\x03-\r\x11\n\n\n\x03\x07\x0e\x01\x12\x03-\x12+\n\n\n\x03\x07\x0e\
+    \x03\x12\x03-.3\nS\n\x02\x07\x0f\x12\x03/\x047\x1aH\x20Generate\x20conve\
+    rsions\x20between\x20map\x20fields\x20and\x20vectors\x20of\x20key-value\
+    \x20pairs\n\n\n\n\x03\x07\x0f\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0f\
+    \x04\x12\x03/\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\x03/\r\x11\n\n\n\x03\x07\
+    \x0f\x01\x12\x03/\x12.\n\n\n\x03\x07\x0f\x03\x12\x03/16\nh\n\x02\x07\x10\
+    \x12\x031\x04%\x1a]\x20Implement\x20`PartialEq`\x20comparing\x20`float`\
+    \x20and\x20`double`\x20fields\x20by\x20bit\x20pattern,\x20so\x20`NaN\x20\
+    ==\x20NaN`\n\n\n\n\x03\x07\x10\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x10\
+    \x04\x12\x031\x04\x0c\n\n\n\x03\x07\x10\x05\x12\x031\r\x11\n\n\n\x03\x07\
+    \x10\x01\x12\x031\x12\x1c\n\n\n\x03\x07\x10\x03\x12\x031\x1f$\n(\n\x02\
+    \x07\x11\x12\x033\x04&\x1a\x1d\x20Implement\x20`Eq`\x20for\x20messages\n\
+    \n\n\n\x03\x07\x11\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x11\x04\x12\x033\
+    \x04\x0c\n\n\n\x03\x07\x11\x05\x12\x033\r\x11\n\n\n\x03\x07\x11\x01\x12\
+    \x033\x12\x1d\n\n\n\x03\x07\x11\x03\x12\x033\x20%\n*\n\x02\x07\x12\x12\
+    \x035\x04(\x1a\x1f\x20Implement\x20`Hash`\x20for\x20messages\n\n\n\n\x03\
+    \x07\x12\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x12\x04\x12\x035\x04\x0c\n\
+    \n\n\x03\x07\x12\x05\x12\x035\r\x11\n\n\n\x03\x07\x12\x01\x12\x035\x12\
+    \x1f\n\n\n\x03\x07\x12\x03\x12\x035\"'\nJ\n\x02\x07\x13\x12\x037\x040\
+    \x1a?\x20Generate\x20typestate\x20builders\x20for\x20messages\x20with\
+    \x20required\x20fields\n\n\n\n\x03\x07\x13\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\x13\x04\x12\x037\x04\x0c\n\n\n\x03\x07\x13\x05\x12\x037\r\x11\n\
+    \n\n\x03\x07\x13\x01\x12\x037\x12'\n\n\n\x03\x07\x13\x03\x12\x037*/\nb\n\
+    \x02\x07\x14\x12\x039\x046\x1aW\x20Generate\x20`from_i32`\x20of\x20enums\
+    \x20with\x20more\x20values\x20than\x20this\x20as\x20a\x20lookup\x20in\
+    \x20a\x20sorted\x20array\n\n\n\n\x03\x07\x14\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\x14\x04\x12\x039\x04\x0c\n\n\n\x03\x07\x14\x05\x12\x039\r\x13\n\
+    \n\n\x03\x07\x14\x01\x12\x039\x14-\n\n\n\x03\x07\x14\x03\x12\x03905\n]\n\
+    \x02\x07\x15\x12\x03;\x04-\x1aR\x20Generate\x20`into_parts`\x20function\
+    \x20decomposing\x20message\x20into\x20struct\x20with\x20owned\x20fields\
+    \n\n\n\n\x03\x07\x15\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x15\x04\x12\x03\
+    ;\x04\x0c\n\n\n\x03\x07\x15\x05\x12\x03;\r\x11\n\n\n\x03\x07\x15\x01\x12\
+    \x03;\x12$\n\n\n\x03\x07\x15\x03\x12\x03;',\na\n\x02\x07\x16\x12\x03=\
+    \x045\x1aV\x20Naming\x20of\x20nested\x20types:\x20`modules`\x20(default)\
+    ,\x20`flatten_underscore`\x20or\x20`flatten_camel`\n\n\n\n\x03\x07\x16\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x16\x04\x12\x03=\x04\x0c\n\n\n\x03\
+    \x07\x16\x05\x12\x03=\r\x13\n\n\n\x03\x07\x16\x01\x12\x03=\x14,\n\n\n\
+    \x03\x07\x16\x03\x12\x03=/4\nY\n\x02\x07\x17\x12\x03?\x045\x1aN\x20Gener\
+    ate\x20`retain_fields`\x20function\x20clearing\x20fields\x20except\x20gi\
+    ven\x20field\x20numbers\n\n\n\n\x03\x07\x17\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\x17\x04\x12\x03?\x04\x0c\n\n\n\x03\x07\x17\x05\x12\x03?\r\x11\n\
+    \n\n\x03\x07\x17\x01\x12\x03?\x12,\n\n\n\x03\x07\x17\x03\x12\x03?/4\nd\n\
+    \x02\x07\x18\x12\x03A\x045\x1aY\x20Generate\x20`message_descriptor_proto\
+    `\x20function\x20returning\x20`DescriptorProto`\x20of\x20the\x20message\
+    \n\n\n\n\x03\x07\x18\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x18\x04\x12\x03\
+    A\x04\x0c\n\n\n\x03\x07\x18\x05\x12\x03A\r\x11\n\n\n\x03\x07\x18\x01\x12\
+    \x03A\x12,\n\n\n\x03\x07\x18\x03\x12\x03A/4\nU\n\x02\x07\x19\x12\x03C\
+    \x04.\x1aJ\x20Type\x20of\x20repeated\x20fields:\x20`Vec`\x20(default)\
+    \x20or\x20`smallvec::SmallVec<[_;\x20N]>`\n\n\n\n\x03\x07\x19\x02\x12\
+    \x03\x0c\x07\"\n\n\n\x03\x07\x19\x04\x12\x03C\x04\x0c\n\n\n\x03\x07\x19\
+    \x05\x12\x03C\r\x13\n\n\n\x03\x07\x19\x01\x12\x03C\x14%\n\n\n\x03\x07\
+    \x19\x03\x12\x03C(-\n3\n\x02\x07\x1a\x12\x03E\x043\x1a(\x20Generate\x20r\
+    epeated\x20fields\x20as\x20`Box<[T]>`\n\n\n\n\x03\x07\x1a\x02\x12\x03\
+    \x0c\x07\"\n\n\n\x03\x07\x1a\x04\x12\x03E\x04\x0c\n\n\n\x03\x07\x1a\x05\
+    \x12\x03E\r\x11\n\n\n\x03\x07\x1a\x01\x12\x03E\x12*\n\n\n\x03\x07\x1a\
+    \x03\x12\x03E-2\n\n\n\x01\x07\x12\x05H\0\x83\x01\x01\n7\n\x02\x07\x1b\
+    \x12\x03J\x04'\x1a,\x20When\x20true,\x20oneof\x20field\x20is\x20generate\
+    d\x20public\n\n\n\n\x03\x07\x1b\x02\x12\x03H\x07%\n\n\n\x03\x07\x1b\x04\
+    \x12\x03J\x04\x0c\n\n\n\x03\x07\x1b\x05\x12\x03J\r\x11\n\n\n\x03\x07\x1b\
+    \x01\x12\x03J\x12\x1e\n\n\n\x03\x07\x1b\x03\x12\x03J!&\nQ\n\x02\x07\x1c\
+    \x12\x03L\x043\x1aF\x20Generate\x20enum\x20of\x20oneof\x20cases\x20witho\
+    ut\x20values\x20and\x20`which_xxx`\x20function\n\n\n\n\x03\x07\x1c\x02\
+    \x12\x03H\x07%\n\n\n\x03\x07\x1c\x04\x12\x03L\x04\x0c\n\n\n\x03\x07\x1c\
+    \x05\x12\x03L\r\x11\n\n\n\x03\x07\x1c\x01\x12\x03L\x12*\n\n\n\x03\x07\
+    \x1c\x03\x12\x03L-2\nI\n\x02\x07\x1d\x12\x03N\x04(\x1a>\x20When\x20true\
     \x20all\x20fields\x20are\x20public,\x20and\x20not\x20accessors\x20genera\
-    ted\n\n\n\n\x03\x07\x11\x02\x12\x036\x07#\n\n\n\x03\x07\x11\x04\x12\x038\
-    \x04\x0c\n\n\n\x03\x07\x11\x05\x12\x038\r\x11\n\n\n\x03\x07\x11\x01\x12\
-    \x038\x12%\n\n\n\x03\x07\x11\x03\x12\x038(-\nP\n\x02\x07\x12\x12\x03:\
-    \x043\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20\
-    accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x12\x02\x12\x036\
-    \x07#\n\n\n\x03\x07\x12\x04\x12\x03:\x04\x0c\n\n\n\x03\x07\x12\x05\x12\
-    \x03:\r\x11\n\n\n\x03\x07\x12\x01\x12\x03:\x12*\n\n\n\x03\x07\x12\x03\
-    \x12\x03:-2\nL\n\x02\x07\x13\x12\x03<\x040\x1aA\x20When\x20false,\x20`ge\
+    ted\n\n\n\n\x03\x07\x1d\x02\x12\x03H\x07%\n\n\n\x03\x07\x1d\x04\x12\x03N\
+    \x04\x0c\n\n\n\x03\x07\x1d\x05\x12\x03N\r\x11\n\n\n\x03\x07\x1d\x01\x12\
+    \x03N\x12\x1f\n\n\n\x03\x07\x1d\x03\x12\x03N\"'\nP\n\x02\x07\x1e\x12\x03\
+    P\x04-\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\
+    \x20accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x1e\x02\x12\x03\
+    H\x07%\n\n\n\x03\x07\x1e\x04\x12\x03P\x04\x0c\n\n\n\x03\x07\x1e\x05\x12\
+    \x03P\r\x11\n\n\n\x03\x07\x1e\x01\x12\x03P\x12$\n\n\n\x03\x07\x1e\x03\
+    \x12\x03P',\nL\n\x02\x07\x1f\x12\x03R\x04*\x1aA\x20When\x20false,\x20`ge\
     t_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\x20\"proto2\
-    \"`\n\n\n\n\x03\x07\x13\x02\x12\x036\x07#\n\n\n\x03\x07\x13\x04\x12\x03<\
-    \x04\x0c\n\n\n\x03\x07\x13\x05\x12\x03<\r\x11\n\n\n\x03\x07\x13\x01\x12\
-    \x03<\x12'\n\n\n\x03\x07\x13\x03\x12\x03<*/\n2\n\x02\x07\x14\x12\x03>\
-    \x04;\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\
-    \x03\x07\x14\x02\x12\x036\x07#\n\n\n\x03\x07\x14\x04\x12\x03>\x04\x0c\n\
-    \n\n\x03\x07\x14\x05\x12\x03>\r\x11\n\n\n\x03\x07\x14\x01\x12\x03>\x122\
-    \n\n\n\x03\x07\x14\x03\x12\x03>5:\n3\n\x02\x07\x15\x12\x03@\x04<\x1a(\
-    \x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\
-    \x15\x02\x12\x036\x07#\n\n\n\x03\x07\x15\x04\x12\x03@\x04\x0c\n\n\n\x03\
-    \x07\x15\x05\x12\x03@\r\x11\n\n\n\x03\x07\x15\x01\x12\x03@\x123\n\n\n\
-    \x03\x07\x15\x03\x12\x03@6;\
+    \"`\n\n\n\n\x03\x07\x1f\x02\x12\x03H\x07%\n\n\n\x03\x07\x1f\x04\x12\x03R\
+    \x04\x0c\n\n\n\x03\x07\x1f\x05\x12\x03R\r\x11\n\n\n\x03\x07\x1f\x01\x12\
+    \x03R\x12!\n\n\n\x03\x07\x1f\x03\x12\x03R$)\nl\n\x02\x07\x20\x12\x03T\
+    \x040\x1aa\x20Return\x20numbers\x20and\x20`bool`\x20from\x20`get_`\x20by\
+    \x20value\x20(`by_value`,\x20default)\x20or\x20by\x20reference\x20(`by_r\
+    ef`)\n\n\n\n\x03\x07\x20\x02\x12\x03H\x07%\n\n\n\x03\x07\x20\x04\x12\x03\
+    T\x04\x0c\n\n\n\x03\x07\x20\x05\x12\x03T\r\x13\n\n\n\x03\x07\x20\x01\x12\
+    \x03T\x14'\n\n\n\x03\x07\x20\x03\x12\x03T*/\n\\\n\x02\x07!\x12\x03V\x042\
+    \x1aQ\x20Generate\x20`get_xxx_opt`\x20returning\x20`None`\x20for\x20unse\
+    t\x20fields\x20with\x20explicit\x20presence\n\n\n\n\x03\x07!\x02\x12\x03\
+    H\x07%\n\n\n\x03\x07!\x04\x12\x03V\x04\x0c\n\n\n\x03\x07!\x05\x12\x03V\r\
+    \x11\n\n\n\x03\x07!\x01\x12\x03V\x12)\n\n\n\x03\x07!\x03\x12\x03V,1\nI\n\
+    \x02\x07\"\x12\x03X\x041\x1a>\x20Add\x20`#[track_caller]`\x20to\x20gener\
+    ated\x20accessors\x20which\x20may\x20panic\n\n\n\n\x03\x07\"\x02\x12\x03\
+    H\x07%\n\n\n\x03\x07\"\x04\x12\x03X\x04\x0c\n\n\n\x03\x07\"\x05\x12\x03X\
+    \r\x11\n\n\n\x03\x07\"\x01\x12\x03X\x12(\n\n\n\x03\x07\"\x03\x12\x03X+0\
+    \n2\n\x02\x07#\x12\x03Z\x045\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`by\
+    tes`\x20fields\n\n\n\n\x03\x07#\x02\x12\x03H\x07%\n\n\n\x03\x07#\x04\x12\
+    \x03Z\x04\x0c\n\n\n\x03\x07#\x05\x12\x03Z\r\x11\n\n\n\x03\x07#\x01\x12\
+    \x03Z\x12,\n\n\n\x03\x07#\x03\x12\x03Z/4\n3\n\x02\x07$\x12\x03\\\x046\
+    \x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\
+    \x07$\x02\x12\x03H\x07%\n\n\n\x03\x07$\x04\x12\x03\\\x04\x0c\n\n\n\x03\
+    \x07$\x05\x12\x03\\\r\x11\n\n\n\x03\x07$\x01\x12\x03\\\x12-\n\n\n\x03\
+    \x07$\x03\x12\x03\\05\nJ\n\x02\x07%\x12\x03^\x04'\x1a?\x20Use\x20`serde_\
+    derive`\x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\n\
+    \n\x03\x07%\x02\x12\x03H\x07%\n\n\n\x03\x07%\x04\x12\x03^\x04\x0c\n\n\n\
+    \x03\x07%\x05\x12\x03^\r\x11\n\n\n\x03\x07%\x01\x12\x03^\x12\x1e\n\n\n\
+    \x03\x07%\x03\x12\x03^!&\n3\n\x02\x07&\x12\x03`\x04-\x1a(\x20Guard\x20se\
+    rde\x20annotations\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07&\x02\x12\x03H\
+    \x07%\n\n\n\x03\x07&\x04\x12\x03`\x04\x0c\n\n\n\x03\x07&\x05\x12\x03`\r\
+    \x13\n\n\n\x03\x07&\x01\x12\x03`\x14$\n\n\n\x03\x07&\x03\x12\x03`',\ng\n\
+    \x02\x07'\x12\x03b\x04(\x1a\\\x20With\x20`serde_derive`,\x20fill\x20miss\
+    ing\x20fields\x20with\x20defaults\x20and\x20skip\x20fields\x20with\x20de\
+    fault\x20values\n\n\n\n\x03\x07'\x02\x12\x03H\x07%\n\n\n\x03\x07'\x04\
+    \x12\x03b\x04\x0c\n\n\n\x03\x07'\x05\x12\x03b\r\x11\n\n\n\x03\x07'\x01\
+    \x12\x03b\x12\x1f\n\n\n\x03\x07'\x03\x12\x03b\"'\nV\n\x02\x07(\x12\x03d\
+    \x040\x1aK\x20Generate\x20`fields_present`\x20function\x20returning\x20s\
+    et\x20of\x20present\x20field\x20numbers\n\n\n\n\x03\x07(\x02\x12\x03H\
+    \x07%\n\n\n\x03\x07(\x04\x12\x03d\x04\x0c\n\n\n\x03\x07(\x05\x12\x03d\r\
+    \x11\n\n\n\x03\x07(\x01\x12\x03d\x12'\n\n\n\x03\x07(\x03\x12\x03d*/\ne\n\
+    \x02\x07)\x12\x03f\x04(\x1aZ\x20Implement\x20`AsRef<[u8]>`\x20and\x20`De\
+    ref<Target\x20=\x20[u8]>`\x20for\x20message\x20with\x20single\x20`bytes`\
+    \x20field\n\n\n\n\x03\x07)\x02\x12\x03H\x07%\n\n\n\x03\x07)\x04\x12\x03f\
+    \x04\x0c\n\n\n\x03\x07)\x05\x12\x03f\r\x11\n\n\n\x03\x07)\x01\x12\x03f\
+    \x12\x1f\n\n\n\x03\x07)\x03\x12\x03f\"'\nL\n\x02\x07*\x12\x03h\x04.\x1aA\
+    \x20Implement\x20`Index<usize>`\x20for\x20message\x20with\x20single\x20r\
+    epeated\x20field\n\n\n\n\x03\x07*\x02\x12\x03H\x07%\n\n\n\x03\x07*\x04\
+    \x12\x03h\x04\x0c\n\n\n\x03\x07*\x05\x12\x03h\r\x11\n\n\n\x03\x07*\x01\
+    \x12\x03h\x12%\n\n\n\x03\x07*\x03\x12\x03h(-\nS\n\x02\x07+\x12\x03j\x043\
+    \x1aH\x20Generate\x20conversions\x20between\x20map\x20fields\x20and\x20v\
+    ectors\x20of\x20key-value\x20pairs\n\n\n\n\x03\x07+\x02\x12\x03H\x07%\n\
+    \n\n\x03\x07+\x04\x12\x03j\x04\x0c\n\n\n\x03\x07+\x05\x12\x03j\r\x11\n\n\
+    \n\x03\x07+\x01\x12\x03j\x12*\n\n\n\x03\x07+\x03\x12\x03j-2\nV\n\x02\x07\
+    ,\x12\x03l\x04.\x1aK\x20Implement\x20`protobuf::Entity`\x20with\x20the\
+    \x20field\x20with\x20this\x20name\x20as\x20primary\x20key\n\n\n\n\x03\
+    \x07,\x02\x12\x03H\x07%\n\n\n\x03\x07,\x04\x12\x03l\x04\x0c\n\n\n\x03\
+    \x07,\x05\x12\x03l\r\x13\n\n\n\x03\x07,\x01\x12\x03l\x14%\n\n\n\x03\x07,\
+    \x03\x12\x03l(-\nh\n\x02\x07-\x12\x03n\x04!\x1a]\x20Implement\x20`Partia\
+    lEq`\x20comparing\x20`float`\x20and\x20`double`\x20fields\x20by\x20bit\
+    \x20pattern,\x20so\x20`NaN\x20==\x20NaN`\n\n\n\n\x03\x07-\x02\x12\x03H\
+    \x07%\n\n\n\x03\x07-\x04\x12\x03n\x04\x0c\n\n\n\x03\x07-\x05\x12\x03n\r\
+    \x11\n\n\n\x03\x07-\x01\x12\x03n\x12\x18\n\n\n\x03\x07-\x03\x12\x03n\x1b\
+    \x20\n(\n\x02\x07.\x12\x03p\x04\"\x1a\x1d\x20Implement\x20`Eq`\x20for\
+    \x20messages\n\n\n\n\x03\x07.\x02\x12\x03H\x07%\n\n\n\x03\x07.\x04\x12\
+    \x03p\x04\x0c\n\n\n\x03\x07.\x05\x12\x03p\r\x11\n\n\n\x03\x07.\x01\x12\
+    \x03p\x12\x19\n\n\n\x03\x07.\x03\x12\x03p\x1c!\n*\n\x02\x07/\x12\x03r\
+    \x04$\x1a\x1f\x20Implement\x20`Hash`\x20for\x20messages\n\n\n\n\x03\x07/\
+    \x02\x12\x03H\x07%\n\n\n\x03\x07/\x04\x12\x03r\x04\x0c\n\n\n\x03\x07/\
+    \x05\x12\x03r\r\x11\n\n\n\x03\x07/\x01\x12\x03r\x12\x1b\n\n\n\x03\x07/\
+    \x03\x12\x03r\x1e#\nH\n\x02\x070\x12\x03t\x04,\x1a=\x20Generate\x20types\
+    tate\x20builder\x20for\x20message\x20with\x20required\x20fields\n\n\n\n\
+    \x03\x070\x02\x12\x03H\x07%\n\n\n\x03\x070\x04\x12\x03t\x04\x0c\n\n\n\
+    \x03\x070\x05\x12\x03t\r\x11\n\n\n\x03\x070\x01\x12\x03t\x12#\n\n\n\x03\
+    \x070\x03\x12\x03t&+\nb\n\x02\x071\x12\x03v\x042\x1aW\x20Generate\x20`fr\
+    om_i32`\x20of\x20enums\x20with\x20more\x20values\x20than\x20this\x20as\
+    \x20a\x20lookup\x20in\x20a\x20sorted\x20array\n\n\n\n\x03\x071\x02\x12\
+    \x03H\x07%\n\n\n\x03\x071\x04\x12\x03v\x04\x0c\n\n\n\x03\x071\x05\x12\
+    \x03v\r\x13\n\n\n\x03\x071\x01\x12\x03v\x14)\n\n\n\x03\x071\x03\x12\x03v\
+    ,1\n]\n\x02\x072\x12\x03x\x04)\x1aR\x20Generate\x20`into_parts`\x20funct\
+    ion\x20decomposing\x20message\x20into\x20struct\x20with\x20owned\x20fiel\
+    ds\n\n\n\n\x03\x072\x02\x12\x03H\x07%\n\n\n\x03\x072\x04\x12\x03x\x04\
+    \x0c\n\n\n\x03\x072\x05\x12\x03x\r\x11\n\n\n\x03\x072\x01\x12\x03x\x12\
+    \x20\n\n\n\x03\x072\x03\x12\x03x#(\nK\n\x02\x073\x12\x03z\x04+\x1a@\x20I\
+    mplement\x20`Display`\x20using\x20template\x20with\x20`{field}`\x20place\
+    holders\n\n\n\n\x03\x073\x02\x12\x03H\x07%\n\n\n\x03\x073\x04\x12\x03z\
+    \x04\x0c\n\n\n\x03\x073\x05\x12\x03z\r\x13\n\n\n\x03\x073\x01\x12\x03z\
+    \x14\"\n\n\n\x03\x073\x03\x12\x03z%*\nY\n\x02\x074\x12\x03|\x041\x1aN\
+    \x20Generate\x20`retain_fields`\x20function\x20clearing\x20fields\x20exc\
+    ept\x20given\x20field\x20numbers\n\n\n\n\x03\x074\x02\x12\x03H\x07%\n\n\
+    \n\x03\x074\x04\x12\x03|\x04\x0c\n\n\n\x03\x074\x05\x12\x03|\r\x11\n\n\n\
+    \x03\x074\x01\x12\x03|\x12(\n\n\n\x03\x074\x03\x12\x03|+0\nd\n\x02\x075\
+    \x12\x03~\x041\x1aY\x20Generate\x20`message_descriptor_proto`\x20functio\
+    n\x20returning\x20`DescriptorProto`\x20of\x20the\x20message\n\n\n\n\x03\
+    \x075\x02\x12\x03H\x07%\n\n\n\x03\x075\x04\x12\x03~\x04\x0c\n\n\n\x03\
+    \x075\x05\x12\x03~\r\x11\n\n\n\x03\x075\x01\x12\x03~\x12(\n\n\n\x03\x075\
+    \x03\x12\x03~+0\nV\n\x02\x076\x12\x04\x80\x01\x04*\x1aJ\x20Type\x20of\
+    \x20repeated\x20fields:\x20`Vec`\x20(default)\x20or\x20`smallvec::SmallV\
+    ec<[_;\x20N]>`\n\n\n\n\x03\x076\x02\x12\x03H\x07%\n\x0b\n\x03\x076\x04\
+    \x12\x04\x80\x01\x04\x0c\n\x0b\n\x03\x076\x05\x12\x04\x80\x01\r\x13\n\
+    \x0b\n\x03\x076\x01\x12\x04\x80\x01\x14!\n\x0b\n\x03\x076\x03\x12\x04\
+    \x80\x01$)\n4\n\x02\x077\x12\x04\x82\x01\x04/\x1a(\x20Generate\x20repeat\
+    ed\x20fields\x20as\x20`Box<[T]>`\n\n\n\n\x03\x077\x02\x12\x03H\x07%\n\
+    \x0b\n\x03\x077\x04\x12\x04\x82\x01\x04\x0c\n\x0b\n\x03\x077\x05\x12\x04\
+    \x82\x01\r\x11\n\x0b\n\x03\x077\x01\x12\x04\x82\x01\x12&\n\x0b\n\x03\x07\
+    7\x03\x12\x04\x82\x01).\n\x0b\n\x01\x07\x12\x06\x85\x01\0\x9d\x01\x01\nJ\
+    \n\x02\x078\x12\x04\x87\x01\x04.\x1a>\x20When\x20true\x20all\x20fields\
+    \x20are\x20public,\x20and\x20not\x20accessors\x20generated\n\n\x0b\n\x03\
+    \x078\x02\x12\x04\x85\x01\x07#\n\x0b\n\x03\x078\x04\x12\x04\x87\x01\x04\
+    \x0c\n\x0b\n\x03\x078\x05\x12\x04\x87\x01\r\x11\n\x0b\n\x03\x078\x01\x12\
+    \x04\x87\x01\x12%\n\x0b\n\x03\x078\x03\x12\x04\x87\x01(-\nQ\n\x02\x079\
+    \x12\x04\x89\x01\x043\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`m\
+    ut_`\x20etc.\x20accessors\x20are\x20not\x20generated\n\n\x0b\n\x03\x079\
+    \x02\x12\x04\x85\x01\x07#\n\x0b\n\x03\x079\x04\x12\x04\x89\x01\x04\x0c\n\
+    \x0b\n\x03\x079\x05\x12\x04\x89\x01\r\x11\n\x0b\n\x03\x079\x01\x12\x04\
+    \x89\x01\x12*\n\x0b\n\x03\x079\x03\x12\x04\x89\x01-2\nM\n\x02\x07:\x12\
+    \x04\x8b\x01\x040\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20gener\
+    ated\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\x0b\n\x03\x07:\x02\
+    \x12\x04\x85\x01\x07#\n\x0b\n\x03\x07:\x04\x12\x04\x8b\x01\x04\x0c\n\x0b\
+    \n\x03\x07:\x05\x12\x04\x8b\x01\r\x11\n\x0b\n\x03\x07:\x01\x12\x04\x8b\
+    \x01\x12'\n\x0b\n\x03\x07:\x03\x12\x04\x8b\x01*/\nm\n\x02\x07;\x12\x04\
+    \x8d\x01\x046\x1aa\x20Return\x20numbers\x20and\x20`bool`\x20from\x20`get\
+    _`\x20by\x20value\x20(`by_value`,\x20default)\x20or\x20by\x20reference\
+    \x20(`by_ref`)\n\n\x0b\n\x03\x07;\x02\x12\x04\x85\x01\x07#\n\x0b\n\x03\
+    \x07;\x04\x12\x04\x8d\x01\x04\x0c\n\x0b\n\x03\x07;\x05\x12\x04\x8d\x01\r\
+    \x13\n\x0b\n\x03\x07;\x01\x12\x04\x8d\x01\x14-\n\x0b\n\x03\x07;\x03\x12\
+    \x04\x8d\x0105\n]\n\x02\x07<\x12\x04\x8f\x01\x048\x1aQ\x20Generate\x20`g\
+    et_xxx_opt`\x20returning\x20`None`\x20for\x20unset\x20fields\x20with\x20\
+    explicit\x20presence\n\n\x0b\n\x03\x07<\x02\x12\x04\x85\x01\x07#\n\x0b\n\
+    \x03\x07<\x04\x12\x04\x8f\x01\x04\x0c\n\x0b\n\x03\x07<\x05\x12\x04\x8f\
+    \x01\r\x11\n\x0b\n\x03\x07<\x01\x12\x04\x8f\x01\x12/\n\x0b\n\x03\x07<\
+    \x03\x12\x04\x8f\x0127\nJ\n\x02\x07=\x12\x04\x91\x01\x047\x1a>\x20Add\
+    \x20`#[track_caller]`\x20to\x20generated\x20accessors\x20which\x20may\
+    \x20panic\n\n\x0b\n\x03\x07=\x02\x12\x04\x85\x01\x07#\n\x0b\n\x03\x07=\
+    \x04\x12\x04\x91\x01\x04\x0c\n\x0b\n\x03\x07=\x05\x12\x04\x91\x01\r\x11\
+    \n\x0b\n\x03\x07=\x01\x12\x04\x91\x01\x12.\n\x0b\n\x03\x07=\x03\x12\x04\
+    \x91\x0116\n3\n\x02\x07>\x12\x04\x93\x01\x04;\x1a'\x20Use\x20`bytes::Byt\
+    es`\x20for\x20`bytes`\x20fields\n\n\x0b\n\x03\x07>\x02\x12\x04\x85\x01\
+    \x07#\n\x0b\n\x03\x07>\x04\x12\x04\x93\x01\x04\x0c\n\x0b\n\x03\x07>\x05\
+    \x12\x04\x93\x01\r\x11\n\x0b\n\x03\x07>\x01\x12\x04\x93\x01\x122\n\x0b\n\
+    \x03\x07>\x03\x12\x04\x93\x015:\n4\n\x02\x07?\x12\x04\x95\x01\x04<\x1a(\
+    \x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\x0b\n\x03\x07\
+    ?\x02\x12\x04\x85\x01\x07#\n\x0b\n\x03\x07?\x04\x12\x04\x95\x01\x04\x0c\
+    \n\x0b\n\x03\x07?\x05\x12\x04\x95\x01\r\x11\n\x0b\n\x03\x07?\x01\x12\x04\
+    \x95\x01\x123\n\x0b\n\x03\x07?\x03\x12\x04\x95\x016;\nT\n\x02\x07@\x12\
+    \x04\x97\x01\x049\x1aH\x20Generate\x20conversions\x20between\x20map\x20f\
+    ields\x20and\x20vectors\x20of\x20key-value\x20pairs\n\n\x0b\n\x03\x07@\
+    \x02\x12\x04\x85\x01\x07#\n\x0b\n\x03\x07@\x04\x12\x04\x97\x01\x04\x0c\n\
+    \x0b\n\x03\x07@\x05\x12\x04\x97\x01\r\x11\n\x0b\n\x03\x07@\x01\x12\x04\
+    \x97\x01\x120\n\x0b\n\x03\x07@\x03\x12\x04\x97\x0138\n~\n\x02\x07A\x12\
+    \x04\x9a\x01\x04*\x1ar\x20For\x20repeated\x20message\x20field,\x20genera\
+    te\x20`xxx_to_map`\x20function\n\x20returning\x20elements\x20keyed\x20by\
+    \x20the\x20field\x20with\x20this\x20name\n\n\x0b\n\x03\x07A\x02\x12\x04\
+    \x85\x01\x07#\n\x0b\n\x03\x07A\x04\x12\x04\x9a\x01\x04\x0c\n\x0b\n\x03\
+    \x07A\x05\x12\x04\x9a\x01\r\x13\n\x0b\n\x03\x07A\x01\x12\x04\x9a\x01\x14\
+    !\n\x0b\n\x03\x07A\x03\x12\x04\x9a\x01$)\nV\n\x02\x07B\x12\x04\x9c\x01\
+    \x040\x1aJ\x20Type\x20of\x20repeated\x20fields:\x20`Vec`\x20(default)\
+    \x20or\x20`smallvec::SmallVec<[_;\x20N]>`\n\n\x0b\n\x03\x07B\x02\x12\x04\
+    \x85\x01\x07#\n\x0b\n\x03\x07B\x04\x12\x04\x9c\x01\x04\x0c\n\x0b\n\x03\
+    \x07B\x05\x12\x04\x9c\x01\r\x13\n\x0b\n\x03\x07B\x01\x12\x04\x9c\x01\x14\
+    '\n\x0b\n\x03\x07B\x03\x12\x04\x9c\x01*/\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
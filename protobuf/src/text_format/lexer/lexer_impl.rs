@@ -24,6 +24,7 @@ pub enum LexerError {
     ParseFloatError,
     IncorrectFloatLit, // TODO: how it is different from ParseFloatError?
     IncorrectJsonEscape,
+    IncorrectEscape(String),
     IncorrectJsonNumber,
     IncorrectUnicodeChar,
     ExpectHexDigit,
@@ -31,6 +32,7 @@ pub enum LexerError {
     ExpectDecDigit,
     StrLitDecodeError(StrLitDecodeError),
     ExpectedIdent,
+    UnterminatedBlockComment,
 }
 
 impl fmt::Display for LexerError {
@@ -43,6 +45,9 @@ impl fmt::Display for LexerError {
             LexerError::ParseFloatError => write!(f, "Parse float error"),
             LexerError::IncorrectFloatLit => write!(f, "Incorrect float literal"),
             LexerError::IncorrectJsonEscape => write!(f, "Incorrect JSON escape"),
+            LexerError::IncorrectEscape(e) => {
+                write!(f, "Invalid escape sequence `{}` in string literal", e)
+            }
             LexerError::IncorrectJsonNumber => write!(f, "Incorrect JSON number"),
             LexerError::IncorrectUnicodeChar => write!(f, "Incorrect Unicode char"),
             LexerError::ExpectHexDigit => write!(f, "Expecting hex digit"),
@@ -50,6 +55,7 @@ impl fmt::Display for LexerError {
             LexerError::ExpectDecDigit => write!(f, "Expecting dec digit"),
             LexerError::StrLitDecodeError(e) => write!(f, "{}", e),
             LexerError::ExpectedIdent => write!(f, "Expecting identifier"),
+            LexerError::UnterminatedBlockComment => write!(f, "Unterminated block comment"),
         }
     }
 }
@@ -150,11 +156,13 @@ impl<'a> Lexer<'a> {
         self.take_while(|c| c.is_whitespace());
     }
 
+    /// Skip `/* ... */` comment. Comments do not nest, and anything
+    /// inside the comment (quotes, `//`, `/*`) is not interpreted.
     fn skip_c_comment(&mut self) -> LexerResult<()> {
         if self.skip_if_lookahead_is_str("/*") {
             let end = "*/";
             match self.rem_chars().find(end) {
-                None => Err(LexerError::UnexpectedEof),
+                None => Err(LexerError::UnterminatedBlockComment),
                 Some(len) => {
                     let new_pos = self.pos + len + end.len();
                     self.skip_to_pos(new_pos);
@@ -381,7 +389,7 @@ impl<'a> Lexer<'a> {
 
     // octalDigit   = "0" … "7"
     fn next_octal_digit(&mut self) -> LexerResult<u32> {
-        self.next_char_expect(|c| c >= '0' && c <= '9', LexerError::ExpectOctDigit)
+        self.next_char_expect(|c| ('0'..='7').contains(&c), LexerError::ExpectOctDigit)
             .map(|c| c as u32 - '0' as u32)
     }
 
@@ -449,50 +457,119 @@ impl<'a> Lexer<'a> {
 
     // String literals
 
-    // charValue = hexEscape | octEscape | charEscape | /[^\0\n\\]/
+    // charValue = hexEscape | octEscape | charEscape | unicodeEscape | /[^\0\n\\]/
     // hexEscape = '\' ( "x" | "X" ) hexDigit hexDigit
     // https://github.com/google/protobuf/issues/4560
     // octEscape = '\' octalDigit octalDigit octalDigit
     // charEscape = '\' ( "a" | "b" | "f" | "n" | "r" | "t" | "v" | '\' | "'" | '"' )
+    // unicodeEscape = '\' "u" hexDigit{4} | '\' "U" hexDigit{8}
     // quote = "'" | '"'
-    pub fn next_byte_value(&mut self) -> LexerResult<u8> {
-        match self.next_char()? {
+    //
+    // Decoded bytes are appended to `target`. Unescaped chars and Unicode escapes
+    // are encoded as UTF-8, octal and hex escapes produce a single byte.
+    //
+    // On incorrect escape lexer is left at the backslash, so errors point to the escape.
+    pub fn next_byte_value_to(&mut self, target: &mut Vec<u8>) -> LexerResult<()> {
+        let start = *self;
+        let byte = match self.next_char()? {
             '\\' => {
                 match self.next_char()? {
-                    '\'' => Ok(b'\''),
-                    '"' => Ok(b'"'),
-                    '\\' => Ok(b'\\'),
-                    'a' => Ok(b'\x07'),
-                    'b' => Ok(b'\x08'),
-                    'f' => Ok(b'\x0c'),
-                    'n' => Ok(b'\n'),
-                    'r' => Ok(b'\r'),
-                    't' => Ok(b'\t'),
-                    'v' => Ok(b'\x0b'),
+                    '\'' => b'\'',
+                    '"' => b'"',
+                    '\\' => b'\\',
+                    '?' => b'?',
+                    'a' => b'\x07',
+                    'b' => b'\x08',
+                    'f' => b'\x0c',
+                    'n' => b'\n',
+                    'r' => b'\r',
+                    't' => b'\t',
+                    'v' => b'\x0b',
                     'x' => {
-                        let d1 = self.next_hex_digit()? as u8;
-                        let d2 = self.next_hex_digit()? as u8;
-                        Ok(((d1 << 4) | d2) as u8)
+                        let d1 = match self.next_hex_digit() {
+                            Ok(d) => d as u8,
+                            Err(_) => return Err(self.incorrect_escape(start)),
+                        };
+                        match self.next_hex_digit() {
+                            Ok(d2) => (d1 << 4) | d2 as u8,
+                            Err(_) => d1,
+                        }
                     }
                     d if d >= '0' && d <= '7' => {
-                        let mut r = d as u8 - b'0';
+                        let mut r = d as u32 - '0' as u32;
                         for _ in 0..2 {
                             match self.next_octal_digit() {
                                 Err(_) => break,
-                                Ok(d) => r = (r << 3) + d as u8,
+                                Ok(d) => r = (r << 3) + d,
                             }
                         }
-                        Ok(r)
+                        if r > u8::MAX as u32 {
+                            return Err(self.incorrect_escape(start));
+                        }
+                        r as u8
+                    }
+                    u @ 'u' | u @ 'U' => {
+                        let len = if u == 'u' { 4 } else { 8 };
+                        let c = self.next_unicode_escape_value(len, start)?;
+                        target.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                        return Ok(());
                     }
                     // https://github.com/google/protobuf/issues/4562
-                    // TODO: overflow
-                    c => Ok(c as u8),
+                    _ => return Err(self.incorrect_escape(start)),
+                }
+            }
+            '\n' | '\0' => return Err(LexerError::IncorrectInput),
+            c => {
+                target.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                return Ok(());
+            }
+        };
+        target.push(byte);
+        Ok(())
+    }
+
+    /// `len` hex digits of escape sequence started at `start`.
+    fn next_escape_hex_digits(&mut self, len: usize, start: Lexer<'a>) -> LexerResult<u32> {
+        let mut r = 0;
+        for _ in 0..len {
+            match self.next_hex_digit() {
+                Ok(d) => r = (r << 4) | d,
+                Err(_) => return Err(self.incorrect_escape(start)),
+            }
+        }
+        Ok(r)
+    }
+
+    /// Value of `\u` or `\U` escape with `len` hex digits, lexer is after `u` or `U`.
+    ///
+    /// Like in `protoc`, high surrogate followed by `\u` escape of low surrogate
+    /// is decoded as a single char.
+    fn next_unicode_escape_value(&mut self, len: usize, start: Lexer<'a>) -> LexerResult<char> {
+        let r = self.next_escape_hex_digits(len, start)?;
+        if (0xD800..=0xDBFF).contains(&r) {
+            let mut low = *self;
+            let low_start = low;
+            if low.next_char_if_eq('\\') && low.next_char_if_eq('u') {
+                if let Ok(l) = low.next_escape_hex_digits(4, low_start) {
+                    if (0xDC00..=0xDFFF).contains(&l) {
+                        *self = low;
+                        let c = 0x10000 + ((r - 0xD800) << 10) + (l - 0xDC00);
+                        return Ok(char::from_u32(c).unwrap());
+                    }
                 }
             }
-            '\n' | '\0' => Err(LexerError::IncorrectInput),
-            // TODO: check overflow
-            c => Ok(c as u8),
         }
+        match char::from_u32(r) {
+            Some(c) => Ok(c),
+            None => Err(self.incorrect_escape(start)),
+        }
+    }
+
+    /// Error for escape sequence started at `start`, lexer is reset to `start`.
+    fn incorrect_escape(&mut self, start: Lexer<'a>) -> LexerError {
+        let escape = self.input[start.pos..self.pos].to_owned();
+        *self = start;
+        LexerError::IncorrectEscape(escape)
     }
 
     fn char_try_from(i: u32) -> LexerResult<char> {
@@ -529,6 +606,7 @@ impl<'a> Lexer<'a> {
     // strLit = ( "'" { charValue } "'" ) | ( '"' { charValue } '"' )
     fn next_str_lit_raw(&mut self) -> LexerResult<String> {
         let mut raw = String::new();
+        let mut bytes = Vec::new();
 
         let mut first = true;
         loop {
@@ -545,7 +623,15 @@ impl<'a> Lexer<'a> {
             };
             first = false;
             while self.lookahead_char() != Some(q) {
-                self.next_byte_value()?;
+                match self.language {
+                    ParserLanguage::Json => {
+                        self.next_json_char_value()?;
+                    }
+                    _ => {
+                        bytes.clear();
+                        self.next_byte_value_to(&mut bytes)?;
+                    }
+                }
             }
             self.next_char_expect_eq(q)?;
 
@@ -723,4 +809,137 @@ mod test {
         let mess = lex(msg, |p| p.next_token_inner());
         assert_eq!(Token::FloatLit(1_000_000_000.0), mess);
     }
+
+    fn lex_all(input: &str) -> Vec<TokenWithLocation> {
+        let mut lexer = Lexer::new(input, ParserLanguage::Proto);
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer
+            .next_token()
+            .unwrap_or_else(|e| panic!("lexer failed at {}: {}", lexer.loc, e))
+        {
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    fn lex_all_tokens(input: &str) -> Vec<Token> {
+        lex_all(input).into_iter().map(|t| t.token).collect()
+    }
+
+    #[test]
+    fn test_lexer_block_comment_with_quotes() {
+        assert_eq!(
+            vec![Token::Ident("a".to_owned()), Token::Ident("b".to_owned())],
+            lex_all_tokens(r#"a /* "not a string' */ b"#)
+        );
+    }
+
+    #[test]
+    fn test_lexer_line_comment_inside_block_comment() {
+        assert_eq!(
+            vec![Token::Ident("a".to_owned()), Token::Symbol(';')],
+            lex_all_tokens("a /* // not a line comment */ ;")
+        );
+    }
+
+    #[test]
+    fn test_lexer_block_comment_inside_line_comment() {
+        assert_eq!(
+            vec![Token::Ident("a".to_owned()), Token::Ident("b".to_owned())],
+            lex_all_tokens("a // /* not a block comment\nb")
+        );
+    }
+
+    #[test]
+    fn test_lexer_comment_without_whitespace() {
+        assert_eq!(
+            vec![
+                Token::Ident("a".to_owned()),
+                Token::Symbol('='),
+                Token::IntLit(1),
+                Token::Symbol(';'),
+            ],
+            lex_all_tokens("a/**/=/*x*/1/*y*/;//z")
+        );
+    }
+
+    #[test]
+    fn test_lexer_comment_between_str_lits() {
+        assert_eq!(
+            vec![Token::StrLit(StrLit {
+                escaped: "a/*b".to_owned()
+            })],
+            lex_all_tokens(r#""a/*"/* "c" */"b""#)
+        );
+    }
+
+    #[test]
+    fn test_lexer_comment_multiline_loc() {
+        let tokens = lex_all("/* a\n * b\n */x");
+        assert_eq!(1, tokens.len());
+        assert_eq!(3, tokens[0].loc.line);
+        assert_eq!(4, tokens[0].loc.col);
+    }
+
+    #[test]
+    fn test_lexer_nested_block_comment_ends_at_first_terminator() {
+        assert_eq!(
+            vec![
+                Token::Ident("b".to_owned()),
+                Token::Symbol('*'),
+                Token::Symbol('/')
+            ],
+            lex_all_tokens("/* a /* inner */ b */")
+        );
+    }
+
+    #[test]
+    fn test_lexer_unterminated_block_comment() {
+        let mut lexer = Lexer::new("a /* b // c", ParserLanguage::Proto);
+        match lexer.next_token() {
+            Err(LexerError::UnterminatedBlockComment) => {}
+            r => panic!(
+                "expecting unterminated comment error, got {:?}",
+                r.map(|_| ())
+            ),
+        }
+    }
+
+    #[test]
+    fn test_lexer_str_lit_escapes() {
+        assert_eq!(
+            b"?\x01\x0f\x07\xff".to_vec(),
+            StrLit {
+                escaped: r#"\?\x1\x0f\7\377"#.to_owned()
+            }
+            .decode_bytes()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lexer_str_lit_incorrect_escape() {
+        for (input, escape, col) in &[
+            (r#"a "x\q""#, r"\q", 5),
+            (r#""\400""#, r"\400", 2),
+            (r#""\xg""#, r"\x", 2),
+            (r#""\u12""#, r"\u12", 2),
+            (r#""\U00110000""#, r"\U00110000", 2),
+            (r#""\udc00""#, r"\udc00", 2),
+        ] {
+            let mut lexer = Lexer::new(input, ParserLanguage::TextFormat);
+            let e = loop {
+                match lexer.next_token() {
+                    Ok(Some(_)) => {}
+                    Ok(None) => panic!("expecting error: {}", input),
+                    Err(e) => break e,
+                }
+            };
+            match e {
+                LexerError::IncorrectEscape(e) => assert_eq!(escape, &e),
+                e => panic!("expecting incorrect escape, got {:?}", e),
+            }
+            assert_eq!(Loc { line: 1, col: *col }, lexer.loc, "{}", input);
+        }
+    }
 }
@@ -15,7 +15,9 @@ pub enum StrLitDecodeError {
 impl fmt::Display for StrLitDecodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            StrLitDecodeError::FromUtf8Error(e) => write!(f, "{}", e),
+            StrLitDecodeError::FromUtf8Error(e) => {
+                write!(f, "invalid UTF-8 in string literal: {}", e)
+            }
             StrLitDecodeError::OtherError => write!(f, "String literal decode error"),
         }
     }
@@ -52,19 +54,19 @@ impl fmt::Display for StrLit {
 impl StrLit {
     /// May fail if not valid UTF8
     pub fn decode_utf8(&self) -> StrLitDecodeResult<String> {
-        let mut lexer = Lexer::new(&self.escaped, ParserLanguage::Json);
-        let mut r = Vec::new();
-        while !lexer.eof() {
-            r.push(lexer.next_byte_value()?);
-        }
-        Ok(String::from_utf8(r)?)
+        Ok(String::from_utf8(self.decode_bytes()?)?)
+    }
+
+    /// Decode string replacing invalid UTF-8 sequences with `U+FFFD`.
+    pub fn decode_utf8_lossy(&self) -> StrLitDecodeResult<String> {
+        Ok(String::from_utf8_lossy(&self.decode_bytes()?).into_owned())
     }
 
     pub fn decode_bytes(&self) -> StrLitDecodeResult<Vec<u8>> {
         let mut lexer = Lexer::new(&self.escaped, ParserLanguage::Json);
         let mut r = Vec::new();
         while !lexer.eof() {
-            r.push(lexer.next_byte_value()?);
+            lexer.next_byte_value_to(&mut r)?;
         }
         Ok(r)
     }
@@ -89,4 +91,38 @@ mod test {
             .unwrap()
         )
     }
+
+    #[test]
+    fn decode_utf8_unescaped() {
+        assert_eq!(
+            "caf\u{e9} \u{1f600}".to_owned(),
+            StrLit {
+                escaped: "caf\u{e9} \u{1f600}".to_owned()
+            }
+            .decode_utf8()
+            .unwrap()
+        )
+    }
+
+    #[test]
+    fn decode_unicode_escapes() {
+        assert_eq!(
+            "\u{e9}\u{1f600}\u{1f600}".to_owned(),
+            StrLit {
+                escaped: r"\u00e9\U0001F600\ud83d\ude00".to_owned()
+            }
+            .decode_utf8()
+            .unwrap()
+        )
+    }
+
+    #[test]
+    fn decode_utf8_invalid() {
+        let lit = StrLit {
+            escaped: r"a\377".to_owned(),
+        };
+        assert!(lit.decode_utf8().is_err());
+        assert_eq!("a\u{fffd}", lit.decode_utf8_lossy().unwrap());
+        assert_eq!(b"a\xff".to_vec(), lit.decode_bytes().unwrap());
+    }
 }
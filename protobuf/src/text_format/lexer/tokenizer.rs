@@ -104,7 +104,14 @@ impl<'a> Tokenizer<'a> {
         Ok(match self.next_token {
             Some(ref token) => Some(&token.token),
             None => {
-                self.next_token = self.lexer.next_token()?;
+                self.next_token = match self.lexer.next_token() {
+                    Ok(token) => token,
+                    Err(e) => {
+                        // Report lexer error at the position where lexer stopped
+                        self.last_token_loc = None;
+                        return Err(e.into());
+                    }
+                };
                 self.last_token_loc = self.next_token.as_ref().map(|t| t.loc.clone());
                 match self.next_token {
                     Some(ref token) => Some(&token.token),
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
 use std::str;
+use std::string::FromUtf8Error;
 
 use crate::message::Message;
 use crate::message_dyn::MessageDyn;
@@ -9,7 +12,6 @@ use crate::reflect::MessageDescriptor;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeTypeBox;
-use crate::text_format::lexer::int;
 use crate::text_format::lexer::Loc;
 use crate::text_format::lexer::ParserLanguage;
 use crate::text_format::lexer::StrLitDecodeError;
@@ -23,11 +25,57 @@ pub enum ParseErrorWithoutLoc {
     UnknownField(String),
     UnknownEnumValue(String),
     MapFieldIsSpecifiedMoreThanOnce(String),
-    IntegerOverflow,
+    /// Integer literal does not fit into the field type.
+    ///
+    /// Reported at the location of the literal rather than at the current
+    /// tokenizer position.
+    ValueOutOfRange {
+        value: String,
+        field_type: &'static str,
+        field_name: String,
+        loc: Loc,
+    },
+    /// String literal of `string` field is not valid UTF-8.
+    ///
+    /// Reported at the location of the literal.
+    InvalidUtf8 {
+        field_name: String,
+        error: FromUtf8Error,
+        loc: Loc,
+    },
     ExpectingBool,
     MessageNotInitialized,
 }
 
+impl fmt::Display for ParseErrorWithoutLoc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorWithoutLoc::TokenizerError(e) => write!(f, "{}", e),
+            ParseErrorWithoutLoc::StrLitDecodeError(e) => write!(f, "{}", e),
+            ParseErrorWithoutLoc::UnknownField(n) => write!(f, "unknown field `{}`", n),
+            ParseErrorWithoutLoc::UnknownEnumValue(n) => write!(f, "unknown enum value `{}`", n),
+            ParseErrorWithoutLoc::MapFieldIsSpecifiedMoreThanOnce(n) => {
+                write!(f, "map entry field `{}` is specified more than once", n)
+            }
+            ParseErrorWithoutLoc::ValueOutOfRange {
+                value,
+                field_type,
+                field_name,
+                ..
+            } => write!(
+                f,
+                "value {} out of range for {} field {}",
+                value, field_type, field_name
+            ),
+            ParseErrorWithoutLoc::InvalidUtf8 {
+                field_name, error, ..
+            } => write!(f, "invalid UTF-8 in string field {}: {}", field_name, error),
+            ParseErrorWithoutLoc::ExpectingBool => write!(f, "expecting bool"),
+            ParseErrorWithoutLoc::MessageNotInitialized => write!(f, "message not initialized"),
+        }
+    }
+}
+
 impl From<TokenizerError> for ParseErrorWithoutLoc {
     fn from(e: TokenizerError) -> Self {
         ParseErrorWithoutLoc::TokenizerError(e)
@@ -40,12 +88,6 @@ impl From<StrLitDecodeError> for ParseErrorWithoutLoc {
     }
 }
 
-impl From<int::Overflow> for ParseErrorWithoutLoc {
-    fn from(_: int::Overflow) -> Self {
-        ParseErrorWithoutLoc::IntegerOverflow
-    }
-}
-
 /// Text format parse error.
 #[derive(Debug)]
 pub struct ParseError {
@@ -55,7 +97,7 @@ pub struct ParseError {
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {:?}", self.loc, self.error)
+        write!(f, "{} at {}", self.error, self.loc)
     }
 }
 
@@ -64,9 +106,37 @@ impl std::error::Error for ParseError {}
 pub type ParseResult<A> = Result<A, ParseErrorWithoutLoc>;
 pub type ParseWithLocResult<A> = Result<A, ParseError>;
 
+/// Text format parse options.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::text_format;
+/// let mut parse_options = text_format::ParseOptions::default();
+/// parse_options
+///     .field_aliases
+///     .insert("old_name".to_owned(), "new_name".to_owned());
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct ParseOptions {
+    /// Map from field names found in input to actual field names.
+    ///
+    /// Alias is used only when a message has no field with the name found in input,
+    /// so aliases apply to all messages, and allow reading data
+    /// written before a field was renamed.
+    pub field_aliases: HashMap<String, String>,
+    /// Replace invalid UTF-8 sequences in values of `string` fields with `U+FFFD`.
+    ///
+    /// By default such values are rejected with an error pointing to the literal.
+    pub replace_invalid_utf8: bool,
+    /// Prevent initializing `ParseOptions` enumerating all field.
+    pub _future_options: (),
+}
+
 #[derive(Clone)]
 struct Parser<'a> {
     tokenizer: Tokenizer<'a>,
+    parse_options: ParseOptions,
 }
 
 impl<'a> Parser<'a> {
@@ -92,44 +162,24 @@ impl<'a> Parser<'a> {
         Ok(value)
     }
 
-    fn read_u64(&mut self) -> ParseResult<u64> {
-        self.read_colon()?;
-
-        Ok(self.tokenizer.next_int_lit()?)
-    }
-
-    fn read_u32(&mut self) -> ParseResult<u32> {
-        self.read_colon()?;
-
-        let int_lit = self.tokenizer.next_int_lit()?;
-        let value_u32 = int_lit as u32;
-        if value_u32 as u64 != int_lit {
-            return Err(ParseErrorWithoutLoc::IntegerOverflow);
-        }
-        Ok(value_u32)
-    }
-
-    fn read_i64(&mut self) -> ParseResult<i64> {
+    /// Read integer literal with optional minus sign and check it fits into `T`.
+    fn read_int<T: TryFrom<i128>>(
+        &mut self,
+        field_name: &str,
+        field_type: &'static str,
+    ) -> ParseResult<T> {
         self.read_colon()?;
 
-        if self.tokenizer.next_symbol_if_eq('-')? {
-            let int_lit = self.tokenizer.next_int_lit()?;
-            Ok(int::neg(int_lit)?)
-        } else {
-            let int_lit = self.tokenizer.next_int_lit()?;
-            if int_lit > i64::max_value() as u64 {
-                return Err(ParseErrorWithoutLoc::IntegerOverflow);
-            }
-            Ok(int_lit as i64)
-        }
-    }
-
-    fn read_i32(&mut self) -> ParseResult<i32> {
-        let value = self.read_i64()?;
-        if value < i32::min_value() as i64 || value > i32::max_value() as i64 {
-            return Err(ParseErrorWithoutLoc::IntegerOverflow);
-        }
-        Ok(value as i32)
+        let loc = self.tokenizer.lookahead_loc();
+        let minus = self.tokenizer.next_symbol_if_eq('-')?;
+        let int_lit = self.tokenizer.next_int_lit()? as i128;
+        let value = if minus { -int_lit } else { int_lit };
+        T::try_from(value).map_err(|_| ParseErrorWithoutLoc::ValueOutOfRange {
+            value: value.to_string(),
+            field_type,
+            field_name: field_name.to_owned(),
+            loc,
+        })
     }
 
     fn read_f64(&mut self) -> ParseResult<f64> {
@@ -162,13 +212,26 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn read_string(&mut self) -> ParseResult<String> {
+    fn read_string(&mut self, field_name: &str) -> ParseResult<String> {
         self.read_colon()?;
 
-        Ok(self
-            .tokenizer
-            .next_str_lit()
-            .and_then(|s| s.decode_utf8().map_err(From::from))?)
+        let lit = self.tokenizer.next_str_lit()?;
+        // location of the literal
+        let loc = self.tokenizer.loc();
+        if self.parse_options.replace_invalid_utf8 {
+            return Ok(lit.decode_utf8_lossy()?);
+        }
+        match lit.decode_utf8() {
+            Ok(s) => Ok(s),
+            Err(StrLitDecodeError::FromUtf8Error(error)) => {
+                Err(ParseErrorWithoutLoc::InvalidUtf8 {
+                    field_name: field_name.to_owned(),
+                    error,
+                    loc,
+                })
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 
     fn read_bytes(&mut self) -> ParseResult<Vec<u8>> {
@@ -182,18 +245,28 @@ impl<'a> Parser<'a> {
 
     fn read_message(&mut self, descriptor: &MessageDescriptor) -> ParseResult<Box<dyn MessageDyn>> {
         let mut message = descriptor.new_instance();
+        self.merge_message(&mut *message, descriptor)?;
+        Ok(message)
+    }
 
+    /// Read message in braces and merge it into existing message.
+    fn merge_message(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        descriptor: &MessageDescriptor,
+    ) -> ParseResult<()> {
         let symbol = self.tokenizer.next_symbol_expect_eq_oneof(&['{', '<'])?;
         let terminator = if symbol == '{' { '}' } else { '>' };
         while !self.tokenizer.lookahead_is_symbol(terminator)? {
-            self.merge_field(&mut *message, descriptor)?;
+            self.merge_field(message, descriptor)?;
         }
         self.tokenizer.next_symbol_expect_eq(terminator)?;
-        Ok(message)
+        Ok(())
     }
 
     fn read_map_entry(
         &mut self,
+        field_name: &str,
         k: &RuntimeTypeBox,
         v: &RuntimeTypeBox,
     ) -> ParseResult<(ReflectValueBox, ReflectValueBox)> {
@@ -217,7 +290,8 @@ impl<'a> Parser<'a> {
                 return Err(ParseErrorWithoutLoc::MapFieldIsSpecifiedMoreThanOnce(ident));
             }
 
-            let field_value = self.read_value_of_type(field_type)?;
+            let field_value =
+                self.read_value_of_type(&format!("{}.{}", field_name, ident), field_type)?;
 
             *field = Some(field_value);
         }
@@ -233,20 +307,24 @@ impl<'a> Parser<'a> {
         Ok((key, value))
     }
 
-    fn read_value_of_type(&mut self, t: &RuntimeTypeBox) -> ParseResult<ReflectValueBox> {
+    fn read_value_of_type(
+        &mut self,
+        field_name: &str,
+        t: &RuntimeTypeBox,
+    ) -> ParseResult<ReflectValueBox> {
         Ok(match t {
             RuntimeTypeBox::Enum(d) => {
                 let value = self.read_enum(&d)?.value();
                 ReflectValueBox::Enum(d.clone(), value)
             }
-            RuntimeTypeBox::U32 => ReflectValueBox::U32(self.read_u32()?),
-            RuntimeTypeBox::U64 => ReflectValueBox::U64(self.read_u64()?),
-            RuntimeTypeBox::I32 => ReflectValueBox::I32(self.read_i32()?),
-            RuntimeTypeBox::I64 => ReflectValueBox::I64(self.read_i64()?),
+            RuntimeTypeBox::U32 => ReflectValueBox::U32(self.read_int(field_name, "uint32")?),
+            RuntimeTypeBox::U64 => ReflectValueBox::U64(self.read_int(field_name, "uint64")?),
+            RuntimeTypeBox::I32 => ReflectValueBox::I32(self.read_int(field_name, "int32")?),
+            RuntimeTypeBox::I64 => ReflectValueBox::I64(self.read_int(field_name, "int64")?),
             RuntimeTypeBox::F32 => ReflectValueBox::F32(self.read_f32()?),
             RuntimeTypeBox::F64 => ReflectValueBox::F64(self.read_f64()?),
             RuntimeTypeBox::Bool => ReflectValueBox::Bool(self.read_bool()?),
-            RuntimeTypeBox::String => ReflectValueBox::String(self.read_string()?),
+            RuntimeTypeBox::String => ReflectValueBox::String(self.read_string(field_name)?),
             RuntimeTypeBox::VecU8 => ReflectValueBox::Bytes(self.read_bytes()?),
             RuntimeTypeBox::Message(m) => ReflectValueBox::Message(self.read_message(&m)?),
         })
@@ -259,7 +337,10 @@ impl<'a> Parser<'a> {
     ) -> ParseResult<()> {
         let field_name = self.next_field_name()?;
 
-        let field = match descriptor.get_field_by_name(&field_name) {
+        let field = match descriptor.get_field_by_name(&field_name).or_else(|| {
+            let alias = self.parse_options.field_aliases.get(&field_name)?;
+            descriptor.get_field_by_name(alias)
+        }) {
             Some(field) => field,
             None => {
                 // TODO: shouldn't unknown fields be quietly skipped?
@@ -267,17 +348,23 @@ impl<'a> Parser<'a> {
             }
         };
 
+        let field_name = field.get_name();
         match field.runtime_field_type() {
+            // Like in other protobuf implementations, singular message field
+            // is merged into existing value rather than replaced
+            RuntimeFieldType::Singular(RuntimeTypeBox::Message(d)) => {
+                self.merge_message(field.mut_message(message), &d)?;
+            }
             RuntimeFieldType::Singular(t) => {
-                let value = self.read_value_of_type(&t)?;
+                let value = self.read_value_of_type(field_name, &t)?;
                 field.set_singular_field(message, value);
             }
             RuntimeFieldType::Repeated(t) => {
-                let value = self.read_value_of_type(&t)?;
+                let value = self.read_value_of_type(field_name, &t)?;
                 field.mut_repeated(message).push(value);
             }
             RuntimeFieldType::Map(k, v) => {
-                let (k, v) = self.read_map_entry(&k, &v)?;
+                let (k, v) = self.read_map_entry(field_name, &k, &v)?;
                 field.mut_map(message).insert(k, v);
             }
         };
@@ -299,10 +386,14 @@ impl<'a> Parser<'a> {
     fn merge(&mut self, message: &mut dyn MessageDyn) -> ParseWithLocResult<()> {
         match self.merge_inner(message) {
             Ok(()) => Ok(()),
-            Err(error) => Err(ParseError {
-                error,
-                loc: self.tokenizer.loc(),
-            }),
+            Err(error) => {
+                let loc = match error {
+                    ParseErrorWithoutLoc::ValueOutOfRange { loc, .. }
+                    | ParseErrorWithoutLoc::InvalidUtf8 { loc, .. } => loc,
+                    _ => self.tokenizer.loc(),
+                };
+                Err(ParseError { error, loc })
+            }
         }
     }
 }
@@ -310,17 +401,32 @@ impl<'a> Parser<'a> {
 /// Parse text format message.
 ///
 /// This function does not check if message required fields are set.
-pub fn merge_from_str(message: &mut dyn MessageDyn, input: &str) -> ParseWithLocResult<()> {
+pub fn merge_from_str_with_options(
+    message: &mut dyn MessageDyn,
+    input: &str,
+    parse_options: &ParseOptions,
+) -> ParseWithLocResult<()> {
     let mut parser = Parser {
         tokenizer: Tokenizer::new(input, ParserLanguage::TextFormat),
+        parse_options: parse_options.clone(),
     };
     parser.merge(message)
 }
 
 /// Parse text format message.
-pub fn parse_from_str<M: Message>(input: &str) -> ParseWithLocResult<M> {
+///
+/// This function does not check if message required fields are set.
+pub fn merge_from_str(message: &mut dyn MessageDyn, input: &str) -> ParseWithLocResult<()> {
+    merge_from_str_with_options(message, input, &ParseOptions::default())
+}
+
+/// Parse text format message.
+pub fn parse_from_str_with_options<M: Message>(
+    input: &str,
+    parse_options: &ParseOptions,
+) -> ParseWithLocResult<M> {
     let mut m = M::new();
-    merge_from_str(&mut m, input)?;
+    merge_from_str_with_options(&mut m, input, parse_options)?;
     if let Err(_) = m.check_initialized() {
         return Err(ParseError {
             error: ParseErrorWithoutLoc::MessageNotInitialized,
@@ -329,3 +435,8 @@ pub fn parse_from_str<M: Message>(input: &str) -> ParseWithLocResult<M> {
     }
     Ok(m)
 }
+
+/// Parse text format message.
+pub fn parse_from_str<M: Message>(input: &str) -> ParseWithLocResult<M> {
+    parse_from_str_with_options(input, &ParseOptions::default())
+}
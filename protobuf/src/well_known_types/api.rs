@@ -226,7 +226,7 @@ impl crate::Message for Api {
             let len = value.compute_size();
             my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
         };
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::DEFAULT) {
             my_size += crate::rt::enum_or_unknown_size(7, self.syntax);
         }
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
@@ -253,7 +253,7 @@ impl crate::Message for Api {
         for v in &self.mixins {
             crate::rt::write_message_field_with_cached_size(6, v, os)?;
         };
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::DEFAULT) {
             os.write_enum(7, crate::ProtobufEnumOrUnknown::value(&self.syntax))?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -304,7 +304,7 @@ impl crate::Clear for Api {
         self.version.clear();
         self.source_context.clear();
         self.mixins.clear();
-        self.syntax = crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2);
+        self.syntax = crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::DEFAULT);
         self.unknown_fields.clear();
     }
 }
@@ -485,7 +485,7 @@ impl crate::Message for Method {
             let len = value.compute_size();
             my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
         };
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::DEFAULT) {
             my_size += crate::rt::enum_or_unknown_size(7, self.syntax);
         }
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
@@ -512,7 +512,7 @@ impl crate::Message for Method {
         for v in &self.options {
             crate::rt::write_message_field_with_cached_size(6, v, os)?;
         };
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::DEFAULT) {
             os.write_enum(7, crate::ProtobufEnumOrUnknown::value(&self.syntax))?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -563,7 +563,7 @@ impl crate::Clear for Method {
         self.response_type_url.clear();
         self.response_streaming = false;
         self.options.clear();
-        self.syntax = crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::SYNTAX_PROTO2);
+        self.syntax = crate::ProtobufEnumOrUnknown::new(crate::well_known_types::Syntax::DEFAULT);
         self.unknown_fields.clear();
     }
 }
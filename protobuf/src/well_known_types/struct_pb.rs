@@ -182,7 +182,7 @@ impl Value {
     pub fn get_null_value(&self) -> NullValue {
         match self.kind {
             ::std::option::Option::Some(value::Kind::null_value(v)) => crate::ProtobufEnumOrUnknown::enum_value_or_default(&v),
-            _ => NullValue::NULL_VALUE,
+            _ => NullValue::DEFAULT,
         }
     }
 
@@ -811,7 +811,7 @@ impl crate::ProtobufEnum for NullValue {
 
 impl ::std::default::Default for NullValue {
     fn default() -> Self {
-        NullValue::NULL_VALUE
+        NullValue::DEFAULT
     }
 }
 
@@ -820,6 +820,9 @@ impl crate::reflect::ProtobufValue for NullValue {
 }
 
 impl NullValue {
+    /// Default value of this enum, also returned by `Default::default()`.
+    pub const DEFAULT: NullValue = NullValue::NULL_VALUE;
+
     fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
         crate::reflect::GeneratedEnumDescriptorData::new_2::<NullValue>("NullValue", 0)
     }
@@ -174,7 +174,7 @@ impl crate::Message for Type {
             let len = v.compute_size();
             my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
         }
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::DEFAULT) {
             my_size += crate::rt::enum_or_unknown_size(6, self.syntax);
         }
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
@@ -198,7 +198,7 @@ impl crate::Message for Type {
         if let Some(v) = self.source_context.as_ref() {
             crate::rt::write_message_field_with_cached_size(5, v, os)?;
         }
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::DEFAULT) {
             os.write_enum(6, crate::ProtobufEnumOrUnknown::value(&self.syntax))?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -247,7 +247,7 @@ impl crate::Clear for Type {
         self.oneofs.clear();
         self.options.clear();
         self.source_context.clear();
-        self.syntax = crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2);
+        self.syntax = crate::ProtobufEnumOrUnknown::new(Syntax::DEFAULT);
         self.unknown_fields.clear();
     }
 }
@@ -450,10 +450,10 @@ impl crate::Message for Field {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.kind != crate::ProtobufEnumOrUnknown::new(field::Kind::TYPE_UNKNOWN) {
+        if self.kind != crate::ProtobufEnumOrUnknown::new(field::Kind::DEFAULT) {
             my_size += crate::rt::enum_or_unknown_size(1, self.kind);
         }
-        if self.cardinality != crate::ProtobufEnumOrUnknown::new(field::Cardinality::CARDINALITY_UNKNOWN) {
+        if self.cardinality != crate::ProtobufEnumOrUnknown::new(field::Cardinality::DEFAULT) {
             my_size += crate::rt::enum_or_unknown_size(2, self.cardinality);
         }
         if self.number != 0 {
@@ -487,10 +487,10 @@ impl crate::Message for Field {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut crate::CodedOutputStream<'_>) -> crate::ProtobufResult<()> {
-        if self.kind != crate::ProtobufEnumOrUnknown::new(field::Kind::TYPE_UNKNOWN) {
+        if self.kind != crate::ProtobufEnumOrUnknown::new(field::Kind::DEFAULT) {
             os.write_enum(1, crate::ProtobufEnumOrUnknown::value(&self.kind))?;
         }
-        if self.cardinality != crate::ProtobufEnumOrUnknown::new(field::Cardinality::CARDINALITY_UNKNOWN) {
+        if self.cardinality != crate::ProtobufEnumOrUnknown::new(field::Cardinality::DEFAULT) {
             os.write_enum(2, crate::ProtobufEnumOrUnknown::value(&self.cardinality))?;
         }
         if self.number != 0 {
@@ -562,8 +562,8 @@ impl crate::Message for Field {
 
 impl crate::Clear for Field {
     fn clear(&mut self) {
-        self.kind = crate::ProtobufEnumOrUnknown::new(field::Kind::TYPE_UNKNOWN);
-        self.cardinality = crate::ProtobufEnumOrUnknown::new(field::Cardinality::CARDINALITY_UNKNOWN);
+        self.kind = crate::ProtobufEnumOrUnknown::new(field::Kind::DEFAULT);
+        self.cardinality = crate::ProtobufEnumOrUnknown::new(field::Cardinality::DEFAULT);
         self.number = 0;
         self.name.clear();
         self.type_url.clear();
@@ -675,7 +675,7 @@ pub mod field {
 
     impl ::std::default::Default for Kind {
         fn default() -> Self {
-            Kind::TYPE_UNKNOWN
+            Kind::DEFAULT
         }
     }
 
@@ -684,6 +684,9 @@ pub mod field {
     }
 
     impl Kind {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: Kind = Kind::TYPE_UNKNOWN;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<Kind>("Field.Kind", 1)
         }
@@ -731,7 +734,7 @@ pub mod field {
 
     impl ::std::default::Default for Cardinality {
         fn default() -> Self {
-            Cardinality::CARDINALITY_UNKNOWN
+            Cardinality::DEFAULT
         }
     }
 
@@ -740,6 +743,9 @@ pub mod field {
     }
 
     impl Cardinality {
+        /// Default value of this enum, also returned by `Default::default()`.
+        pub const DEFAULT: Cardinality = Cardinality::CARDINALITY_UNKNOWN;
+
         pub(in super) fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
             crate::reflect::GeneratedEnumDescriptorData::new_2::<Cardinality>("Field.Cardinality", 2)
         }
@@ -886,7 +892,7 @@ impl crate::Message for Enum {
             let len = v.compute_size();
             my_size += 1 + crate::rt::compute_raw_varint32_size(len) + len;
         }
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::DEFAULT) {
             my_size += crate::rt::enum_or_unknown_size(5, self.syntax);
         }
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
@@ -907,7 +913,7 @@ impl crate::Message for Enum {
         if let Some(v) = self.source_context.as_ref() {
             crate::rt::write_message_field_with_cached_size(4, v, os)?;
         }
-        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2) {
+        if self.syntax != crate::ProtobufEnumOrUnknown::new(Syntax::DEFAULT) {
             os.write_enum(5, crate::ProtobufEnumOrUnknown::value(&self.syntax))?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -954,7 +960,7 @@ impl crate::Clear for Enum {
         self.enumvalue.clear();
         self.options.clear();
         self.source_context.clear();
-        self.syntax = crate::ProtobufEnumOrUnknown::new(Syntax::SYNTAX_PROTO2);
+        self.syntax = crate::ProtobufEnumOrUnknown::new(Syntax::DEFAULT);
         self.unknown_fields.clear();
     }
 }
@@ -1341,7 +1347,7 @@ impl crate::ProtobufEnum for Syntax {
 
 impl ::std::default::Default for Syntax {
     fn default() -> Self {
-        Syntax::SYNTAX_PROTO2
+        Syntax::DEFAULT
     }
 }
 
@@ -1350,6 +1356,9 @@ impl crate::reflect::ProtobufValue for Syntax {
 }
 
 impl Syntax {
+    /// Default value of this enum, also returned by `Default::default()`.
+    pub const DEFAULT: Syntax = Syntax::SYNTAX_PROTO2;
+
     fn generated_enum_descriptor_data() -> crate::reflect::GeneratedEnumDescriptorData {
         crate::reflect::GeneratedEnumDescriptorData::new_2::<Syntax>("Syntax", 0)
     }